@@ -0,0 +1,34 @@
+//! Defines analyses performed over the syntactic tree.
+
+mod asdoc_example_checker;
+pub use asdoc_example_checker::*;
+mod asset_resolver;
+pub use asset_resolver::*;
+mod class_hierarchy;
+pub use class_hierarchy::*;
+mod constant_evaluator;
+pub use constant_evaluator::*;
+mod css_media_environment;
+pub use css_media_environment::*;
+mod css_selector_matcher;
+pub use css_selector_matcher::*;
+mod css_style_validator;
+pub use css_style_validator::*;
+mod css_usage_analysis;
+pub use css_usage_analysis::*;
+mod definition_index;
+pub use definition_index::*;
+mod flex_manifest;
+pub use flex_manifest::*;
+mod import_analysis;
+pub use import_analysis::*;
+mod mxml_language_elements;
+pub use mxml_language_elements::*;
+mod mxml_name_resolver;
+pub use mxml_name_resolver::*;
+mod mxml_states;
+pub use mxml_states::*;
+mod static_type;
+pub use static_type::*;
+mod type_checker;
+pub use type_checker::*;
@@ -0,0 +1,305 @@
+use crate::ns::*;
+use lazy_regex::*;
+
+/// An ActionScript code example extracted from an ASDoc comment.
+pub struct AsDocExample {
+    /// The location of the example within the host compilation unit.
+    pub location: Location,
+    /// The compilation unit consisting of the example source text.
+    pub embedded: Rc<EmbeddedCompilationUnit>,
+    pub content: AsDocExampleContent,
+}
+
+pub enum AsDocExampleContent {
+    /// An example containing a package, parsed as a program.
+    Program(Rc<Program>),
+    Directives(Vec<Rc<Directive>>),
+}
+
+/// Verifies the ActionScript code examples contained in ASDoc comments.
+///
+/// Examples are either fenced by triple backticks or given through the `<listing>`
+/// element of an `@example` tag, whose XML entities, such as `&lt;`, are unescaped.
+/// The prose of an `@example` tag without a `<listing>` element is not checked.
+/// Fences whose info string contains `ignore` or `text`, or whose language is not
+/// ActionScript, are skipped.
+///
+/// Syntax errors within an example are contributed to the compilation unit
+/// of the ASDoc comment, at the corresponding location within the comment.
+pub struct AsDocExampleChecker;
+
+impl AsDocExampleChecker {
+    /// Checks the examples of every ASDoc comment in a program.
+    pub fn check_program(program: &Rc<Program>) -> Vec<AsDocExample> {
+        let mut asdocs = vec![];
+        for pckg in program.packages.iter() {
+            collect_package_asdocs(pckg, &mut asdocs);
+        }
+        for directive in program.directives.iter() {
+            collect_directive_asdocs(directive, &mut asdocs);
+        }
+        asdocs.iter().flat_map(Self::check_asdoc).collect()
+    }
+
+    /// Checks the examples of every ASDoc comment in a directive sequence.
+    pub fn check_directives(directives: &[Rc<Directive>]) -> Vec<AsDocExample> {
+        let mut asdocs = vec![];
+        for directive in directives.iter() {
+            collect_directive_asdocs(directive, &mut asdocs);
+        }
+        asdocs.iter().flat_map(Self::check_asdoc).collect()
+    }
+
+    /// Checks the examples of an ASDoc comment.
+    pub fn check_asdoc(asdoc: &Rc<AsDoc>) -> Vec<AsDocExample> {
+        let host = asdoc.location.compilation_unit();
+        let mut examples = vec![];
+        for fragments in Self::extract_examples(asdoc) {
+            let first_offset = fragments[0].1;
+            let last = fragments.last().unwrap();
+            let last_offset = last.1 + last.0.trim_end().len();
+            let embedded = Rc::new(EmbeddedCompilationUnit::new(&host, &fragments));
+            let compilation_unit = embedded.compilation_unit();
+            let content = if regex_is_match!(r"(?m)^\s*package\b", compilation_unit.text()) {
+                AsDocExampleContent::Program(ParserFacade(&compilation_unit, default()).parse_program())
+            } else {
+                AsDocExampleContent::Directives(ParserFacade(&compilation_unit, default()).parse_directives(ParserDirectiveContext::TopLevel))
+            };
+            embedded.transfer_diagnostics();
+            examples.push(AsDocExample {
+                location: Location::with_offsets(&host, first_offset, last_offset.max(first_offset)),
+                embedded,
+                content,
+            });
+        }
+        examples
+    }
+
+    /// Extracts the code examples of an ASDoc comment, each as a list
+    /// of line fragments consisting of a text and a host offset.
+    pub fn extract_examples(asdoc: &AsDoc) -> Vec<Vec<(String, usize)>> {
+        let lines = split_comment_lines(&asdoc.location);
+        let mut examples = vec![];
+        let mut i = 0;
+        while i < lines.len() {
+            let (line, offset) = &lines[i];
+
+            // ```info
+            if let Some((_, info)) = regex_captures!(r"^\s*```\s*([^`]*)$", line) {
+                let mut fragments = vec![];
+                i += 1;
+                while i < lines.len() && !regex_is_match!(r"^\s*```\s*$", &lines[i].0) {
+                    fragments.push((lines[i].0.clone() + "\n", lines[i].1));
+                    i += 1;
+                }
+                if is_actionscript_fence_info(info) && !is_blank(&fragments) {
+                    examples.push(fragments);
+                }
+            // @example
+            } else if let Some((_, prefix, content)) = regex_captures!(r"^(\s*@example)(.*)", line) {
+                let mut block = vec![(content.to_owned(), offset + prefix.len())];
+                let mut j = i + 1;
+                while j < lines.len() && !regex_is_match!(r"^\s*@[^\s]", &lines[j].0) {
+                    block.push(lines[j].clone());
+                    j += 1;
+                }
+                // Fenced examples inside the tag are handled separately
+                if !block.iter().any(|(line, _)| regex_is_match!(r"^\s*```", line)) {
+                    if let Some(fragments) = extract_listing(&block).filter(|fragments| !is_blank(fragments)) {
+                        examples.push(fragments);
+                    }
+                    i = j - 1;
+                }
+            }
+            i += 1;
+        }
+        examples
+    }
+}
+
+/// Splits the content of an ASDoc comment into lines, stripping
+/// the leading asterisk of each line.
+fn split_comment_lines(location: &Location) -> Vec<(String, usize)> {
+    let text = location.compilation_unit().text().clone();
+    let first_offset = location.first_offset() + 3;
+    let last_offset = decrease_last_offset(first_offset, location.last_offset(), 2);
+    let mut lines = vec![];
+    let mut line_offset = first_offset;
+    let mut characters = text[first_offset..last_offset].char_indices().peekable();
+    while let Some((i, ch)) = characters.next() {
+        if CharacterValidator::is_line_terminator(ch) {
+            lines.push((text[line_offset..(first_offset + i)].to_owned(), line_offset));
+            line_offset = first_offset + i + ch.len_utf8();
+            // <CR><LF> sequence
+            if ch == '\r' && characters.peek().map(|(_, ch)| *ch) == Some('\n') {
+                characters.next();
+                line_offset += 1;
+            }
+        }
+    }
+    lines.push((text[line_offset..last_offset].to_owned(), line_offset));
+    for (line, offset) in lines.iter_mut() {
+        if let Some((prefix, _)) = regex_captures!(r"^\s*(\*\s?)", line) {
+            *offset += prefix.len();
+            *line = line[prefix.len()..].to_owned();
+        }
+    }
+    lines
+}
+
+/// Extracts the content of a `<listing>` element from the lines of an `@example` tag.
+fn extract_listing(block: &[(String, usize)]) -> Option<Vec<(String, usize)>> {
+    let mut fragments = vec![];
+    let mut inside = false;
+    for (line, offset) in block.iter() {
+        let mut start = 0;
+        if !inside {
+            let Some(opening) = regex_find!(r"<listing[^>]*>", line) else {
+                continue;
+            };
+            start = line.find(opening).unwrap() + opening.len();
+            inside = true;
+        }
+        if let Some(end) = line[start..].find("</listing>") {
+            fragments.extend(unescape_fragment(&line[start..(start + end)], offset + start));
+            return Some(fragments);
+        }
+        fragments.extend(unescape_fragment(&line[start..], offset + start));
+    }
+    if inside { Some(fragments) } else { None }
+}
+
+/// Unescapes the XML entities of a listing line, splitting it into fragments
+/// at each entity so that the following text keeps its host offset.
+fn unescape_fragment(line: &str, offset: usize) -> Vec<(String, usize)> {
+    let mut fragments = vec![];
+    let mut start = 0;
+    for entity in regex!(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-zA-Z][a-zA-Z0-9]*);").find_iter(line) {
+        if entity.start() > start {
+            fragments.push((line[start..entity.start()].to_owned(), offset + start));
+        }
+        fragments.push((unescape_xml(entity.as_str()), offset + entity.start()));
+        start = entity.end();
+    }
+    fragments.push((line[start..].to_owned() + "\n", offset + start));
+    fragments
+}
+
+fn is_actionscript_fence_info(info: &str) -> bool {
+    let words: Vec<String> = info.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if words.iter().any(|w| ["ignore", "text"].contains(&w.as_str())) {
+        return false;
+    }
+    words.first().is_none_or(|w| ["as", "as3", "actionscript", "actionscript3"].contains(&w.as_str()))
+}
+
+fn is_blank(fragments: &[(String, usize)]) -> bool {
+    fragments.iter().all(|(text, _)| text.trim().is_empty())
+}
+
+fn collect_package_asdocs(pckg: &Rc<PackageDefinition>, output: &mut Vec<Rc<AsDoc>>) {
+    if let Some(asdoc) = &pckg.asdoc {
+        output.push(asdoc.clone());
+    }
+    for directive in pckg.block.directives.iter() {
+        collect_directive_asdocs(directive, output);
+    }
+}
+
+fn collect_directive_asdocs(directive: &Rc<Directive>, output: &mut Vec<Rc<AsDoc>>) {
+    let (asdoc, attributes, block): (Option<Rc<AsDoc>>, &[Attribute], Option<Rc<Block>>) = match directive.as_ref() {
+        Directive::VariableDefinition(d) => (d.asdoc.clone(), &d.attributes, None),
+        Directive::FunctionDefinition(d) => (d.asdoc.clone(), &d.attributes, None),
+        Directive::ClassDefinition(d) => (d.asdoc.clone(), &d.attributes, Some(d.block.clone())),
+        Directive::InterfaceDefinition(d) => (d.asdoc.clone(), &d.attributes, Some(d.block.clone())),
+        Directive::EnumDefinition(d) => (d.asdoc.clone(), &d.attributes, Some(d.block.clone())),
+        Directive::TypeDefinition(d) => (d.asdoc.clone(), &d.attributes, None),
+        Directive::NamespaceDefinition(d) => (d.asdoc.clone(), &d.attributes, None),
+        Directive::Block(d) => {
+            for directive in d.directives.iter() {
+                collect_directive_asdocs(directive, output);
+            }
+            return;
+        },
+        Directive::IfStatement(d) => {
+            collect_directive_asdocs(&d.consequent, output);
+            if let Some(alternative) = &d.alternative {
+                collect_directive_asdocs(alternative, output);
+            }
+            return;
+        },
+        Directive::ConfigurationDirective(d) => {
+            collect_directive_asdocs(&d.directive, output);
+            return;
+        },
+        Directive::NormalConfigurationDirective(d) => {
+            collect_directive_asdocs(&d.directive, output);
+            return;
+        },
+        Directive::IncludeDirective(d) => {
+            for pckg in d.nested_packages.iter() {
+                collect_package_asdocs(pckg, output);
+            }
+            for directive in d.nested_directives.iter() {
+                collect_directive_asdocs(directive, output);
+            }
+            return;
+        },
+        _ => return,
+    };
+    if let Some(asdoc) = asdoc {
+        output.push(asdoc);
+    }
+    for metadata in Attribute::find_metadata(attributes) {
+        if let Some(asdoc) = &metadata.asdoc {
+            output.push(asdoc.clone());
+        }
+    }
+    if let Some(block) = block {
+        for directive in block.directives.iter() {
+            collect_directive_asdocs(directive, output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_promise_examples() {
+        let compilation_unit = CompilationUnit::new(None, include_str!("../../../as3/promise/Promise.as").to_owned());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        assert!(!compilation_unit.invalidated());
+        let examples = AsDocExampleChecker::check_program(&program);
+        assert!(examples.len() >= 2);
+        assert!(!compilation_unit.invalidated());
+    }
+
+    #[test]
+    fn test_mapped_location() {
+        let source = "/**\n * ```\n * var x = ;\n * ```\n */\nvar y;";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        let examples = AsDocExampleChecker::check_program(&program);
+        assert_eq!(examples.len(), 1);
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location().first_line_number(), 3);
+        assert_eq!(diagnostics[0].location().first_column(), 11);
+    }
+
+    #[test]
+    fn test_listing_entities() {
+        let source = "/**\n * @example Compares two values.\n * <listing>\n * if (a &lt; b &amp;&amp; c) { var x = ; }\n * </listing>\n */\nvar y;\n/**\n * @example This example is described in prose only.\n */\nvar z;";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        let examples = AsDocExampleChecker::check_program(&program);
+        assert_eq!(examples.len(), 1);
+        assert!(examples[0].embedded.compilation_unit().text().contains("if (a < b && c)"));
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location().first_line_number(), 4);
+        assert_eq!(diagnostics[0].location().first_column(), 40);
+    }
+}
@@ -0,0 +1,363 @@
+use crate::ns::*;
+use file_paths::FlexPath;
+
+/// An asset file referenced by `Embed(...)` or `url(...)` in CSS,
+/// or by `[Embed]` metadata.
+#[derive(Clone)]
+pub struct AssetReference {
+    /// Location of the `Embed(...)`, `url(...)` or `[Embed]` reference.
+    pub location: Location,
+    pub source: (String, Location),
+    /// The resolved file path, or `None` if the file was not found.
+    pub file_path: Option<String>,
+    pub mime_type: Option<String>,
+    pub symbol: Option<String>,
+}
+
+/// Resolves the asset files referenced by `Embed(...)` and `url(...)`
+/// in CSS and by `[Embed]` metadata, validating `Embed` entries.
+///
+/// Sources are resolved relative to the directory of the referencing
+/// compilation unit, or to the working directory when the compilation unit
+/// is not a file. Sources starting with a slash are resolved against
+/// `source_paths`. Absolute URLs such as `http://...` and `data:` URLs
+/// are not assets and are skipped.
+///
+/// The following are reported to the referencing compilation unit:
+///
+/// - Asset files that do not exist, as verify errors.
+/// - `Embed` references without a source, as verify errors.
+/// - Unknown `Embed` entries and entry values of invalid type,
+///   such as a non-numeric `scaleGridTop`, as warnings.
+///
+/// # Example
+///
+/// ```no_run
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(Some("src/defaults.css".into()), std::fs::read_to_string("src/defaults.css").unwrap());
+/// let document = CssParserFacade(&compilation_unit, default()).parse_document();
+///
+/// let mut resolver = AssetResolver::new();
+/// resolver.add_style_sheet(&document);
+/// for file_path in resolver.file_paths() {
+///     println!("{file_path}");
+/// }
+/// ```
+#[derive(Default)]
+pub struct AssetResolver {
+    /// Source paths that sources starting with a slash are resolved against.
+    pub source_paths: Vec<String>,
+    references: Vec<AssetReference>,
+}
+
+/// An optional key and a value of an `Embed` reference.
+type EmbedEntry = (Option<(String, Location)>, (String, Location));
+
+/// Value types of `Embed` entries.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum EmbedEntryType {
+    String,
+    Number,
+    Boolean,
+}
+
+impl AssetResolver {
+    /// Entries recognized in `Embed(...)` and `[Embed]` metadata.
+    const EMBED_ENTRIES: [(&'static str, EmbedEntryType); 20] = [
+        ("source", EmbedEntryType::String),
+        ("mimeType", EmbedEntryType::String),
+        ("symbol", EmbedEntryType::String),
+        ("scaleGridTop", EmbedEntryType::Number),
+        ("scaleGridBottom", EmbedEntryType::Number),
+        ("scaleGridLeft", EmbedEntryType::Number),
+        ("scaleGridRight", EmbedEntryType::Number),
+        ("fontName", EmbedEntryType::String),
+        ("fontFamily", EmbedEntryType::String),
+        ("fontStyle", EmbedEntryType::String),
+        ("fontWeight", EmbedEntryType::String),
+        ("fontStretch", EmbedEntryType::String),
+        ("unicodeRange", EmbedEntryType::String),
+        ("embedAsCFF", EmbedEntryType::Boolean),
+        ("advancedAntiAliasing", EmbedEntryType::Boolean),
+        ("flashType", EmbedEntryType::Boolean),
+        ("compression", EmbedEntryType::Boolean),
+        ("smoothing", EmbedEntryType::Boolean),
+        ("quality", EmbedEntryType::Number),
+        ("encoding", EmbedEntryType::String),
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The asset references, in the order they were added.
+    pub fn references(&self) -> &[AssetReference] {
+        &self.references
+    }
+
+    /// The file paths of the assets found, sorted and without duplicates.
+    pub fn file_paths(&self) -> Vec<String> {
+        let mut file_paths: Vec<String> = self.references.iter().filter_map(|reference| reference.file_path.clone()).collect();
+        file_paths.sort();
+        file_paths.dedup();
+        file_paths
+    }
+
+    /// Adds the assets referenced by the property values of a style
+    /// sheet, including `@font-face` rules and `@media` queries.
+    pub fn add_style_sheet(&mut self, document: &CssDocument) {
+        for directive in document.directives.iter() {
+            match directive.as_ref() {
+                CssDirective::Rule(rule) => self.add_css_properties(&rule.properties),
+                CssDirective::FontFace(font_face) => self.add_css_properties(&font_face.properties),
+                CssDirective::MediaQuery(query) => {
+                    for rule in query.rules.iter() {
+                        self.add_css_properties(&rule.properties);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn add_css_properties(&mut self, properties: &[Rc<CssProperty>]) {
+        for property in properties.iter() {
+            self.add_css_value(&property.value);
+        }
+    }
+
+    fn add_css_value(&mut self, value: &CssPropertyValue) {
+        match value {
+            CssPropertyValue::Array(array) => {
+                for element in array.elements.iter() {
+                    self.add_css_value(element);
+                }
+            },
+            CssPropertyValue::MultiValue(multi_value) => {
+                for value in multi_value.values.iter() {
+                    self.add_css_value(value);
+                }
+            },
+            CssPropertyValue::Url(url) => {
+                let source = &url.url.0;
+                if source.is_empty() || source.starts_with("data:") || source.contains("://") {
+                    return;
+                }
+                self.add_reference(&url.location, url.url.clone(), None, None);
+            },
+            CssPropertyValue::Embed(embed) => {
+                let entries: Vec<EmbedEntry> = embed.entries.iter()
+                    .map(|entry| (entry.key.clone(), entry.value.clone()))
+                    .collect();
+                self.add_embed(&embed.location, &entries);
+            },
+            _ => {},
+        }
+    }
+
+    /// Adds the asset referenced by `[Embed]` metadata;
+    /// other metadata are ignored.
+    pub fn add_metadata(&mut self, metadata: &Metadata) {
+        if metadata.name.0 != "Embed" {
+            return;
+        }
+        let entries: Vec<EmbedEntry> = metadata.entries.iter().flatten().map(|entry| {
+            let (MetadataValue::String(value) | MetadataValue::IdentifierString(value)) = entry.value.as_ref();
+            (entry.key.clone(), value.clone())
+        }).collect();
+        self.add_embed(&metadata.location, &entries);
+    }
+
+    /// Adds the assets referenced by the `[Embed]` metadata
+    /// of the definitions of a program.
+    pub fn add_program(&mut self, program: &Program) {
+        TreeWalker::walk_program(self, program);
+    }
+
+    /// Adds the assets referenced by the scripts and style sheets
+    /// of a MXML document.
+    pub fn add_mxml(&mut self, document: &Mxml) {
+        for content in document.content.iter() {
+            if let MxmlContent::Element(element) = content.as_ref() {
+                self.add_mxml_element(element);
+            }
+        }
+    }
+
+    fn add_mxml_element(&mut self, element: &MxmlElement) {
+        if let Some(directives) = &element.script_directives {
+            TreeWalker::walk_directives(self, directives);
+        }
+        if let Some(document) = &element.style_document {
+            self.add_style_sheet(document);
+        }
+        for content in element.content.iter().flatten() {
+            if let MxmlContent::Element(child) = content.as_ref() {
+                self.add_mxml_element(child);
+            }
+        }
+    }
+
+    /// Validates the entries of an `Embed` reference, where a keyless
+    /// entry is the source, and adds the referenced asset.
+    fn add_embed(&mut self, location: &Location, entries: &[EmbedEntry]) {
+        let compilation_unit = location.compilation_unit();
+        let mut source = None;
+        let mut mime_type = None;
+        let mut symbol = None;
+        for (key, value) in entries.iter() {
+            let Some((key, key_location)) = key else {
+                source = Some(value.clone());
+                continue;
+            };
+            let Some((_, entry_type)) = Self::EMBED_ENTRIES.iter().find(|(name, _)| name == key) else {
+                compilation_unit.add_diagnostic(Diagnostic::new_warning(key_location, DiagnosticKind::UnknownEmbedEntry, diagarg![key.clone()]));
+                continue;
+            };
+            let (valid, expected) = match entry_type {
+                EmbedEntryType::String => (true, ""),
+                EmbedEntryType::Number => (value.0.trim().parse::<f64>().is_ok(), "a number"),
+                EmbedEntryType::Boolean => (value.0 == "true" || value.0 == "false", "true or false"),
+            };
+            if !valid {
+                compilation_unit.add_diagnostic(Diagnostic::new_warning(&value.1, DiagnosticKind::InvalidEmbedEntryValue, diagarg![key.clone(), expected.to_owned()]));
+                continue;
+            }
+            match key.as_str() {
+                "source" => source = Some(value.clone()),
+                "mimeType" => mime_type = Some(value.0.clone()),
+                "symbol" => symbol = Some(value.0.clone()),
+                _ => {},
+            }
+        }
+        let Some(source) = source.filter(|(source, _)| !source.is_empty()) else {
+            compilation_unit.add_diagnostic(Diagnostic::new_verify_error(location, DiagnosticKind::MissingEmbedSource, vec![]));
+            return;
+        };
+        self.add_reference(location, source, mime_type, symbol);
+    }
+
+    fn add_reference(&mut self, location: &Location, source: (String, Location), mime_type: Option<String>, symbol: Option<String>) {
+        let compilation_unit = location.compilation_unit();
+        let file_path = match self.resolve(&source.0, &compilation_unit) {
+            Ok(file_path) => Some(file_path),
+            Err(error) => {
+                compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&source.1, match error {
+                    SourceResolutionError::ParentSourceIsNotAFile => DiagnosticKind::ParentSourceIsNotAFile,
+                    SourceResolutionError::FailedToReadFile => DiagnosticKind::AssetNotFound,
+                }, diagarg![source.0.clone()]));
+                None
+            },
+        };
+        self.references.push(AssetReference {
+            location: location.clone(),
+            source,
+            file_path,
+            mime_type,
+            symbol,
+        });
+    }
+
+    /// Resolves the file path of an asset, failing if the file does not exist.
+    pub fn resolve(&self, source: &str, origin: &Rc<CompilationUnit>) -> Result<String, SourceResolutionError> {
+        if let Some(source) = source.strip_prefix('/') {
+            return self.source_paths.iter()
+                .map(|source_path| FlexPath::from_n_native([source_path.as_ref(), source]).to_string_with_flex_separator())
+                .find(|file_path| std::path::Path::new(file_path).is_file())
+                .ok_or(SourceResolutionError::FailedToReadFile);
+        }
+        let origin_file_path = if let Some(file_path) = origin.file_path() {
+            file_path
+        } else if let Ok(dir) = std::env::current_dir() {
+            FlexPath::from_n_native([dir.to_string_lossy().as_ref(), "_"]).to_string_with_flex_separator()
+        } else {
+            return Err(SourceResolutionError::ParentSourceIsNotAFile);
+        };
+        let file_path = FlexPath::from_n_native([origin_file_path.as_ref(), "..", source]).to_string_with_flex_separator();
+        if std::path::Path::new(&file_path).is_file() {
+            Ok(file_path)
+        } else {
+            Err(SourceResolutionError::FailedToReadFile)
+        }
+    }
+}
+
+impl TreeVisitor for AssetResolver {
+    fn visit_directive(&mut self, directive: &Rc<Directive>) -> bool {
+        let attributes = match directive.as_ref() {
+            Directive::VariableDefinition(defn) => &defn.attributes,
+            Directive::FunctionDefinition(defn) => &defn.attributes,
+            Directive::ClassDefinition(defn) => &defn.attributes,
+            Directive::EnumDefinition(defn) => &defn.attributes,
+            Directive::InterfaceDefinition(defn) => &defn.attributes,
+            _ => return true,
+        };
+        for metadata in Attribute::find_metadata(attributes) {
+            self.add_metadata(&metadata);
+        }
+        true
+    }
+
+    fn visit_expression(&mut self, _expression: &Rc<Expression>) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+    use file_paths::FlexPath;
+
+    #[test]
+    fn test_asset_resolution() {
+        let dir = std::env::temp_dir().join("as3_parser_asset_resolver_test");
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("assets/ok.png"), [0x89, 0x50]).unwrap();
+        std::fs::write(dir.join("assets/skin.swf"), [0x46, 0x57]).unwrap();
+        std::fs::write(dir.join("font.ttf"), [0]).unwrap();
+        let dir = FlexPath::new_native(dir.to_string_lossy().as_ref()).to_string_with_flex_separator();
+
+        let css = CompilationUnit::new(Some(format!("{dir}/styles/defaults.css")), r#"
+            @font-face { src: url("../font.ttf"); fontFamily: Main }
+            .ok { icon: Embed("../assets/ok.png"); background: url(http://example.com/a.png) }
+            .skin { skin: Embed(source="/assets/skin.swf", symbol="Up", scaleGridTop="four", color="red") }
+            .missing { icon: Embed(source="../assets/missing.png"), Embed(mimeType="image/png") }
+        "#.into());
+        let document = CssParserFacade(&css, default()).parse_document();
+
+        let program = CompilationUnit::new(Some(format!("{dir}/src/Main.as")), r#"
+            package {
+                public class Main {
+                    [Embed(source="../assets/ok.png", mimeType="image/png")]
+                    private static const Ok: Class;
+                }
+            }
+        "#.into());
+        let program = ParserFacade(&program, default()).parse_program();
+
+        let mut resolver = AssetResolver::new();
+        resolver.source_paths.push(dir.clone());
+        resolver.add_style_sheet(&document);
+        resolver.add_program(&program);
+
+        let references = resolver.references();
+        assert_eq!(references.len(), 5);
+        assert_eq!(references[2].symbol.as_deref(), Some("Up"));
+        assert!(references[3].file_path.is_none());
+        assert_eq!(references[4].mime_type.as_deref(), Some("image/png"));
+        assert_eq!(resolver.file_paths(), [
+            format!("{dir}/assets/ok.png"),
+            format!("{dir}/assets/skin.swf"),
+            format!("{dir}/font.ttf"),
+        ]);
+
+        let diagnostics = css.diagnostics();
+        assert_eq!(diagnostics.len(), 4);
+        assert!(diagnostics[0].kind() == DiagnosticKind::InvalidEmbedEntryValue);
+        assert!(diagnostics[1].kind() == DiagnosticKind::UnknownEmbedEntry);
+        assert!(diagnostics[2].kind() == DiagnosticKind::AssetNotFound);
+        assert_eq!(diagnostics[2].location().text(), "\"../assets/missing.png\"");
+        assert!(diagnostics[3].kind() == DiagnosticKind::MissingEmbedSource);
+    }
+}
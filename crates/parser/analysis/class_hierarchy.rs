@@ -0,0 +1,577 @@
+use crate::ns::*;
+
+/// Kinds of instance members recorded by a [`ClassHierarchy`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClassMemberKind {
+    Method,
+    Getter,
+    Setter,
+    Variable,
+}
+
+impl ClassMemberKind {
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Method => "method",
+            Self::Getter => "getter",
+            Self::Setter => "setter",
+            Self::Variable => "variable",
+        }
+    }
+}
+
+/// An instance member of a class or interface.
+#[derive(Clone)]
+pub struct ClassMember {
+    pub name: (String, Location),
+    pub kind: ClassMemberKind,
+    pub is_override: bool,
+    pub is_private: bool,
+}
+
+/// A reference to a supertype, such as the expression
+/// of an `extends` clause.
+#[derive(Clone)]
+struct TypeReference {
+    /// The dotted name as written, such as `Sprite` or `flash.display.Sprite`.
+    name: String,
+    location: Location,
+}
+
+struct HierarchyType {
+    name: (String, Location),
+    package_name: String,
+    is_interface: bool,
+    is_final: bool,
+    imports: Rc<Vec<ImportDirective>>,
+    /// The type of the `extends` clause of a class,
+    /// or the types of the `extends` clause of an interface.
+    extends_clause: Vec<TypeReference>,
+    implements_clause: Vec<TypeReference>,
+    members: Vec<ClassMember>,
+}
+
+/// An index of the class and interface definitions of a project
+/// along with their inheritance relationships.
+///
+/// Type names in `extends` and `implements` clauses are resolved through
+/// the imports of the enclosing package, the enclosing package itself and
+/// the top-level package, in that order. Types not added to the hierarchy,
+/// such as those of libraries, are considered unknown and are not checked.
+///
+/// `verify()` reports the following as verify errors:
+///
+/// - Circular inheritance.
+/// - Extension of a `final` class.
+/// - A class extending an interface, and a class implementing
+///   or an interface extending a class.
+/// - Interface methods, getters and setters not implemented by
+///   a class or its superclasses, with a matching kind.
+/// - Redefinitions of superclass members without the `override`
+///   attribute, and `override` members redefining no superclass member.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, r#"
+///     package com.acme {
+///         public interface IShape { function get area(): Number; }
+///         public class Shape implements IShape { public function get area(): Number { return 0 } }
+///         public class Circle extends Shape { override public function get area(): Number { return 1 } }
+///     }
+/// "#.into());
+/// let program = ParserFacade(&compilation_unit, default()).parse_program();
+///
+/// let mut hierarchy = ClassHierarchy::new();
+/// hierarchy.add_program(&program);
+/// hierarchy.verify();
+/// assert!(compilation_unit.diagnostics().is_empty());
+///
+/// assert_eq!(hierarchy.superclass("com.acme.Circle"), Some("com.acme.Shape".into()));
+/// assert_eq!(hierarchy.supertypes("com.acme.Circle"), ["com.acme.Shape", "com.acme.IShape"]);
+/// assert_eq!(hierarchy.subtypes("com.acme.IShape"), ["com.acme.Circle", "com.acme.Shape"]);
+/// assert!(hierarchy.is_subtype("com.acme.Circle", "com.acme.IShape"));
+/// ```
+#[derive(Default)]
+pub struct ClassHierarchy {
+    types: HashMap<String, HierarchyType>,
+}
+
+impl ClassHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the class and interface definitions of the packages of a program.
+    pub fn add_program(&mut self, program: &Program) {
+        for package in program.packages.iter() {
+            let package_name = package.name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".");
+            let imports: Rc<Vec<ImportDirective>> = Rc::new(package.block.directives.iter().filter_map(|directive| {
+                if let Directive::ImportDirective(import) = directive.as_ref() { Some(import.clone()) } else { None }
+            }).collect());
+            for directive in package.block.directives.iter() {
+                self.add_definition(directive, &package_name, &imports);
+            }
+        }
+    }
+
+    fn add_definition(&mut self, directive: &Rc<Directive>, package_name: &str, imports: &Rc<Vec<ImportDirective>>) {
+        let (name, is_interface, is_final, extends_clause, implements_clause, block) = match directive.as_ref() {
+            Directive::ClassDefinition(defn) => (
+                &defn.name,
+                false,
+                Attribute::find_final(&defn.attributes).is_some(),
+                defn.extends_clause.iter().cloned().collect::<Vec<_>>(),
+                defn.implements_clause.clone().unwrap_or_default(),
+                &defn.block,
+            ),
+            Directive::InterfaceDefinition(defn) => (
+                &defn.name,
+                true,
+                false,
+                defn.extends_clause.clone().unwrap_or_default(),
+                vec![],
+                &defn.block,
+            ),
+            _ => return,
+        };
+        let mut members = vec![];
+        for directive in block.directives.iter() {
+            collect_members(directive, &mut members);
+        }
+        self.types.insert(qualify(package_name, &name.0), HierarchyType {
+            name: name.clone(),
+            package_name: package_name.to_owned(),
+            is_interface,
+            is_final,
+            imports: imports.clone(),
+            extends_clause: extends_clause.iter().filter_map(type_reference).collect(),
+            implements_clause: implements_clause.iter().filter_map(type_reference).collect(),
+            members,
+        });
+    }
+
+    /// Indicates whether a class or interface is part of the hierarchy.
+    pub fn has_type(&self, qualified_name: &str) -> bool {
+        self.types.contains_key(qualified_name)
+    }
+
+    pub fn is_interface(&self, qualified_name: &str) -> bool {
+        self.types.get(qualified_name).is_some_and(|t| t.is_interface)
+    }
+
+    /// Returns the instance members defined by a class or interface,
+    /// excluding inherited members.
+    pub fn members(&self, qualified_name: &str) -> &[ClassMember] {
+        self.types.get(qualified_name).map_or(&[], |t| &t.members)
+    }
+
+    /// Returns the superclass of a class, if it is part of the hierarchy.
+    pub fn superclass(&self, qualified_name: &str) -> Option<String> {
+        let t = self.types.get(qualified_name)?;
+        if t.is_interface {
+            return None;
+        }
+        self.resolve(t, t.extends_clause.first()?)
+    }
+
+    /// Returns the interfaces a class implements or an interface
+    /// extends directly, that are part of the hierarchy.
+    pub fn interfaces(&self, qualified_name: &str) -> Vec<String> {
+        let Some(t) = self.types.get(qualified_name) else {
+            return vec![];
+        };
+        let references = if t.is_interface { &t.extends_clause } else { &t.implements_clause };
+        references.iter().filter_map(|reference| self.resolve(t, reference)).collect()
+    }
+
+    /// Returns the direct supertypes of a type: its superclass
+    /// followed by its interfaces.
+    fn direct_supertypes(&self, qualified_name: &str) -> Vec<String> {
+        let mut result: Vec<String> = self.superclass(qualified_name).into_iter().collect();
+        result.extend(self.interfaces(qualified_name));
+        result
+    }
+
+    /// Returns every supertype of a type: its superclasses, nearest first,
+    /// followed by the interfaces it implements, directly or indirectly.
+    pub fn supertypes(&self, qualified_name: &str) -> Vec<String> {
+        let mut classes = vec![];
+        let mut visited = HashSet::from([qualified_name.to_owned()]);
+        let mut class = self.superclass(qualified_name);
+        while let Some(c) = class {
+            if !visited.insert(c.clone()) {
+                break;
+            }
+            class = self.superclass(&c);
+            classes.push(c);
+        }
+        let mut interfaces = vec![];
+        let mut pending: Vec<String> = std::iter::once(qualified_name.to_owned()).chain(classes.iter().cloned()).collect();
+        while !pending.is_empty() {
+            let mut next = vec![];
+            for t in pending.iter() {
+                for interface in self.interfaces(t) {
+                    if visited.insert(interface.clone()) {
+                        interfaces.push(interface.clone());
+                        next.push(interface);
+                    }
+                }
+            }
+            pending = next;
+        }
+        classes.extend(interfaces);
+        classes
+    }
+
+    /// Returns the names of the supertype references of a type and of its
+    /// supertypes that are not part of the hierarchy, such as `Error`
+    /// in `class MyError extends Error`.
+    pub fn unresolved_supertypes(&self, qualified_name: &str) -> Vec<String> {
+        let mut result = vec![];
+        for name in std::iter::once(qualified_name.to_owned()).chain(self.supertypes(qualified_name)) {
+            let Some(t) = self.types.get(&name) else {
+                continue;
+            };
+            for reference in t.extends_clause.iter().chain(t.implements_clause.iter()) {
+                if self.resolve(t, reference).is_none() {
+                    result.push(reference.name.clone());
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns every type that is a subtype of a type, sorted by name.
+    pub fn subtypes(&self, qualified_name: &str) -> Vec<String> {
+        let mut result: Vec<String> = self.types.keys()
+            .filter(|t| *t != qualified_name && self.is_subtype(t, qualified_name))
+            .cloned()
+            .collect();
+        result.sort();
+        result
+    }
+
+    /// Indicates whether a type is the same as or a subtype of another type.
+    pub fn is_subtype(&self, qualified_name: &str, supertype: &str) -> bool {
+        qualified_name == supertype || self.supertypes(qualified_name).iter().any(|t| t == supertype)
+    }
+
+    /// Resolves a supertype reference of a type.
+    fn resolve(&self, t: &HierarchyType, reference: &TypeReference) -> Option<String> {
+        self.resolve_name(&reference.name, &t.package_name, &t.imports)
+    }
+
+    /// Resolves a possibly dotted type name appearing in a package
+    /// with the given imports to a type of the hierarchy.
+    pub fn resolve_name(&self, name: &str, package_name: &str, imports: &[ImportDirective]) -> Option<String> {
+        if name.contains('.') {
+            return self.types.contains_key(name).then(|| name.to_owned());
+        }
+        let mut candidates = vec![];
+        for import in imports.iter() {
+            let import_package_name = import.package_name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".");
+            match &import.import_specifier {
+                ImportSpecifier::Identifier((imported, _)) => {
+                    let local_name = import.alias.as_ref().map_or(imported, |(alias, _)| alias);
+                    if local_name == name {
+                        candidates.insert(0, qualify(&import_package_name, imported));
+                    }
+                },
+                ImportSpecifier::Wildcard(_) if import.alias.is_none() => {
+                    candidates.push(qualify(&import_package_name, name));
+                },
+                _ => {},
+            }
+        }
+        candidates.push(qualify(package_name, name));
+        candidates.push(name.to_owned());
+        candidates.into_iter().find(|candidate| self.types.contains_key(candidate))
+    }
+
+    /// Reports inheritance errors as verify errors.
+    pub fn verify(&self) {
+        let mut names: Vec<&String> = self.types.keys().collect();
+        names.sort_by_key(|name| {
+            let location = &self.types[*name].name.1;
+            (location.compilation_unit().file_path().unwrap_or_default(), location.first_offset())
+        });
+        for qualified_name in names {
+            let t = &self.types[qualified_name];
+            if self.is_circular(qualified_name) {
+                report(&t.name.1, DiagnosticKind::CircularInheritance, diagarg![t.name.0.clone()]);
+                continue;
+            }
+            if self.verify_supertypes(t) && !t.is_interface {
+                self.verify_overrides(qualified_name, t);
+                self.verify_implementations(qualified_name, t);
+            }
+        }
+    }
+
+    fn is_circular(&self, qualified_name: &str) -> bool {
+        let mut visited = HashSet::new();
+        let mut pending = self.direct_supertypes(qualified_name);
+        while let Some(t) = pending.pop() {
+            if t == qualified_name {
+                return true;
+            }
+            if visited.insert(t.clone()) {
+                pending.extend(self.direct_supertypes(&t));
+            }
+        }
+        false
+    }
+
+    /// Verifies the kinds of the supertypes of a type, returning
+    /// whether they are valid.
+    fn verify_supertypes(&self, t: &HierarchyType) -> bool {
+        let mut valid = true;
+        for reference in t.extends_clause.iter() {
+            let Some(supertype_name) = self.resolve(t, reference) else {
+                continue;
+            };
+            let supertype = &self.types[&supertype_name];
+            if t.is_interface && !supertype.is_interface {
+                valid = false;
+                report(&reference.location, DiagnosticKind::InterfaceExpected, diagarg![reference.name.clone()]);
+            } else if !t.is_interface && supertype.is_interface {
+                valid = false;
+                report(&reference.location, DiagnosticKind::ClassExpected, diagarg![reference.name.clone()]);
+            } else if supertype.is_final {
+                report(&reference.location, DiagnosticKind::ExtendingFinalClass, diagarg![reference.name.clone()]);
+            }
+        }
+        for reference in t.implements_clause.iter() {
+            let Some(supertype_name) = self.resolve(t, reference) else {
+                continue;
+            };
+            if !self.types[&supertype_name].is_interface {
+                valid = false;
+                report(&reference.location, DiagnosticKind::InterfaceExpected, diagarg![reference.name.clone()]);
+            }
+        }
+        valid
+    }
+
+    /// Returns the members inherited from the superclasses of a class,
+    /// along with whether every superclass is part of the hierarchy.
+    fn inherited_members(&self, qualified_name: &str) -> (Vec<&ClassMember>, bool) {
+        let mut members = vec![];
+        let mut class = qualified_name.to_owned();
+        let mut visited = HashSet::new();
+        loop {
+            if !visited.insert(class.clone()) {
+                return (members, false);
+            }
+            let t = &self.types[&class];
+            let Some(reference) = t.extends_clause.first() else {
+                return (members, true);
+            };
+            match self.resolve(t, reference) {
+                Some(superclass) if !self.types[&superclass].is_interface => {
+                    members.extend(self.types[&superclass].members.iter().filter(|m| !m.is_private));
+                    class = superclass;
+                },
+                _ => return (members, false),
+            }
+        }
+    }
+
+    fn verify_overrides(&self, qualified_name: &str, t: &HierarchyType) {
+        let (inherited, complete) = self.inherited_members(qualified_name);
+        for member in t.members.iter() {
+            if member.kind == ClassMemberKind::Variable || member.is_private {
+                continue;
+            }
+            let redefined: Vec<&ClassMember> = inherited.iter().copied().filter(|m| m.name.0 == member.name.0 && m.kind != ClassMemberKind::Variable).collect();
+
+            // A getter and a setter of the same name form one property,
+            // which a method cannot redefine and conversely.
+            let is_accessor = |kind: ClassMemberKind| matches!(kind, ClassMemberKind::Getter | ClassMemberKind::Setter);
+            if let Some(other) = redefined.iter().find(|m| is_accessor(m.kind) != is_accessor(member.kind)) {
+                report(&member.name.1, DiagnosticKind::IncompatibleOverride, diagarg![member.name.0.clone(), other.kind.description().to_owned()]);
+                continue;
+            }
+            let redefines = redefined.iter().any(|m| m.kind == member.kind);
+            if !redefines && member.is_override && !redefined.is_empty() {
+                // Such as overriding a setter of a property that only has a getter
+                report(&member.name.1, DiagnosticKind::IncompatibleOverride, diagarg![member.name.0.clone(), redefined[0].kind.description().to_owned()]);
+            } else if redefines && !member.is_override {
+                report(&member.name.1, DiagnosticKind::MissingOverride, diagarg![member.name.0.clone()]);
+            } else if !redefines && member.is_override && complete {
+                report(&member.name.1, DiagnosticKind::UnnecessaryOverride, diagarg![member.name.0.clone()]);
+            }
+        }
+    }
+
+    fn verify_implementations(&self, qualified_name: &str, t: &HierarchyType) {
+        let (inherited, _) = self.inherited_members(qualified_name);
+        let implemented = |name: &str, kind: ClassMemberKind| {
+            t.members.iter().chain(inherited.iter().copied()).any(|m| m.name.0 == name && m.kind == kind)
+        };
+        let mut interfaces: Vec<String> = self.supertypes(qualified_name).into_iter().filter(|s| self.is_interface(s)).collect();
+        interfaces.sort();
+        for interface in interfaces {
+            for member in self.types[&interface].members.iter() {
+                if !implemented(&member.name.0, member.kind) {
+                    report(&t.name.1, DiagnosticKind::MissingInterfaceMember, diagarg![
+                        t.name.0.clone(),
+                        member.kind.description().to_owned(),
+                        member.name.0.clone(),
+                        interface.clone()]);
+                }
+            }
+        }
+    }
+}
+
+fn report(location: &Location, kind: DiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+    location.compilation_unit().add_diagnostic(Diagnostic::new_verify_error(location, kind, arguments));
+}
+
+fn collect_members(directive: &Rc<Directive>, members: &mut Vec<ClassMember>) {
+    match directive.as_ref() {
+        Directive::FunctionDefinition(defn) => {
+            if Attribute::find_static(&defn.attributes).is_some() {
+                return;
+            }
+            let (name, kind) = match &defn.name {
+                FunctionName::Identifier(name) => (name, ClassMemberKind::Method),
+                FunctionName::Getter(name) => (name, ClassMemberKind::Getter),
+                FunctionName::Setter(name) => (name, ClassMemberKind::Setter),
+                FunctionName::Constructor(_) => return,
+            };
+            members.push(ClassMember {
+                name: name.clone(),
+                kind,
+                is_override: Attribute::find_override(&defn.attributes).is_some(),
+                is_private: Attribute::find_private(&defn.attributes).is_some(),
+            });
+        },
+        Directive::VariableDefinition(defn) => {
+            if Attribute::find_static(&defn.attributes).is_some() {
+                return;
+            }
+            for binding in defn.bindings.iter() {
+                if let Some(name) = binding.destructuring.destructuring.to_identifier_name() {
+                    members.push(ClassMember {
+                        name,
+                        kind: ClassMemberKind::Variable,
+                        is_override: false,
+                        is_private: Attribute::find_private(&defn.attributes).is_some(),
+                    });
+                }
+            }
+        },
+        Directive::ConfigurationDirective(d) => collect_members(&d.directive, members),
+        Directive::NormalConfigurationDirective(d) => collect_members(&d.directive, members),
+        Directive::Block(block) => {
+            for directive in block.directives.iter() {
+                collect_members(directive, members);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn type_reference(expression: &Rc<Expression>) -> Option<TypeReference> {
+    Some(TypeReference {
+        name: dotted_name(expression)?,
+        location: expression.location(),
+    })
+}
+
+fn dotted_name(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::QualifiedIdentifier(id) => id.to_identifier_name().map(|(name, _)| name),
+        Expression::Member(member) => {
+            let (name, _) = member.identifier.to_identifier_name()?;
+            Some(format!("{}.{name}", dotted_name(&member.base)?))
+        },
+        _ => None,
+    }
+}
+
+fn qualify(package_name: &str, name: &str) -> String {
+    if package_name.is_empty() { name.to_owned() } else { format!("{package_name}.{name}") }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_class_hierarchy() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+package com.acme.shapes {
+    public interface IDrawable { function draw(): void; }
+    public interface ISized { function get size(): Number; function set size(value: Number): void; }
+    public interface IShape extends IDrawable, ISized {}
+    public final class Base { public function draw(): void {} }
+    public class Shape implements IShape {
+        public function draw(): void {}
+        public function get size(): Number { return 0 }
+    }
+}
+package com.acme {
+    import com.acme.shapes.*;
+    import Drawable = com.acme.shapes.IDrawable;
+    public class Square extends Shape {
+        public function draw(): void {}
+        override public function set size(value: Number): void {}
+        override public function toString(): String { return "" }
+    }
+    public class Sealed extends Base implements Drawable {}
+    public class Wrong extends IShape implements Shape {}
+    public interface IWrong extends Shape {}
+    public class A extends B {}
+    public class B extends A {}
+    public class C extends A { public function f(): void {} }
+    public class Ring extends Shape { override public function size(): Number { return 1 } }
+    public class View extends flash.display.Sprite { override public function toString(): String { return "" } }
+}"#.into());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        assert!(!compilation_unit.invalidated());
+        let mut hierarchy = ClassHierarchy::new();
+        hierarchy.add_program(&program);
+        hierarchy.verify();
+
+        let summary: Vec<String> = compilation_unit.diagnostics().iter().map(|d| format!("{} {}", d.id(), d.format_message_english())).collect();
+        let id = |kind: DiagnosticKind| kind.id();
+        assert_eq!(summary, [
+            format!("{} Class 'Shape' does not implement setter 'size' of interface 'com.acme.shapes.ISized'.", id(DiagnosticKind::MissingInterfaceMember)),
+            format!("{} 'draw' redefines a superclass member and requires the 'override' attribute.", id(DiagnosticKind::MissingOverride)),
+            format!("{} Incompatible override of 'size', which is a getter in the superclass.", id(DiagnosticKind::IncompatibleOverride)),
+            format!("{} 'toString' does not override a superclass member.", id(DiagnosticKind::UnnecessaryOverride)),
+            format!("{} Cannot extend final class 'Base'.", id(DiagnosticKind::ExtendingFinalClass)),
+            format!("{} 'IShape' is not a class.", id(DiagnosticKind::ClassExpected)),
+            format!("{} 'Shape' is not an interface.", id(DiagnosticKind::InterfaceExpected)),
+            format!("{} 'Shape' is not an interface.", id(DiagnosticKind::InterfaceExpected)),
+            format!("{} Circular inheritance involving 'A'.", id(DiagnosticKind::CircularInheritance)),
+            format!("{} Circular inheritance involving 'B'.", id(DiagnosticKind::CircularInheritance)),
+            format!("{} Incompatible override of 'size', which is a getter in the superclass.", id(DiagnosticKind::IncompatibleOverride)),
+            format!("{} Class 'Ring' does not implement setter 'size' of interface 'com.acme.shapes.ISized'.", id(DiagnosticKind::MissingInterfaceMember)),
+        ]);
+
+        assert_eq!(hierarchy.supertypes("com.acme.Square"), [
+            "com.acme.shapes.Shape",
+            "com.acme.shapes.IShape",
+            "com.acme.shapes.IDrawable",
+            "com.acme.shapes.ISized",
+        ]);
+        assert_eq!(hierarchy.interfaces("com.acme.Sealed"), ["com.acme.shapes.IDrawable"]);
+        assert_eq!(hierarchy.subtypes("com.acme.shapes.IDrawable"), [
+            "com.acme.IWrong",
+            "com.acme.Ring",
+            "com.acme.Sealed",
+            "com.acme.Square",
+            "com.acme.Wrong",
+            "com.acme.shapes.IShape",
+            "com.acme.shapes.Shape",
+        ]);
+        assert!(!hierarchy.is_subtype("com.acme.A", "com.acme.shapes.Shape"));
+    }
+}
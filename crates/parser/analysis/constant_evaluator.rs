@@ -0,0 +1,645 @@
+use crate::ns::*;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use std::fmt;
+
+/// A compile-time constant value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstantValue {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    Int(i32),
+    Uint(u32),
+    String(String),
+}
+
+impl ConstantValue {
+    /// Converts the value to a `Boolean` as in ActionScript 3.
+    pub fn to_boolean(&self) -> bool {
+        match self {
+            Self::Undefined | Self::Null => false,
+            Self::Boolean(value) => *value,
+            Self::Number(value) => !(value.is_nan() || *value == 0.0),
+            Self::Int(value) => *value != 0,
+            Self::Uint(value) => *value != 0,
+            Self::String(value) => !value.is_empty(),
+        }
+    }
+
+    /// Converts the value to a `Number` as in ActionScript 3.
+    pub fn to_number(&self) -> f64 {
+        match self {
+            Self::Undefined => f64::NAN,
+            Self::Null => 0.0,
+            Self::Boolean(value) => if *value { 1.0 } else { 0.0 },
+            Self::Number(value) => *value,
+            Self::Int(value) => *value as f64,
+            Self::Uint(value) => *value as f64,
+            Self::String(value) => string_to_number(value),
+        }
+    }
+
+    /// Converts the value to an `int`, wrapping around values
+    /// out of range.
+    pub fn to_int32(&self) -> i32 {
+        match self {
+            Self::Int(value) => *value,
+            Self::Uint(value) => *value as i32,
+            _ => number_to_uint32(self.to_number()) as i32,
+        }
+    }
+
+    /// Converts the value to a `uint`, wrapping around values
+    /// out of range.
+    pub fn to_uint32(&self) -> u32 {
+        match self {
+            Self::Int(value) => *value as u32,
+            Self::Uint(value) => *value,
+            _ => number_to_uint32(self.to_number()),
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Self::Number(_) | Self::Int(_) | Self::Uint(_))
+    }
+
+    /// Returns the result of the `typeof` operator.
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            Self::Undefined => "undefined",
+            Self::Null => "object",
+            Self::Boolean(_) => "boolean",
+            Self::Number(_) | Self::Int(_) | Self::Uint(_) => "number",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+impl fmt::Display for ConstantValue {
+    /// Converts the value to a `String` as in ActionScript 3.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undefined => write!(f, "undefined"),
+            Self::Null => write!(f, "null"),
+            Self::Boolean(value) => write!(f, "{value}"),
+            Self::Number(value) => write!(f, "{}", number_to_string(*value)),
+            Self::Int(value) => write!(f, "{value}"),
+            Self::Uint(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Folds compile-time constant expressions into values.
+///
+/// The evaluator folds literals, unary and binary operators, conditionals
+/// and references to known constants, such as `const` bindings, enumeration
+/// members (`E.MEMBER`) and configuration constants (`CONFIG::DEBUG`).
+/// It is used wherever a constant is required, such as configuration
+/// constants, enumeration member values, metadata defaults and parameter
+/// default values.
+///
+/// Integer literals have the `int` type, or the `uint` type
+/// if they do not fit in `int`. As in ActionScript 3, addition, subtraction,
+/// multiplication and remainder are performed in `Number`; a result of `int`
+/// and `uint` operands is an `int` if it fits in `int`, a `uint` if either
+/// operand is a `uint` and it fits in `uint`, and a `Number` otherwise.
+/// Division and `**` always result in a `Number`. A `const` binding annotated
+/// with `int` or `uint` wraps its value around to that type.
+///
+/// `evaluate()` reports the following:
+///
+/// - Division by zero, as a warning.
+/// - Integer literals not exactly representable as a `Number`, as warnings.
+/// - Subexpressions that are not compile-time constants, as verify errors.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, r#"
+///     const SIZE: uint = 4 * 1024;
+///     enum Color { const RED; const GREEN; const BLUE }
+/// "#.into());
+/// let program = ParserFacade(&compilation_unit, default()).parse_program();
+///
+/// let mut evaluator = ConstantEvaluator::new();
+/// evaluator.define("CONFIG::DEBUG", ConstantValue::Boolean(true));
+/// evaluator.add_directives(&program.directives);
+///
+/// let evaluate = |source: &str| {
+///     let compilation_unit = CompilationUnit::new(None, source.into());
+///     let expression = ParserFacade(&compilation_unit, default()).parse_expression();
+///     evaluator.evaluate(&expression)
+/// };
+/// assert_eq!(evaluate("SIZE >> 2"), Some(ConstantValue::Int(1024)));
+/// assert_eq!(evaluate("Color.BLUE"), Some(ConstantValue::Int(2)));
+/// assert_eq!(evaluate("CONFIG::DEBUG ? 'debug' : 'release'"), Some(ConstantValue::String("debug".into())));
+/// assert_eq!(evaluate("'v' + 1.5"), Some(ConstantValue::String("v1.5".into())));
+/// assert_eq!(evaluate("Math.random()"), None);
+/// ```
+#[derive(Clone, Default)]
+pub struct ConstantEvaluator {
+    /// Constant values by name, such as `X`, `E.MEMBER` or `CONFIG::DEBUG`.
+    constants: HashMap<String, ConstantValue>,
+}
+
+impl ConstantEvaluator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines a constant, such as `("CONFIG::DEBUG", ConstantValue::Boolean(true))`.
+    pub fn define(&mut self, name: &str, value: ConstantValue) {
+        self.constants.insert(name.to_owned(), value);
+    }
+
+    pub fn constant(&self, name: &str) -> Option<&ConstantValue> {
+        self.constants.get(name)
+    }
+
+    /// Defines the constants of a program, including those of its packages.
+    pub fn add_program(&mut self, program: &Program) {
+        for package in program.packages.iter() {
+            self.add_directives(&package.block.directives);
+        }
+        self.add_directives(&program.directives);
+    }
+
+    /// Defines the `const` bindings with constant initializers and the
+    /// enumeration members among directives. Bindings whose initializers
+    /// are not constant are skipped; only `int` and `uint` bindings whose
+    /// values wrap around are reported, as warnings.
+    pub fn add_directives(&mut self, directives: &[Rc<Directive>]) {
+        for directive in directives.iter() {
+            match directive.as_ref() {
+                Directive::VariableDefinition(defn) if defn.kind.0 == VariableDefinitionKind::Const => {
+                    for binding in defn.bindings.iter() {
+                        let Some((name, _)) = binding.destructuring.destructuring.to_identifier_name() else {
+                            continue;
+                        };
+                        let Some(initializer) = binding.initializer.as_ref() else {
+                            continue;
+                        };
+                        let Some(value) = self.evaluate_expression(initializer, false) else {
+                            continue;
+                        };
+                        let value = match binding.destructuring.type_annotation.as_deref() {
+                            Some(Expression::QualifiedIdentifier(id)) => match id.to_identifier_name() {
+                                Some((type_name, _)) => coerce(value, &type_name, &initializer.location()),
+                                None => value,
+                            },
+                            _ => value,
+                        };
+                        self.define(&name, value);
+                    }
+                },
+                Directive::EnumDefinition(defn) => self.add_enum(defn),
+                Directive::ConfigurationDirective(d) => self.add_directives(std::slice::from_ref(&d.directive)),
+                Directive::Block(block) => self.add_directives(&block.directives),
+                _ => {},
+            }
+        }
+    }
+
+    /// Defines the members of an enumeration as `E.MEMBER`. Members without
+    /// an initializer are numbered after the previous numeric member, counting
+    /// from zero, or by the next power of two, from one, for `[Set]` enumerations.
+    /// Initializers may refer to previous members by their unqualified names.
+    fn add_enum(&mut self, defn: &EnumDefinition) {
+        let mut shadowed: Vec<(String, Option<ConstantValue>)> = vec![];
+        let mut previous: Option<f64> = None;
+        for directive in defn.block.directives.iter() {
+            let Directive::VariableDefinition(member) = directive.as_ref() else {
+                continue;
+            };
+            if Attribute::find_static(&member.attributes).is_some() || member.kind.0 != VariableDefinitionKind::Const {
+                continue;
+            }
+            for binding in member.bindings.iter() {
+                let Some((name, _)) = binding.destructuring.destructuring.to_identifier_name() else {
+                    continue;
+                };
+                let value = match &binding.initializer {
+                    Some(init) => self.evaluate_expression(init, false),
+                    None => {
+                        let n = match previous {
+                            None => if defn.is_set { 1.0 } else { 0.0 },
+                            Some(n) if defn.is_set => {
+                                // The next power of two not covered by the previous member
+                                let mut bit = 1.0;
+                                while bit <= n {
+                                    bit *= 2.0;
+                                }
+                                bit
+                            },
+                            Some(n) => n + 1.0,
+                        };
+                        Some(if n.fract() == 0.0 && n >= i32::MIN as f64 && n <= i32::MAX as f64 { ConstantValue::Int(n as i32) } else { ConstantValue::Number(n) })
+                    },
+                };
+                let Some(value) = value else {
+                    continue;
+                };
+                if value.is_numeric() {
+                    previous = Some(value.to_number());
+                }
+                self.define(&format!("{}.{name}", defn.name.0), value.clone());
+                shadowed.push((name.clone(), self.constants.insert(name, value)));
+            }
+        }
+        for (name, value) in shadowed.into_iter().rev() {
+            match value {
+                Some(value) => self.define(&name, value),
+                None => {
+                    self.constants.remove(&name);
+                },
+            }
+        }
+    }
+
+    /// Evaluates a `CONFIG::NAME` directive's constant, returning whether
+    /// its directive is included. A constant that is not defined is
+    /// reported as a verify error.
+    pub fn evaluate_configuration(&self, directive: &NormalConfigurationDirective) -> Option<bool> {
+        let name = format!("{}::{}", directive.namespace.0, directive.constant_name.0);
+        let Some(value) = self.constants.get(&name) else {
+            let location = directive.namespace.1.combine_with(directive.constant_name.1.clone());
+            report(&location, DiagnosticKind::NotAConstant, vec![], true);
+            return None;
+        };
+        Some(value.to_boolean())
+    }
+
+    /// Evaluates a constant expression, reporting division by zero,
+    /// imprecise integer literals and non-constant subexpressions.
+    pub fn evaluate(&self, expression: &Expression) -> Option<ConstantValue> {
+        self.evaluate_expression(expression, true)
+    }
+
+    fn evaluate_expression(&self, expression: &Expression, reports: bool) -> Option<ConstantValue> {
+        match expression {
+            Expression::NullLiteral(_) => Some(ConstantValue::Null),
+            Expression::BooleanLiteral(e) => Some(ConstantValue::Boolean(e.value)),
+            Expression::StringLiteral(e) => Some(ConstantValue::String(e.value.clone())),
+            Expression::NumericLiteral(e) => evaluate_numeric_literal(e, false, reports),
+            Expression::Paren(e) => self.evaluate_expression(&e.expression, reports),
+            Expression::QualifiedIdentifier(id) => {
+                let name = match (&id.qualifier, &id.id) {
+                    (None, QualifiedIdentifierIdentifier::Id((name, _))) if !id.attribute => Some(name.clone()),
+                    (Some(qualifier), QualifiedIdentifierIdentifier::Id((name, _))) if !id.attribute => {
+                        dotted_name(qualifier).map(|qualifier| format!("{qualifier}::{name}"))
+                    },
+                    _ => None,
+                };
+                self.lookup(name, expression, reports)
+            },
+            Expression::Member(_) => self.lookup(dotted_name(expression), expression, reports),
+            Expression::Unary(e) => {
+                if let (Operator::Negative, Expression::NumericLiteral(literal)) = (e.operator, e.expression.as_ref()) {
+                    return evaluate_numeric_literal(literal, true, reports);
+                }
+                let operand = match e.operator {
+                    Operator::Positive | Operator::Negative | Operator::BitwiseNot | Operator::LogicalNot |
+                    Operator::Typeof | Operator::Void => self.evaluate_expression(&e.expression, reports)?,
+                    _ => return self.not_a_constant(expression, reports),
+                };
+                Some(match e.operator {
+                    Operator::Positive => if operand.is_numeric() { operand } else { ConstantValue::Number(operand.to_number()) },
+                    Operator::Negative => match operand {
+                        ConstantValue::Int(value) if value != 0 && value != i32::MIN => ConstantValue::Int(-value),
+                        other => ConstantValue::Number(-other.to_number()),
+                    },
+                    Operator::BitwiseNot => ConstantValue::Int(!operand.to_int32()),
+                    Operator::LogicalNot => ConstantValue::Boolean(!operand.to_boolean()),
+                    Operator::Typeof => ConstantValue::String(operand.type_of().into()),
+                    _ => ConstantValue::Undefined,
+                })
+            },
+            Expression::Binary(e) => {
+                let supported = !matches!(e.operator, Operator::Instanceof | Operator::In | Operator::NotIn | Operator::Is | Operator::IsNot | Operator::As);
+                if !supported {
+                    return self.not_a_constant(expression, reports);
+                }
+                let left = self.evaluate_expression(&e.left, reports);
+                let right = self.evaluate_expression(&e.right, reports);
+                evaluate_binary(e, left?, right?, reports)
+            },
+            Expression::Conditional(e) => {
+                let test = self.evaluate_expression(&e.test, reports)?;
+                self.evaluate_expression(if test.to_boolean() { &e.consequent } else { &e.alternative }, reports)
+            },
+            _ => self.not_a_constant(expression, reports),
+        }
+    }
+
+    fn lookup(&self, name: Option<String>, expression: &Expression, reports: bool) -> Option<ConstantValue> {
+        match name.and_then(|name| self.constants.get(&name)) {
+            Some(value) => Some(value.clone()),
+            None => self.not_a_constant(expression, reports),
+        }
+    }
+
+    fn not_a_constant(&self, expression: &Expression, reports: bool) -> Option<ConstantValue> {
+        if reports {
+            report(&expression.location(), DiagnosticKind::NotAConstant, vec![], true);
+        }
+        None
+    }
+}
+
+fn evaluate_numeric_literal(literal: &NumericLiteral, negative: bool, reports: bool) -> Option<ConstantValue> {
+    let digits = literal.value.replace('_', "").to_lowercase();
+    let is_integer = digits.starts_with("0x") || digits.starts_with("0b") || !digits.contains(['.', 'e']);
+    if literal.suffix == NumberSuffix::F || !is_integer {
+        let value = literal.parse_double(negative).ok()?;
+        if reports && value.is_infinite() {
+            report(&literal.location, DiagnosticKind::ConstantOverflow, diagarg!["Number".to_owned()], false);
+        }
+        return Some(ConstantValue::Number(value));
+    }
+    let value = literal.parse_big_int(negative).ok()?;
+    if let Some(value) = value.to_i32() {
+        return Some(ConstantValue::Int(value));
+    }
+    if let Some(value) = value.to_u32() {
+        return Some(ConstantValue::Uint(value));
+    }
+    // Integers beyond 2^53 lose precision as a Number.
+    if reports && value.magnitude() > &(BigInt::from(1u64 << 53).magnitude().clone()) {
+        report(&literal.location, DiagnosticKind::ConstantOverflow, diagarg!["Number".to_owned()], false);
+    }
+    value.to_f64().map(ConstantValue::Number)
+}
+
+fn evaluate_binary(e: &BinaryExpression, left: ConstantValue, right: ConstantValue, reports: bool) -> Option<ConstantValue> {
+    use ConstantValue::*;
+    let integers = matches!(left, Int(_) | Uint(_)) && matches!(right, Int(_) | Uint(_));
+    let unsigned = matches!(left, Uint(_)) || matches!(right, Uint(_));
+    let number = |value: f64| if integers { integer_result(value, unsigned) } else { Number(value) };
+    Some(match e.operator {
+        Operator::Add if matches!(left, String(_)) || matches!(right, String(_)) => String(format!("{left}{right}")),
+        Operator::Add => number(left.to_number() + right.to_number()),
+        Operator::Subtract => number(left.to_number() - right.to_number()),
+        Operator::Multiply => number(left.to_number() * right.to_number()),
+        Operator::Divide | Operator::Remainder => {
+            let divisor = right.to_number();
+            if divisor == 0.0 && reports {
+                report(&e.right.location(), DiagnosticKind::DivisionByZero, vec![], false);
+            }
+            if e.operator == Operator::Divide {
+                Number(left.to_number() / divisor)
+            } else {
+                number(left.to_number() % divisor)
+            }
+        },
+        Operator::Power => Number(left.to_number().powf(right.to_number())),
+        Operator::ShiftLeft => Int(left.to_int32().wrapping_shl(right.to_uint32() & 31)),
+        Operator::ShiftRight => Int(left.to_int32() >> (right.to_uint32() & 31)),
+        Operator::ShiftRightUnsigned => Uint(left.to_uint32() >> (right.to_uint32() & 31)),
+        Operator::BitwiseAnd => Int(left.to_int32() & right.to_int32()),
+        Operator::BitwiseXor => Int(left.to_int32() ^ right.to_int32()),
+        Operator::BitwiseOr => Int(left.to_int32() | right.to_int32()),
+        Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge => {
+            let ordering = match (&left, &right) {
+                (String(l), String(r)) => Some(l.cmp(r)),
+                _ => left.to_number().partial_cmp(&right.to_number()),
+            };
+            Boolean(ordering.is_some_and(|ordering| match e.operator {
+                Operator::Lt => ordering.is_lt(),
+                Operator::Gt => ordering.is_gt(),
+                Operator::Le => ordering.is_le(),
+                _ => ordering.is_ge(),
+            }))
+        },
+        Operator::Equals => Boolean(loosely_equals(&left, &right)),
+        Operator::NotEquals => Boolean(!loosely_equals(&left, &right)),
+        Operator::StrictEquals => Boolean(strictly_equals(&left, &right)),
+        Operator::StrictNotEquals => Boolean(!strictly_equals(&left, &right)),
+        Operator::LogicalAnd => if left.to_boolean() { right } else { left },
+        Operator::LogicalOr => if left.to_boolean() { left } else { right },
+        Operator::LogicalXor => Boolean(left.to_boolean() != right.to_boolean()),
+        Operator::NullCoalescing => if matches!(left, Undefined | Null) { right } else { left },
+        _ => return None,
+    })
+}
+
+/// Converts the result of integer operands to an `int` if it fits,
+/// or to a `uint` if either operand is a `uint` and it fits,
+/// leaving it a `Number` otherwise.
+fn integer_result(value: f64, unsigned: bool) -> ConstantValue {
+    if value.fract() != 0.0 {
+        ConstantValue::Number(value)
+    } else if value >= i32::MIN as f64 && value <= i32::MAX as f64 {
+        ConstantValue::Int(value as i32)
+    } else if unsigned && value >= 0.0 && value <= u32::MAX as f64 {
+        ConstantValue::Uint(value as u32)
+    } else {
+        ConstantValue::Number(value)
+    }
+}
+
+/// Coerces a constant to the type annotation of a binding,
+/// reporting numbers that wrap around as `int` or `uint`.
+fn coerce(value: ConstantValue, type_name: &str, location: &Location) -> ConstantValue {
+    let overflows = |min: f64, max: f64| {
+        let n = value.to_number().trunc();
+        value.is_numeric() && !n.is_nan() && !(min..=max).contains(&n)
+    };
+    let check = |min: f64, max: f64| {
+        if overflows(min, max) {
+            report(location, DiagnosticKind::ConstantOverflow, diagarg![type_name.to_owned()], false);
+        }
+    };
+    match type_name {
+        "int" => {
+            check(i32::MIN as f64, i32::MAX as f64);
+            ConstantValue::Int(value.to_int32())
+        },
+        "uint" => {
+            check(0.0, u32::MAX as f64);
+            ConstantValue::Uint(value.to_uint32())
+        },
+        "Number" => ConstantValue::Number(value.to_number()),
+        "Boolean" => ConstantValue::Boolean(value.to_boolean()),
+        "String" if !matches!(value, ConstantValue::Null) => ConstantValue::String(value.to_string()),
+        _ => value,
+    }
+}
+
+fn loosely_equals(left: &ConstantValue, right: &ConstantValue) -> bool {
+    use ConstantValue::*;
+    match (left, right) {
+        (Undefined | Null, Undefined | Null) => true,
+        (Undefined | Null, _) | (_, Undefined | Null) => false,
+        (String(l), String(r)) => l == r,
+        _ => left.to_number() == right.to_number(),
+    }
+}
+
+fn strictly_equals(left: &ConstantValue, right: &ConstantValue) -> bool {
+    use ConstantValue::*;
+    match (left, right) {
+        (Undefined, Undefined) | (Null, Null) => true,
+        (Boolean(l), Boolean(r)) => l == r,
+        (String(l), String(r)) => l == r,
+        _ if left.is_numeric() && right.is_numeric() => left.to_number() == right.to_number(),
+        _ => false,
+    }
+}
+
+fn number_to_uint32(value: f64) -> u32 {
+    if !value.is_finite() {
+        return 0;
+    }
+    value.trunc().rem_euclid(4294967296.0) as u32
+}
+
+/// Converts a Number to a String as in ECMA-262 `Number::toString`.
+fn number_to_string(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".into();
+    }
+    if value == 0.0 {
+        return "0".into();
+    }
+    if value < 0.0 {
+        return format!("-{}", number_to_string(-value));
+    }
+    if value.is_infinite() {
+        return "Infinity".into();
+    }
+
+    // The shortest digits that round-trip, and the
+    // position of the decimal point relative to them.
+    let scientific = format!("{value:e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap() + 1;
+
+    if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let exponent = (n - 1).abs();
+        if k == 1 {
+            format!("{digits}e{sign}{exponent}")
+        } else {
+            format!("{}.{}e{sign}{exponent}", &digits[..1], &digits[1..])
+        }
+    }
+}
+
+fn string_to_number(value: &str) -> f64 {
+    let value = value.trim();
+    if value.is_empty() {
+        return 0.0;
+    }
+    if let Some(hex) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        return u64::from_str_radix(hex, 16).map_or(f64::NAN, |n| n as f64);
+    }
+    match value {
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        _ if value.contains(|ch: char| ch.is_ascii_alphabetic() && ch != 'e' && ch != 'E') => f64::NAN,
+        _ => value.parse().unwrap_or(f64::NAN),
+    }
+}
+
+fn dotted_name(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::QualifiedIdentifier(id) => id.to_identifier_name().map(|(name, _)| name),
+        Expression::Member(member) => {
+            let (name, _) = member.identifier.to_identifier_name()?;
+            Some(format!("{}.{name}", dotted_name(&member.base)?))
+        },
+        _ => None,
+    }
+}
+
+fn report(location: &Location, kind: DiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>, is_error: bool) {
+    let diagnostic = if is_error {
+        Diagnostic::new_verify_error(location, kind, arguments)
+    } else {
+        Diagnostic::new_warning(location, kind, arguments)
+    };
+    location.compilation_unit().add_diagnostic(diagnostic);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_constant_evaluator() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+            const MAX: int = 2147483647;
+            const MASK: uint = 0xFFFFFFFF;
+            const SMALL: uint = 5;
+            const WRAPPED: int = MAX + 1;
+            const NAME = "app";
+            const LIST = [1, 2];
+            [Set]
+            enum Flags { const A; const B; const C = A | B; const D }
+            enum Level { const LOW = 10; const HIGH }
+        "#.into());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        assert!(!compilation_unit.invalidated());
+        let mut evaluator = ConstantEvaluator::new();
+        evaluator.define("CONFIG::VERSION", ConstantValue::Int(2));
+        evaluator.add_program(&program);
+        assert_eq!(evaluator.constant("WRAPPED"), Some(&ConstantValue::Int(i32::MIN)));
+        assert_eq!(compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect::<Vec<_>>(), ["The constant value overflows the type 'int'."]);
+        assert!(evaluator.constant("LIST").is_none());
+        assert!(evaluator.constant("A").is_none());
+
+        let evaluate = |source: &str| -> (Option<ConstantValue>, Vec<std::string::String>) {
+            let compilation_unit = CompilationUnit::new(None, source.into());
+            let expression = ParserFacade(&compilation_unit, default()).parse_expression();
+            let value = evaluator.evaluate(&expression);
+            (value, compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect())
+        };
+        use ConstantValue::*;
+        assert_eq!(evaluate("-2147483648"), (Some(Int(i32::MIN)), vec![]));
+        assert_eq!(evaluate("MAX + 1"), (Some(Number(2147483648.0)), vec![]));
+        assert_eq!(evaluate("(MAX + 1) / 2"), (Some(Number(1073741824.0)), vec![]));
+        assert_eq!(evaluate("MASK + 1"), (Some(Number(4294967296.0)), vec![]));
+        assert_eq!(evaluate("MASK - 1"), (Some(Uint(4294967294)), vec![]));
+        assert_eq!(evaluate("SMALL - 10"), (Some(Int(-5)), vec![]));
+        assert_eq!(evaluate("MASK >>> 28"), (Some(Uint(15)), vec![]));
+        assert_eq!(evaluate("MASK | 0"), (Some(Int(-1)), vec![]));
+        assert_eq!(evaluate("7 / 2"), (Some(Number(3.5)), vec![]));
+        assert_eq!(evaluate("7 % 2"), (Some(Int(1)), vec![]));
+        assert_eq!(evaluate("2 ** 10"), (Some(Number(1024.0)), vec![]));
+        assert_eq!(evaluate("1 / 0"), (Some(Number(f64::INFINITY)), vec!["Division by zero.".to_owned()]));
+        assert_eq!(evaluate("NAME + '-' + CONFIG::VERSION + '.' + 0.5"), (Some(String("app-2.0.5".into())), vec![]));
+        assert_eq!(evaluate("null ?? NAME"), (Some(String("app".into())), vec![]));
+        assert_eq!(evaluate("'' || 'default'"), (Some(String("default".into())), vec![]));
+        assert_eq!(evaluate("CONFIG::VERSION > 1 ? 'new' : 'old'"), (Some(String("new".into())), vec![]));
+        assert_eq!(evaluate("'10' == 10 && !('10' === 10)"), (Some(Boolean(true)), vec![]));
+        assert_eq!(evaluate("typeof NAME"), (Some(String("string".into())), vec![]));
+        assert_eq!(evaluate("Flags.C + Flags.D"), (Some(Int(7)), vec![]));
+        assert_eq!(evaluate("Level.HIGH"), (Some(Int(11)), vec![]));
+        assert_eq!(evaluate("9007199254740993"), (Some(Number(9007199254740992.0)), vec!["The constant value overflows the type 'Number'.".to_owned()]));
+        assert_eq!(evaluate("1e400"), (Some(Number(f64::INFINITY)), vec!["The constant value overflows the type 'Number'.".to_owned()]));
+        assert_eq!(evaluate("'' + 0.0000001"), (Some(String("1e-7".into())), vec![]));
+        assert_eq!(evaluate("'' + 0.000001"), (Some(String("0.000001".into())), vec![]));
+        assert_eq!(evaluate("'' + 1e21"), (Some(String("1e+21".into())), vec![]));
+        assert_eq!(evaluate("'' + 1.2345678901234568e20"), (Some(String("123456789012345680000".into())), vec![]));
+        assert_eq!(evaluate("'' + 1.5e300"), (Some(String("1.5e+300".into())), vec![]));
+        assert_eq!(evaluate("'' + -0.1"), (Some(String("-0.1".into())), vec![]));
+        assert_eq!(evaluate("'' + 0.1 * 3"), (Some(String("0.30000000000000004".into())), vec![]));
+        assert_eq!(evaluate("LIST.length + f()"), (None, vec![
+            "The expression is not a compile-time constant.".to_owned(),
+            "The expression is not a compile-time constant.".to_owned(),
+        ]));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::ns::*;
+
+/// The environment `@media` queries are evaluated against, such
+/// as a device profile of a Flex mobile application.
+///
+/// The following media features are supported:
+///
+/// - `application-dpi`, with the `min-` and `max-` variants, compared
+///   against `application_dpi`.
+/// - `os-platform`, such as `"IOS"` or `"Android"`, compared
+///   case-insensitively against `os_platform`.
+/// - `device-width` and `device-height`, in pixels, with the `min-`
+///   and `max-` variants.
+///
+/// Media types other than `all` are compared against `media_type`.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let source = r#"@media (application-dpi: 240) and (os-platform: "Android") { .a { color: red } }"#;
+/// let compilation_unit = CompilationUnit::new(None, source.into());
+/// let document = CssParserFacade(&compilation_unit, default()).parse_document();
+/// let CssDirective::MediaQuery(query) = document.directives[0].as_ref() else { panic!() };
+///
+/// let environment = CssMediaEnvironment {
+///     application_dpi: 240.0,
+///     os_platform: "Android".into(),
+///     ..default()
+/// };
+/// assert!(environment.matches(query));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CssMediaEnvironment {
+    /// The media type, such as `screen`. Default: `screen`.
+    pub media_type: String,
+    /// Default: 160.
+    pub application_dpi: f64,
+    /// The operating system, such as `IOS`, `Android`, `Windows`,
+    /// `Macintosh` or `Linux`. Default: empty.
+    pub os_platform: String,
+    /// The device width in pixels. Default: 0.
+    pub device_width: f64,
+    /// The device height in pixels. Default: 0.
+    pub device_height: f64,
+}
+
+impl Default for CssMediaEnvironment {
+    fn default() -> Self {
+        Self {
+            media_type: "screen".into(),
+            application_dpi: 160.0,
+            os_platform: "".into(),
+            device_width: 0.0,
+            device_height: 0.0,
+        }
+    }
+}
+
+/// Comparison of a numeric media feature.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum CssMediaFeatureComparison {
+    Equals,
+    Min,
+    Max,
+}
+
+enum CssMediaFeature {
+    ApplicationDpi,
+    OsPlatform,
+    DeviceWidth,
+    DeviceHeight,
+}
+
+impl CssMediaFeature {
+    /// Resolves a normalized property name, such as `minApplicationDpi`.
+    fn from_property_name(name: &str) -> Option<(Self, CssMediaFeatureComparison)> {
+        let (comparison, name) = if let Some(name) = name.strip_prefix("min") {
+            (CssMediaFeatureComparison::Min, name)
+        } else if let Some(name) = name.strip_prefix("max") {
+            (CssMediaFeatureComparison::Max, name)
+        } else {
+            (CssMediaFeatureComparison::Equals, name)
+        };
+        let feature = match name {
+            "applicationDpi" | "ApplicationDpi" => Self::ApplicationDpi,
+            "deviceWidth" | "DeviceWidth" => Self::DeviceWidth,
+            "deviceHeight" | "DeviceHeight" => Self::DeviceHeight,
+            "osPlatform" if comparison == CssMediaFeatureComparison::Equals => Self::OsPlatform,
+            _ => return None,
+        };
+        Some((feature, comparison))
+    }
+}
+
+impl CssMediaEnvironment {
+    /// Indicates whether a media query applies, that is, whether
+    /// any of its comma-separated conditions holds.
+    pub fn matches(&self, query: &CssMediaQuery) -> bool {
+        query.conditions.iter().any(|condition| self.matches_condition(condition))
+    }
+
+    /// Indicates whether a media query condition holds. Conditions
+    /// involving unknown media features or invalid values do not hold.
+    pub fn matches_condition(&self, condition: &CssMediaQueryCondition) -> bool {
+        match condition {
+            CssMediaQueryCondition::Invalidated(_) => false,
+            CssMediaQueryCondition::Id((media_type, _)) |
+            CssMediaQueryCondition::OnlyId { id: (media_type, _), .. } => {
+                media_type.eq_ignore_ascii_case("all") || media_type.eq_ignore_ascii_case(&self.media_type)
+            },
+            CssMediaQueryCondition::And { left, right, .. } => self.matches_condition(left) && self.matches_condition(right),
+            CssMediaQueryCondition::ParenProperty((property, _)) => {
+                let Some((feature, comparison)) = CssMediaFeature::from_property_name(&property.name.0) else {
+                    return false;
+                };
+                let actual = match feature {
+                    CssMediaFeature::OsPlatform => {
+                        return media_feature_text(&property.value).is_some_and(|platform| platform.eq_ignore_ascii_case(&self.os_platform));
+                    },
+                    CssMediaFeature::ApplicationDpi => self.application_dpi,
+                    CssMediaFeature::DeviceWidth => self.device_width,
+                    CssMediaFeature::DeviceHeight => self.device_height,
+                };
+                let Some(expected) = media_feature_number(&property.value) else {
+                    return false;
+                };
+                match comparison {
+                    CssMediaFeatureComparison::Equals => actual == expected,
+                    CssMediaFeatureComparison::Min => actual >= expected,
+                    CssMediaFeatureComparison::Max => actual <= expected,
+                }
+            },
+        }
+    }
+
+    /// Reports unknown media features and values of invalid type
+    /// in the `@media` queries of a style sheet as warnings.
+    pub fn verify_document(document: &CssDocument) {
+        for directive in document.directives.iter() {
+            if let CssDirective::MediaQuery(query) = directive.as_ref() {
+                for condition in query.conditions.iter() {
+                    Self::verify_condition(condition);
+                }
+            }
+        }
+    }
+
+    fn verify_condition(condition: &CssMediaQueryCondition) {
+        match condition {
+            CssMediaQueryCondition::And { left, right, .. } => {
+                Self::verify_condition(left);
+                Self::verify_condition(right);
+            },
+            CssMediaQueryCondition::ParenProperty((property, _)) => {
+                let name = property.name.1.text();
+                let compilation_unit = property.location.compilation_unit();
+                let Some((feature, _)) = CssMediaFeature::from_property_name(&property.name.0) else {
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&property.name.1, DiagnosticKind::UnknownMediaFeature, diagarg![name]));
+                    return;
+                };
+                let (valid, expected) = match feature {
+                    CssMediaFeature::OsPlatform => (media_feature_text(&property.value).is_some(), "a string or identifier"),
+                    _ => (media_feature_number(&property.value).is_some(), "a number"),
+                };
+                if !valid {
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&property.value.location(), DiagnosticKind::InvalidMediaFeatureValue, diagarg![name, expected.to_owned()]));
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Returns a number, optionally in pixels.
+fn media_feature_number(value: &CssPropertyValue) -> Option<f64> {
+    match value {
+        CssPropertyValue::Number(number) if number.unit.as_ref().is_none_or(|unit| unit == "px" || unit == "dpi") => Some(number.value),
+        _ => None,
+    }
+}
+
+fn media_feature_text(value: &CssPropertyValue) -> Option<String> {
+    match value {
+        CssPropertyValue::String(string) => Some(string.value.clone()),
+        CssPropertyValue::Identifier(identifier) => Some(identifier.value.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_media_queries() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+.a { color: black }
+@media (application-dpi: 160) { .a { color: red } }
+@media (min-application-dpi: 240) and (os-platform: "IOS") { .a { color: green } }
+@media screen and (max-device-width: 800), (os-platform: Android) { .b { color: blue } }
+@media print { .a { color: white } }
+@media (screen-density: 2) and (application-dpi: high) { .c { color: gray } }"#.into());
+        let document = CssParserFacade(&compilation_unit, default()).parse_document();
+        CssMediaEnvironment::verify_document(&document);
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].kind() == DiagnosticKind::UnknownMediaFeature);
+        assert_eq!(diagnostics[0].location().text(), "screen-density");
+        assert!(diagnostics[1].kind() == DiagnosticKind::InvalidMediaFeatureValue);
+
+        let queries: Vec<Rc<CssMediaQuery>> = document.directives.iter().filter_map(|directive| {
+            if let CssDirective::MediaQuery(query) = directive.as_ref() { Some(Rc::new(query.clone())) } else { None }
+        }).collect();
+        let applying = |environment: &CssMediaEnvironment| -> Vec<bool> {
+            queries.iter().map(|query| environment.matches(query)).collect()
+        };
+
+        let phone = CssMediaEnvironment {
+            application_dpi: 320.0,
+            os_platform: "ios".into(),
+            device_width: 640.0,
+            device_height: 1136.0,
+            ..default()
+        };
+        assert_eq!(applying(&phone), [false, true, true, false, false]);
+
+        let tablet = CssMediaEnvironment {
+            os_platform: "Android".into(),
+            device_width: 1280.0,
+            ..default()
+        };
+        assert_eq!(applying(&tablet), [true, false, true, false, false]);
+
+        let mut matcher = CssSelectorMatcher::new();
+        matcher.add_document_with_media(&document, &phone);
+        let mut node = CssStyleNode::new("", "Label");
+        node.style_names.push("a".into());
+        let cascade = matcher.cascade(&Rc::new(node));
+        assert_eq!(cascade["color"].value.location().text(), "green");
+    }
+}
@@ -0,0 +1,464 @@
+use std::cmp::Ordering;
+
+use crate::ns::*;
+
+/// The specificity of a CSS selector, compared as the number of
+/// `id` conditions, then the number of class, pseudo-state and attribute
+/// conditions, then the number of type names and pseudo-elements.
+///
+/// The specificity of a `:not()` condition is that of its argument.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CssSpecificity(pub u32, pub u32, pub u32);
+
+impl CssSpecificity {
+    pub fn of(selector: &CssSelector) -> Self {
+        match selector {
+            CssSelector::Invalidated(_) => Self::default(),
+            CssSelector::Base(base) => {
+                let mut specificity = Self::default();
+                if base.element_name.as_ref().is_some_and(|(name, _)| name != "*") {
+                    specificity.2 += 1;
+                }
+                for condition in base.conditions.iter() {
+                    specificity = specificity + Self::of_condition(condition);
+                }
+                specificity
+            },
+            CssSelector::Combinator(combinator) => Self::of(&combinator.left) + Self::of(&combinator.right),
+        }
+    }
+
+    fn of_condition(condition: &CssSelectorCondition) -> Self {
+        match condition {
+            CssSelectorCondition::Invalidated(_) => Self::default(),
+            CssSelectorCondition::Id(_) => Self(1, 0, 0),
+            CssSelectorCondition::Class(_) |
+            CssSelectorCondition::Pseudo(_) |
+            CssSelectorCondition::Attribute { .. } => Self(0, 1, 0),
+            CssSelectorCondition::PseudoElement(_) => Self(0, 0, 1),
+            CssSelectorCondition::Not { condition, .. } => Self::of_condition(condition),
+        }
+    }
+}
+
+impl std::ops::Add for CssSpecificity {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+/// A node of an abstract component tree matched by CSS selectors,
+/// such as a component instance of a MXML document.
+///
+/// Child nodes are added through `CssStyleNode::append_child`,
+/// which links them to their parent and siblings.
+pub struct CssStyleNode {
+    /// The namespace URI and local name of the component type, such as
+    /// `("library://ns.adobe.com/flex/spark", "Button")`.
+    pub type_name: (String, String),
+    /// The namespace URIs and local names of the supertypes of the component,
+    /// which type selectors also match, as in Flex.
+    pub supertype_names: Vec<(String, String)>,
+    /// The style names, given by the `styleName` property.
+    pub style_names: Vec<String>,
+    pub id: Option<String>,
+    /// The current states, matched by pseudo-state conditions, such as `:over`.
+    pub states: Vec<String>,
+    /// The attributes matched by attribute conditions, such as `[enabled="true"]`.
+    pub attributes: HashMap<String, String>,
+    /// The MXML element the node is created from, if any.
+    pub element: Option<Rc<MxmlElement>>,
+    parent: RefCell<Weak<CssStyleNode>>,
+    children: RefCell<Vec<Rc<CssStyleNode>>>,
+}
+
+impl CssStyleNode {
+    pub fn new(uri: &str, local_name: &str) -> Self {
+        Self {
+            type_name: (uri.to_owned(), local_name.to_owned()),
+            supertype_names: vec![],
+            style_names: vec![],
+            id: None,
+            states: vec![],
+            attributes: HashMap::new(),
+            element: None,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![]),
+        }
+    }
+
+    /// Appends a child node, linking it to its parent.
+    pub fn append_child(parent: &Rc<Self>, child: Rc<Self>) {
+        child.parent.replace(Rc::downgrade(parent));
+        parent.children.borrow_mut().push(child);
+    }
+
+    pub fn parent(&self) -> Option<Rc<Self>> {
+        self.parent.borrow().upgrade()
+    }
+
+    pub fn children(&self) -> Vec<Rc<Self>> {
+        self.children.borrow().clone()
+    }
+
+    /// Returns the siblings preceding the node, nearest first.
+    pub fn previous_siblings(self: &Rc<Self>) -> Vec<Rc<Self>> {
+        let Some(parent) = self.parent() else {
+            return vec![];
+        };
+        let children = parent.children.borrow();
+        let i = children.iter().position(|child| Rc::ptr_eq(child, self)).unwrap_or(0);
+        children[..i].iter().rev().cloned().collect()
+    }
+
+    /// Builds a component tree from the elements of a MXML document.
+    ///
+    /// Every element other than language elements, such as `<fx:Script>`,
+    /// and property elements, such as `<s:layout>`, becomes a node,
+    /// whose style names and `id` are taken from the `styleName`
+    /// and `id` attributes. Children of `<fx:Declarations>` and of
+    /// property elements are appended to the nearest component.
+    pub fn from_mxml(document: &Mxml) -> Option<Rc<Self>> {
+        let root = document.content.iter().find_map(|content| {
+            if let MxmlContent::Element(element) = content.as_ref() { Some(element.clone()) } else { None }
+        })?;
+        let node = Self::from_mxml_element(&root)?;
+        let node = Rc::new(node);
+        Self::append_mxml_children(&node, &root);
+        Some(node)
+    }
+
+    fn from_mxml_element(element: &Rc<MxmlElement>) -> Option<Self> {
+        let (uri, local_name) = element.name.resolve_name(&element.namespace).ok()?;
+        let mut node = Self::new(&uri, &local_name);
+        if let Some((style_name, _)) = element.attribute_value("styleName") {
+            node.style_names = style_name.split_whitespace().map(|name| name.to_owned()).collect();
+        }
+        node.id = element.attribute_value("id").map(|(id, _)| id.trim().to_owned());
+        node.element = Some(element.clone());
+        Some(node)
+    }
+
+    fn append_mxml_children(node: &Rc<Self>, element: &MxmlElement) {
+        let element_uri = element.name.resolve_prefix(&element.namespace).ok();
+        for content in element.content.iter().flatten() {
+            let MxmlContent::Element(child) = content.as_ref() else {
+                continue;
+            };
+            if child.is_language_element("Declarations") {
+                Self::append_mxml_children(node, child);
+                continue;
+            }
+            let child_uri = child.name.resolve_prefix(&child.namespace).ok();
+            if matches!(child_uri.as_deref(), Some(MxmlNamespace::MXML_2009 | MxmlNamespace::MXML_2006)) {
+                continue;
+            }
+            if child_uri == element_uri && child.name.name.starts_with(|ch: char| ch.is_lowercase()) {
+                Self::append_mxml_children(node, child);
+                continue;
+            }
+            let Some(child_node) = Self::from_mxml_element(child) else {
+                continue;
+            };
+            let child_node = Rc::new(child_node);
+            Self::append_child(node, child_node.clone());
+            Self::append_mxml_children(&child_node, child);
+        }
+    }
+
+    /// Finds the node created from a MXML element.
+    pub fn find_mxml_element(self: &Rc<Self>, element: &Rc<MxmlElement>) -> Option<Rc<Self>> {
+        if self.element.as_ref().is_some_and(|e| Rc::ptr_eq(e, element)) {
+            return Some(self.clone());
+        }
+        self.children.borrow().iter().find_map(|child| child.find_mxml_element(element))
+    }
+}
+
+/// Matches CSS rules against nodes of a component tree, producing
+/// their cascade.
+///
+/// Namespace prefixes in selectors are resolved through the
+/// `@namespace` definitions of the style sheets. A type selector
+/// without a namespace prefix matches any namespace, unless a default
+/// namespace is defined. Rules within `@media` queries are not matched.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let source = r#"@namespace s "library://ns.adobe.com/flex/spark";
+/// s|Button { color: red }
+/// s|Button.primary:over { color: blue }"#;
+/// let compilation_unit = CompilationUnit::new(None, source.into());
+/// let document = CssParserFacade(&compilation_unit, default()).parse_document();
+/// let mut matcher = CssSelectorMatcher::new();
+/// matcher.add_document(&document);
+///
+/// let mut button = CssStyleNode::new("library://ns.adobe.com/flex/spark", "Button");
+/// button.style_names.push("primary".into());
+/// button.states.push("over".into());
+/// let cascade = matcher.cascade(&Rc::new(button));
+/// assert_eq!(cascade["color"].value.location().text(), "blue");
+/// ```
+#[derive(Default)]
+pub struct CssSelectorMatcher {
+    namespaces: HashMap<String, String>,
+    rules: Vec<Rc<CssRule>>,
+}
+
+/// A rule matching a node.
+#[derive(Clone)]
+pub struct CssMatchedRule {
+    pub rule: Rc<CssRule>,
+    /// The matching selector of highest specificity.
+    pub selector: Rc<CssSelector>,
+    pub specificity: CssSpecificity,
+    /// The position of the rule among the rules of the matcher.
+    pub order: usize,
+}
+
+/// A declaration of a rule matching a node.
+#[derive(Clone)]
+pub struct CssMatchedDeclaration {
+    pub property: Rc<CssProperty>,
+    pub matched_rule: CssMatchedRule,
+}
+
+impl CssSelectorMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `@namespace` definitions and rules of a style sheet,
+    /// ignoring `@media` rules.
+    /// Rules of documents added later take precedence over rules
+    /// of the same specificity.
+    pub fn add_document(&mut self, document: &CssDocument) {
+        self.add_document_directives(document, None);
+    }
+
+    /// Adds the `@namespace` definitions and rules of a style sheet,
+    /// including the rules of the `@media` queries that apply
+    /// in the given environment, in source order.
+    pub fn add_document_with_media(&mut self, document: &CssDocument, environment: &CssMediaEnvironment) {
+        self.add_document_directives(document, Some(environment));
+    }
+
+    fn add_document_directives(&mut self, document: &CssDocument, environment: Option<&CssMediaEnvironment>) {
+        for directive in document.directives.iter() {
+            match directive.as_ref() {
+                CssDirective::NamespaceDefinition(defn) => {
+                    self.namespaces.insert(defn.prefix.0.clone(), defn.uri.0.clone());
+                },
+                CssDirective::Rule(rule) => {
+                    self.rules.push(Rc::new(rule.clone()));
+                },
+                CssDirective::MediaQuery(query) if environment.is_some_and(|environment| environment.matches(query)) => {
+                    self.rules.extend(query.rules.iter().cloned());
+                },
+                _ => {},
+            }
+        }
+    }
+
+    /// Defines a namespace prefix used by selectors.
+    pub fn set_namespace(&mut self, prefix: &str, uri: &str) {
+        self.namespaces.insert(prefix.to_owned(), uri.to_owned());
+    }
+
+    /// Adds a rule, taking precedence over previous rules
+    /// of the same specificity.
+    pub fn add_rule(&mut self, rule: &Rc<CssRule>) {
+        self.rules.push(rule.clone());
+    }
+
+    /// Returns the rules matching a node, in cascade order, that is,
+    /// ordered by specificity and then by source order, with
+    /// the prevailing rules last.
+    pub fn matching_rules(&self, node: &Rc<CssStyleNode>) -> Vec<CssMatchedRule> {
+        let mut matched = vec![];
+        for (order, rule) in self.rules.iter().enumerate() {
+            let best = rule.selectors.iter()
+                .filter(|selector| self.matches(selector, node))
+                .map(|selector| (CssSpecificity::of(selector), selector))
+                .max_by(|a, b| a.0.cmp(&b.0));
+            if let Some((specificity, selector)) = best {
+                matched.push(CssMatchedRule {
+                    rule: rule.clone(),
+                    selector: selector.clone(),
+                    specificity,
+                    order,
+                });
+            }
+        }
+        matched.sort_by(|a, b| match a.specificity.cmp(&b.specificity) {
+            Ordering::Equal => a.order.cmp(&b.order),
+            ordering => ordering,
+        });
+        matched
+    }
+
+    /// Returns the declarations of the rules matching a node,
+    /// in cascade order, with the prevailing declarations last.
+    pub fn matching_declarations(&self, node: &Rc<CssStyleNode>) -> Vec<CssMatchedDeclaration> {
+        let mut declarations = vec![];
+        for matched_rule in self.matching_rules(node) {
+            for property in matched_rule.rule.properties.iter() {
+                declarations.push(CssMatchedDeclaration {
+                    property: property.clone(),
+                    matched_rule: matched_rule.clone(),
+                });
+            }
+        }
+        declarations
+    }
+
+    /// Returns the prevailing declaration of every property styling
+    /// a node, by normalized property name.
+    pub fn cascade(&self, node: &Rc<CssStyleNode>) -> HashMap<String, Rc<CssProperty>> {
+        let mut cascade = HashMap::new();
+        for declaration in self.matching_declarations(node) {
+            cascade.insert(declaration.property.name.0.clone(), declaration.property);
+        }
+        cascade
+    }
+
+    /// Indicates whether a selector matches a node.
+    pub fn matches(&self, selector: &CssSelector, node: &Rc<CssStyleNode>) -> bool {
+        match selector {
+            CssSelector::Invalidated(_) => false,
+            CssSelector::Base(base) => self.matches_base(base, node),
+            CssSelector::Combinator(combinator) => {
+                if !self.matches(&combinator.right, node) {
+                    return false;
+                }
+                match combinator.combinator_type {
+                    CssCombinatorType::Descendant => {
+                        let mut ancestor = node.parent();
+                        while let Some(node) = ancestor {
+                            if self.matches(&combinator.left, &node) {
+                                return true;
+                            }
+                            ancestor = node.parent();
+                        }
+                        false
+                    },
+                    CssCombinatorType::Child => node.parent().is_some_and(|parent| self.matches(&combinator.left, &parent)),
+                    CssCombinatorType::Preceded => node.previous_siblings().first().is_some_and(|sibling| self.matches(&combinator.left, sibling)),
+                    CssCombinatorType::Sibling => node.previous_siblings().iter().any(|sibling| self.matches(&combinator.left, sibling)),
+                }
+            },
+        }
+    }
+
+    fn matches_base(&self, base: &CssBaseSelector, node: &CssStyleNode) -> bool {
+        if let Some((name, _)) = base.element_name.as_ref().filter(|(name, _)| name != "*") {
+            let uri = match &base.namespace_prefix {
+                Some((prefix, _)) => {
+                    let Some(uri) = self.namespaces.get(prefix) else {
+                        return false;
+                    };
+                    Some(uri)
+                },
+                None => self.namespaces.get(""),
+            };
+            let matches_type = |(type_uri, type_local_name): &(String, String)| {
+                type_local_name == name && uri.is_none_or(|uri| uri == type_uri)
+            };
+            if !(matches_type(&node.type_name) || node.supertype_names.iter().any(matches_type)) {
+                return false;
+            }
+        }
+        base.conditions.iter().all(|condition| self.matches_condition(condition, node))
+    }
+
+    fn matches_condition(&self, condition: &CssSelectorCondition, node: &CssStyleNode) -> bool {
+        match condition {
+            CssSelectorCondition::Invalidated(_) => false,
+            CssSelectorCondition::Class((name, _)) => node.style_names.contains(name),
+            CssSelectorCondition::Id((id, _)) => node.id.as_ref() == Some(id),
+            CssSelectorCondition::Pseudo((state, _)) => node.states.contains(state),
+            CssSelectorCondition::PseudoElement(_) => false,
+            CssSelectorCondition::Not { condition, .. } => !self.matches_condition(condition, node),
+            CssSelectorCondition::Attribute { name, operator, value, .. } => {
+                let Some(actual) = node.attributes.get(&name.0) else {
+                    return false;
+                };
+                let (Some(operator), Some((value, _))) = (operator, value) else {
+                    return true;
+                };
+                match operator {
+                    CssAttributeOperator::Equals => actual == value,
+                    CssAttributeOperator::BeginsWith => actual.starts_with(value.as_str()),
+                    CssAttributeOperator::EndsWith => actual.ends_with(value.as_str()),
+                    CssAttributeOperator::Contains => actual.contains(value.as_str()),
+                    CssAttributeOperator::ListMatch => actual.split_whitespace().any(|item| item == value),
+                    CssAttributeOperator::HreflangMatch => actual == value || actual.starts_with(&format!("{value}-")),
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_selector_matching() {
+        let css = CompilationUnit::new(None, r#"@namespace s "library://ns.adobe.com/flex/spark";
+@namespace mx "library://ns.adobe.com/flex/mx";
+s|Button { color: red; font-size: 10 }
+mx|Button { color: green }
+.primary { color: blue }
+s|Group s|Button { font-weight: bold }
+#save:not(.primary) { color: yellow }
+s|Button[label="Save"]:down { color: black }
+s|Button#save.primary { color: gray }
+s|SkinnableComponent { chrome-color: white }"#.into());
+        let document = CssParserFacade(&css, default()).parse_document();
+        assert!(!css.invalidated());
+        let Some(CssDirective::Rule(rule)) = document.directives.last().map(|d| d.as_ref()) else { panic!() };
+        assert_eq!(CssSpecificity::of(&rule.selectors[0]), CssSpecificity(0, 0, 1));
+
+        let mut matcher = CssSelectorMatcher::new();
+        matcher.add_document(&document);
+
+        let mxml = CompilationUnit::new(None, r#"<s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark">
+    <fx:Declarations/>
+    <s:layout><s:VerticalLayout/></s:layout>
+    <s:Group>
+        <s:Button id="save" styleName="primary"/>
+    </s:Group>
+    <s:Button id="cancel"/>
+</s:Application>"#.into());
+        let document = ParserFacade(&mxml, default()).parse_mxml();
+        let root = CssStyleNode::from_mxml(&document).unwrap();
+        let children = root.children();
+        assert_eq!(children.len(), 3);
+        let save = children[1].children()[0].clone();
+        let cancel = children[2].clone();
+
+        let rules: Vec<String> = matcher.matching_rules(&save).iter().map(|rule| CssSerializer::format_selector(&rule.selector)).collect();
+        assert_eq!(rules, ["s|Button", "s|Group s|Button", ".primary", "s|Button#save.primary"]);
+        let cascade = matcher.cascade(&save);
+        assert_eq!(cascade["color"].value.location().text(), "gray");
+        assert_eq!(cascade["fontWeight"].value.location().text(), "bold");
+
+        let cascade = matcher.cascade(&cancel);
+        assert_eq!(cascade["color"].value.location().text(), "red");
+        assert!(!cascade.contains_key("fontWeight"));
+
+        let mut button = CssStyleNode::new("library://ns.adobe.com/flex/spark", "Button");
+        button.id = Some("save".into());
+        button.states.push("down".into());
+        button.attributes.insert("label".into(), "Save".into());
+        button.supertype_names.push(("library://ns.adobe.com/flex/spark".into(), "SkinnableComponent".into()));
+        let cascade = matcher.cascade(&Rc::new(button));
+        assert_eq!(cascade["color"].value.location().text(), "yellow");
+        assert_eq!(cascade["chromeColor"].value.location().text(), "white");
+    }
+}
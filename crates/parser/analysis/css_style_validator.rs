@@ -0,0 +1,465 @@
+use crate::ns::*;
+
+/// A style declared by `[Style]` metadata, such as
+/// `[Style(name="color", type="uint", format="Color", inherit="yes")]`.
+#[derive(Clone, Debug)]
+pub struct CssStyleMetadata {
+    pub name: String,
+    /// The `type` entry, such as `uint`, `Number`, `String` or `Class`.
+    pub type_name: Option<String>,
+    /// The `format` entry, such as `Color`, `Length` or `Time`.
+    pub format: Option<String>,
+    /// The comma-separated `enumeration` entry.
+    pub enumeration: Vec<String>,
+    pub inherit: bool,
+}
+
+impl CssStyleMetadata {
+    /// Reads `[Style]` metadata, returning `None` for other metadata
+    /// or if the `name` entry is missing.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        if metadata.name.0 != "Style" {
+            return None;
+        }
+        let mut name = None;
+        let mut type_name = None;
+        let mut format = None;
+        let mut enumeration = vec![];
+        let mut inherit = false;
+        for entry in metadata.entries.iter().flatten() {
+            let (MetadataValue::String((value, _)) | MetadataValue::IdentifierString((value, _))) = entry.value.as_ref();
+            match entry.key.as_ref().map(|(key, _)| key.as_str()) {
+                Some("name") => name = Some(value.clone()),
+                Some("type") => type_name = Some(value.clone()),
+                Some("format") => format = Some(value.clone()),
+                Some("enumeration") => enumeration = value.split(',').map(|value| value.trim().to_owned()).collect(),
+                Some("inherit") => inherit = value == "yes",
+                _ => {},
+            }
+        }
+        Some(Self {
+            name: name?,
+            type_name,
+            format,
+            enumeration,
+            inherit,
+        })
+    }
+}
+
+struct CssStyleClass {
+    /// Candidate qualified names of the superclass, in order of
+    /// precedence; the first registered class is the superclass.
+    superclass: Vec<String>,
+    styles: Vec<CssStyleMetadata>,
+}
+
+/// Validates the declarations of CSS rules against the `[Style]`
+/// metadata of the component classes their selectors designate.
+///
+/// The following are reported as warnings:
+///
+/// - Styles not declared by a component class or its superclasses.
+/// - Values not matching the style type or format, such as a string
+///   for a style of the `Color` format.
+/// - Values outside the style enumeration.
+/// - `ClassReference(...)` values naming unregistered classes.
+///
+/// Type selectors are resolved to classes through the `@namespace`
+/// definitions of the style sheet and a `MxmlNameResolver`; unprefixed
+/// type selectors resolve through the default namespace, such as
+/// `@namespace "library://ns.adobe.com/flex/spark"`, if any, and name
+/// classes of the top-level package otherwise. Rules whose
+/// selectors designate no registered class, such as `.styleName` and
+/// `global`, are only checked for unresolved class references.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let classes = CompilationUnit::new(None, r#"
+///     package com.acme {
+///         [Style(name="color", type="uint", format="Color")]
+///         public class Label {}
+///     }
+/// "#.into());
+/// let program = ParserFacade(&classes, default()).parse_program();
+///
+/// let mut validator = CssStyleValidator::new(MxmlNameResolver::new());
+/// validator.add_program(&program);
+///
+/// let style_sheet = CompilationUnit::new(None, r#"
+///     @namespace acme "com.acme.*";
+///     acme|Label { color: "red"; fontSize: 12 }
+/// "#.into());
+/// let document = CssParserFacade(&style_sheet, default()).parse_document();
+/// validator.verify_document(&document);
+/// assert_eq!(style_sheet.diagnostics().len(), 2);
+/// ```
+pub struct CssStyleValidator {
+    name_resolver: MxmlNameResolver,
+    classes: HashMap<String, CssStyleClass>,
+}
+
+impl CssStyleValidator {
+    pub fn new(name_resolver: MxmlNameResolver) -> Self {
+        Self {
+            name_resolver,
+            classes: HashMap::new(),
+        }
+    }
+
+    /// Registers a class given its fully qualified name, the fully
+    /// qualified name of its superclass and its metadata.
+    pub fn add_class(&mut self, qualified_name: &str, superclass: Option<&str>, metadata: &[Rc<Metadata>]) {
+        self.classes.insert(qualified_name.to_owned(), CssStyleClass {
+            superclass: superclass.into_iter().map(|name| name.to_owned()).collect(),
+            styles: metadata.iter().filter_map(|metadata| CssStyleMetadata::from_metadata(metadata)).collect(),
+        });
+    }
+
+    /// Registers the classes defined by the packages of a program.
+    /// Superclass names are resolved through the imports of
+    /// the enclosing package.
+    pub fn add_program(&mut self, program: &Program) {
+        for package in program.packages.iter() {
+            let package_name = package.name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".");
+            let mut imports = vec![];
+            for directive in package.block.directives.iter() {
+                if let Directive::ImportDirective(import) = directive.as_ref() {
+                    imports.push(import);
+                }
+            }
+            for directive in package.block.directives.iter() {
+                let Directive::ClassDefinition(defn) = directive.as_ref() else {
+                    continue;
+                };
+                let qualified_name = qualify(&package_name, &defn.name.0);
+                let metadata: Vec<Rc<Metadata>> = defn.attributes.iter().filter_map(|attribute| {
+                    if let Attribute::Metadata(metadata) = attribute { Some(metadata.clone()) } else { None }
+                }).collect();
+                let superclass = defn.extends_clause.as_ref()
+                    .map(|name| superclass_candidates(&name.location().text(), &package_name, &imports))
+                    .unwrap_or_default();
+                self.classes.insert(qualified_name, CssStyleClass {
+                    superclass,
+                    styles: metadata.iter().filter_map(|metadata| CssStyleMetadata::from_metadata(metadata)).collect(),
+                });
+            }
+        }
+    }
+
+    /// Indicates whether a class of a given fully qualified name
+    /// has been registered.
+    pub fn has_class(&self, qualified_name: &str) -> bool {
+        self.classes.contains_key(qualified_name)
+    }
+
+    /// Returns the styles declared by a class or its superclasses,
+    /// keyed by style name.
+    pub fn styles(&self, qualified_name: &str) -> HashMap<String, CssStyleMetadata> {
+        let mut styles = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut class_name = Some(qualified_name.to_owned());
+        while let Some(name) = class_name {
+            if !visited.insert(name.clone()) {
+                break;
+            }
+            let Some(class) = self.classes.get(&name) else {
+                break;
+            };
+            for style in class.styles.iter() {
+                styles.entry(style.name.clone()).or_insert_with(|| style.clone());
+            }
+            class_name = class.superclass.iter().find(|name| self.classes.contains_key(*name)).cloned();
+        }
+        styles
+    }
+
+    /// Validates the rules of a style sheet, including the rules
+    /// of `@media` queries, reporting warnings.
+    pub fn verify_document(&self, document: &CssDocument) {
+        let mut namespaces = HashMap::new();
+        for directive in document.directives.iter() {
+            match directive.as_ref() {
+                CssDirective::NamespaceDefinition(defn) => {
+                    namespaces.insert(defn.prefix.0.clone(), defn.uri.0.clone());
+                },
+                CssDirective::Rule(rule) => {
+                    self.verify_rule(rule, &namespaces);
+                },
+                CssDirective::MediaQuery(query) => {
+                    for rule in query.rules.iter() {
+                        self.verify_rule(rule, &namespaces);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn verify_rule(&self, rule: &CssRule, namespaces: &HashMap<String, String>) {
+        // Component classes designated by the selectors, along with
+        // their local names.
+        let mut components: Vec<(String, HashMap<String, CssStyleMetadata>)> = vec![];
+        for selector in rule.selectors.iter() {
+            let Some((local_name, class_name)) = self.resolve_selector(selector, namespaces) else {
+                continue;
+            };
+            if self.has_class(&class_name) {
+                components.push((local_name, self.styles(&class_name)));
+            }
+        }
+
+        for property in rule.properties.iter() {
+            self.verify_class_references(&property.value);
+
+            let name = property.name.1.text();
+            if let Some((local_name, _)) = components.iter().find(|(_, styles)| !styles.contains_key(&property.name.0)) {
+                let compilation_unit = property.location.compilation_unit();
+                compilation_unit.add_diagnostic(Diagnostic::new_warning(&property.name.1, DiagnosticKind::UnknownStyle, diagarg![name, local_name.clone()]));
+                continue;
+            }
+            if let Some((_, styles)) = components.first() {
+                self.verify_value(&name, &styles[&property.name.0], &property.value);
+            }
+        }
+    }
+
+    /// Resolves the local name and class name of the type designated
+    /// by the rightmost compound selector.
+    fn resolve_selector(&self, selector: &CssSelector, namespaces: &HashMap<String, String>) -> Option<(String, String)> {
+        match selector {
+            CssSelector::Base(base) => {
+                let (local_name, _) = base.element_name.as_ref()?;
+                if local_name == "global" || local_name == "*" {
+                    return None;
+                }
+                let class_name = match (base.namespace_prefix.as_ref(), namespaces.get("")) {
+                    (Some((prefix, _)), _) => self.name_resolver.resolve(namespaces.get(prefix)?, local_name)?,
+                    (None, Some(uri)) => self.name_resolver.resolve(uri, local_name)?,
+                    (None, None) => local_name.clone(),
+                };
+                Some((local_name.clone(), class_name))
+            },
+            CssSelector::Combinator(combinator) => self.resolve_selector(&combinator.right, namespaces),
+            CssSelector::Invalidated(_) => None,
+        }
+    }
+
+    fn verify_value(&self, name: &str, style: &CssStyleMetadata, value: &CssPropertyValue) {
+        match value {
+            CssPropertyValue::Invalidated(_) |
+            CssPropertyValue::PropertyReference(_) => {},
+            CssPropertyValue::Array(_) |
+            CssPropertyValue::MultiValue(_) if style.type_name.as_deref() == Some("Array") => {},
+            CssPropertyValue::Array(array) => {
+                for element in array.elements.iter() {
+                    self.verify_value(name, style, element);
+                }
+            },
+            CssPropertyValue::MultiValue(multi_value) => {
+                for element in multi_value.values.iter() {
+                    self.verify_value(name, style, element);
+                }
+            },
+            _ => {
+                let compilation_unit = value.location().compilation_unit();
+                if !style.enumeration.is_empty() {
+                    if let Some(text) = css_text_value(value) {
+                        if !style.enumeration.contains(&text) {
+                            let expected = style.enumeration.iter().map(|value| format!("'{value}'")).collect::<Vec<_>>().join(", ");
+                            compilation_unit.add_diagnostic(Diagnostic::new_warning(&value.location(), DiagnosticKind::StyleValueNotInEnumeration, diagarg![text, name.to_owned(), expected]));
+                        }
+                        return;
+                    }
+                }
+                if let Some(expected) = css_value_mismatch(style, value) {
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&value.location(), DiagnosticKind::InvalidStyleValue, diagarg![name.to_owned(), expected.to_owned()]));
+                }
+            },
+        }
+    }
+
+    fn verify_class_references(&self, value: &CssPropertyValue) {
+        match value {
+            CssPropertyValue::ClassReference(reference) => {
+                if reference.name.0 != "null" && !self.has_class(&reference.name.0) {
+                    let compilation_unit = reference.location.compilation_unit();
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&reference.name.1, DiagnosticKind::UnresolvedCssClassReference, diagarg![reference.name.0.clone()]));
+                }
+            },
+            CssPropertyValue::Array(array) => {
+                for element in array.elements.iter() {
+                    self.verify_class_references(element);
+                }
+            },
+            CssPropertyValue::MultiValue(multi_value) => {
+                for element in multi_value.values.iter() {
+                    self.verify_class_references(element);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+fn qualify(package_name: &str, name: &str) -> String {
+    if package_name.is_empty() { name.to_owned() } else { format!("{package_name}.{name}") }
+}
+
+/// Returns the candidate qualified names of a superclass name
+/// appearing in a package.
+fn superclass_candidates(name: &str, package_name: &str, imports: &[&ImportDirective]) -> Vec<String> {
+    let name: String = name.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if name.contains('.') {
+        return vec![name];
+    }
+    let mut candidates = vec![];
+    for import in imports.iter() {
+        if import.alias.is_some() {
+            continue;
+        }
+        let import_package = import.package_name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".");
+        match &import.import_specifier {
+            ImportSpecifier::Identifier((imported, _)) if *imported == name => {
+                candidates.insert(0, qualify(&import_package, &name));
+            },
+            ImportSpecifier::Wildcard(_) => {
+                candidates.push(qualify(&import_package, &name));
+            },
+            _ => {},
+        }
+    }
+    candidates.push(qualify(package_name, &name));
+    if !package_name.is_empty() {
+        candidates.push(name);
+    }
+    candidates
+}
+
+/// Returns the text of a string, identifier or color name.
+fn css_text_value(value: &CssPropertyValue) -> Option<String> {
+    match value {
+        CssPropertyValue::String(string) => Some(string.value.clone()),
+        CssPropertyValue::Identifier(identifier) => Some(identifier.value.clone()),
+        CssPropertyValue::Color(color) => {
+            let text = color.location.text();
+            if text.starts_with('#') { None } else { Some(text) }
+        },
+        _ => None,
+    }
+}
+
+/// Returns a description of the expected value if a value does not
+/// match the type or format of a style.
+fn css_value_mismatch(style: &CssStyleMetadata, value: &CssPropertyValue) -> Option<&'static str> {
+    if style.format.as_deref() == Some("Color") {
+        return match value {
+            CssPropertyValue::Color(_) |
+            CssPropertyValue::RgbColor(_) => None,
+            CssPropertyValue::Number(number) if number.unit.is_none() => None,
+            _ => Some("a color"),
+        };
+    }
+    match style.type_name.as_deref() {
+        Some("Number" | "int" | "uint") => {
+            if matches!(value, CssPropertyValue::Number(_)) { None } else { Some("a number") }
+        },
+        Some("Boolean") => {
+            if matches!(value, CssPropertyValue::Identifier(identifier) if identifier.value == "true" || identifier.value == "false") {
+                None
+            } else {
+                Some("'true' or 'false'")
+            }
+        },
+        Some("String") => {
+            if css_text_value(value).is_some() { None } else { Some("a string") }
+        },
+        Some("Class") => {
+            match value {
+                CssPropertyValue::ClassReference(_) |
+                CssPropertyValue::Embed(_) |
+                CssPropertyValue::Url(_) => None,
+                _ => Some("a class reference"),
+            }
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_style_validation() {
+        let classes = CompilationUnit::new(None, r#"
+            package spark.components.supportClasses {
+                [Style(name="color", type="uint", format="Color", inherit="yes")]
+                [Style(name="fontWeight", type="String", enumeration="normal,bold", inherit="yes")]
+                [Style(name="skinClass", type="Class")]
+                public class SkinnableComponent {}
+            }
+            package spark.components {
+                import spark.components.supportClasses.*;
+                [Style(name="cornerRadius", type="Number", format="Length")]
+                public class Button extends SkinnableComponent {}
+            }
+            package spark.skins {
+                public class ButtonSkin {}
+            }
+        "#.into());
+        let program = ParserFacade(&classes, default()).parse_program();
+        assert!(classes.diagnostics().is_empty());
+
+        let mut manifest = FlexManifest::new();
+        manifest.add_component("Button", "spark.components.Button");
+        let mut resolver = MxmlNameResolver::new();
+        resolver.add_manifest("library://ns.adobe.com/flex/spark", manifest);
+        let mut validator = CssStyleValidator::new(resolver);
+        validator.add_program(&program);
+        assert_eq!(validator.styles("spark.components.Button").len(), 4);
+
+        let style_sheet = CompilationUnit::new(None, r#"
+            @namespace s "library://ns.adobe.com/flex/spark";
+            s|Button {
+                color: #FF0000;
+                fontWeight: bold;
+                cornerRadius: 4;
+                skinClass: ClassReference("spark.skins.ButtonSkin");
+            }
+            s|Button.primary {
+                colour: red;
+                color: "red";
+                font-weight: heavy;
+                skinClass: ClassReference("spark.skins.PrimarySkin");
+            }
+            .other {
+                anything: 1;
+            }
+        "#.into());
+        let document = CssParserFacade(&style_sheet, default()).parse_document();
+        validator.verify_document(&document);
+        let diagnostics = style_sheet.diagnostics();
+        assert_eq!(diagnostics.len(), 4);
+        assert!(diagnostics[0].kind() == DiagnosticKind::UnknownStyle);
+        assert_eq!(diagnostics[0].location().text(), "colour");
+        assert!(diagnostics[1].kind() == DiagnosticKind::InvalidStyleValue);
+        assert!(diagnostics[2].kind() == DiagnosticKind::StyleValueNotInEnumeration);
+        assert_eq!(diagnostics[2].location().text(), "heavy");
+        assert!(diagnostics[3].kind() == DiagnosticKind::UnresolvedCssClassReference);
+
+        // Unprefixed type selectors resolve through the default namespace
+        let style_sheet = CompilationUnit::new(None, r#"
+            @namespace "library://ns.adobe.com/flex/spark";
+            Button { colour: red }
+        "#.into());
+        let document = CssParserFacade(&style_sheet, default()).parse_document();
+        validator.verify_document(&document);
+        let diagnostics = style_sheet.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind() == DiagnosticKind::UnknownStyle);
+    }
+}
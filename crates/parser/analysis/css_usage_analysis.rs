@@ -0,0 +1,382 @@
+use crate::ns::*;
+
+/// The result of a [`CssUsageAnalysis`].
+pub struct CssUsageReport {
+    /// Selectors that match no component in use, in source order.
+    pub unused_selectors: Vec<Rc<CssSelector>>,
+    /// Style names assigned in MXML or ActionScript code that
+    /// no CSS class selector defines, along with their locations.
+    pub undefined_style_names: Vec<(String, Location)>,
+}
+
+/// Detects CSS selectors that can never match and style names
+/// used without a CSS definition across MXML and ActionScript 3 code.
+///
+/// Usage consists of:
+///
+/// - The types of MXML elements, resolved through a `MxmlNameResolver`.
+/// - `styleName` and `id` attribute values of MXML elements.
+/// - `styleName = "..."` assignments in ActionScript 3 code,
+///   including MXML event handlers and, for documents parsed with
+///   the `parse_mxml_scripts` option, MXML scripts.
+/// - `new T(...)` expressions in ActionScript 3 code.
+///
+/// A selector is unused if any of its compound selectors designates
+/// a type, style name or identifier not in use. Since type selectors
+/// also match subclasses, a type is in use if a subclass of it defined
+/// by an added program is in use. Types are compared by their unqualified
+/// names, so that no selector is reported unless it certainly never matches;
+/// type selectors with an unresolvable namespace prefix, `global`,
+/// `*` and pseudo conditions are assumed to match.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let css = CompilationUnit::new(None, r#"
+///     @namespace s "library://ns.adobe.com/flex/spark";
+///     s|Button.primary { color: blue }
+///     s|Label { color: black }
+/// "#.into());
+/// let document = CssParserFacade(&css, default()).parse_document();
+///
+/// let mxml = CompilationUnit::new(None, r#"
+///     <s:Group xmlns:s="library://ns.adobe.com/flex/spark">
+///         <s:Button styleName="primary secondary"/>
+///     </s:Group>
+/// "#.into());
+/// let mxml = ParserFacade(&mxml, default()).parse_mxml();
+///
+/// let mut manifest = FlexManifest::new();
+/// manifest.add_component("Button", "spark.components.Button");
+/// manifest.add_component("Group", "spark.components.Group");
+/// manifest.add_component("Label", "spark.components.Label");
+/// let mut resolver = MxmlNameResolver::new();
+/// resolver.add_manifest("library://ns.adobe.com/flex/spark", manifest);
+///
+/// let mut analysis = CssUsageAnalysis::new(resolver);
+/// analysis.add_style_sheet(&document);
+/// analysis.add_mxml(&mxml);
+/// let report = analysis.report();
+/// assert_eq!(report.unused_selectors[0].location().text(), "s|Label");
+/// assert_eq!(report.undefined_style_names[0].0, "secondary");
+/// ```
+pub struct CssUsageAnalysis {
+    name_resolver: MxmlNameResolver,
+    selectors: Vec<CssUsageSelector>,
+    /// Unqualified names of the types in use.
+    used_types: HashSet<String>,
+    /// Unqualified superclass names by unqualified class name.
+    superclasses: HashMap<String, String>,
+    style_names: Vec<(String, Location)>,
+    ids: HashSet<String>,
+}
+
+/// A selector along with the `@namespace` definitions of its style sheet.
+struct CssUsageSelector {
+    selector: Rc<CssSelector>,
+    namespaces: Rc<HashMap<String, String>>,
+}
+
+impl CssUsageAnalysis {
+    pub fn new(name_resolver: MxmlNameResolver) -> Self {
+        Self {
+            name_resolver,
+            selectors: vec![],
+            used_types: HashSet::new(),
+            superclasses: HashMap::new(),
+            style_names: vec![],
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Adds the selectors of the rules of a style sheet,
+    /// including rules nested in `@media` queries.
+    pub fn add_style_sheet(&mut self, document: &CssDocument) {
+        let mut namespaces = HashMap::new();
+        for directive in document.directives.iter() {
+            if let CssDirective::NamespaceDefinition(defn) = directive.as_ref() {
+                namespaces.insert(defn.prefix.0.clone(), defn.uri.0.clone());
+            }
+        }
+        let namespaces = Rc::new(namespaces);
+        for directive in document.directives.iter() {
+            match directive.as_ref() {
+                CssDirective::Rule(rule) => self.add_rule(rule, &namespaces),
+                CssDirective::MediaQuery(query) => {
+                    for rule in query.rules.iter() {
+                        self.add_rule(rule, &namespaces);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn add_rule(&mut self, rule: &CssRule, namespaces: &Rc<HashMap<String, String>>) {
+        for selector in rule.selectors.iter() {
+            self.selectors.push(CssUsageSelector {
+                selector: selector.clone(),
+                namespaces: namespaces.clone(),
+            });
+        }
+    }
+
+    /// Marks a type as in use, given its qualified name, such as
+    /// a component instantiated by code that is not analyzed.
+    pub fn add_used_type(&mut self, qualified_name: &str) {
+        self.used_types.insert(unqualified_name(qualified_name).to_owned());
+    }
+
+    /// Adds the usage within a MXML document, along with the
+    /// style sheets of its `<fx:Style>` elements.
+    pub fn add_mxml(&mut self, document: &Mxml) {
+        for content in document.content.iter() {
+            if let MxmlContent::Element(element) = content.as_ref() {
+                self.add_mxml_element(element);
+            }
+        }
+    }
+
+    fn add_mxml_element(&mut self, element: &MxmlElement) {
+        if let Some(class_name) = self.name_resolver.resolve_element(element) {
+            self.add_used_type(&class_name);
+        }
+        if let Some(document) = &element.style_document {
+            self.add_style_sheet(document);
+        }
+        for attribute in element.attributes.iter() {
+            if attribute.xmlns || attribute.name.prefix.is_some() {
+                continue;
+            }
+            if let Some(directives) = &attribute.event_handler {
+                TreeWalker::walk_directives(self, directives);
+            }
+            if attribute.binding.is_some() {
+                continue;
+            }
+            match attribute.name.name.as_str() {
+                "styleName" => self.add_style_names(&attribute.value.0, &attribute.value.1),
+                "id" => {
+                    self.ids.insert(attribute.value.0.clone());
+                },
+                _ => {},
+            }
+        }
+        if let Some(directives) = &element.script_directives {
+            TreeWalker::walk_directives(self, directives);
+        }
+        for content in element.content.iter().flatten() {
+            if let MxmlContent::Element(child) = content.as_ref() {
+                self.add_mxml_element(child);
+            }
+        }
+    }
+
+    /// Adds the usage within an ActionScript 3 program, along with
+    /// the superclasses of the classes it defines.
+    pub fn add_program(&mut self, program: &Program) {
+        TreeWalker::walk_program(self, program);
+    }
+
+    fn add_style_names(&mut self, value: &str, location: &Location) {
+        for name in value.split_whitespace() {
+            self.style_names.push((name.to_owned(), location.clone()));
+        }
+    }
+
+    pub fn report(&self) -> CssUsageReport {
+        // Types in use along with their superclasses.
+        let mut used_types = HashSet::new();
+        for name in self.used_types.iter() {
+            let mut name = Some(name);
+            while let Some(n) = name {
+                if !used_types.insert(n.clone()) {
+                    break;
+                }
+                name = self.superclasses.get(n);
+            }
+        }
+        let used_style_names: HashSet<&str> = self.style_names.iter().map(|(name, _)| name.as_str()).collect();
+
+        let mut unused_selectors = vec![];
+        let mut defined_style_names = HashSet::new();
+        for CssUsageSelector { selector, namespaces } in self.selectors.iter() {
+            let mut used = true;
+            for base in compound_selectors(selector) {
+                used &= self.base_selector_type_used(base, namespaces, &used_types);
+                for condition in base.conditions.iter() {
+                    match condition.as_ref() {
+                        CssSelectorCondition::Class((name, _)) => {
+                            defined_style_names.insert(name.clone());
+                            used &= used_style_names.contains(name.as_str());
+                        },
+                        CssSelectorCondition::Id((id, _)) => {
+                            used &= self.ids.contains(id);
+                        },
+                        _ => {},
+                    }
+                }
+            }
+            if !used {
+                unused_selectors.push(selector.clone());
+            }
+        }
+
+        let undefined_style_names = self.style_names.iter()
+            .filter(|(name, _)| !defined_style_names.contains(name))
+            .cloned()
+            .collect();
+
+        CssUsageReport {
+            unused_selectors,
+            undefined_style_names,
+        }
+    }
+
+    fn base_selector_type_used(&self, base: &CssBaseSelector, namespaces: &HashMap<String, String>, used_types: &HashSet<String>) -> bool {
+        let Some((local_name, _)) = base.element_name.as_ref() else {
+            return true;
+        };
+        if local_name == "global" || local_name == "*" {
+            return true;
+        }
+        if let Some((prefix, _)) = base.namespace_prefix.as_ref() {
+            let resolved = namespaces.get(prefix).and_then(|uri| self.name_resolver.resolve(uri, local_name));
+            let Some(class_name) = resolved else {
+                return true;
+            };
+            return used_types.contains(unqualified_name(&class_name));
+        }
+        used_types.contains(local_name)
+    }
+}
+
+impl TreeVisitor for CssUsageAnalysis {
+    fn visit_directive(&mut self, directive: &Rc<Directive>) -> bool {
+        if let Directive::ClassDefinition(defn) = directive.as_ref() {
+            if let Some(superclass) = defn.extends_clause.as_ref().and_then(|exp| type_expression_name(exp)) {
+                self.superclasses.insert(defn.name.0.clone(), superclass);
+            }
+        }
+        true
+    }
+
+    fn visit_expression(&mut self, expression: &Rc<Expression>) -> bool {
+        match expression.as_ref() {
+            Expression::Assignment(assignment) if assignment.compound.is_none() => {
+                let name = match assignment.left.as_ref() {
+                    Expression::Member(member) => member.identifier.to_identifier_name(),
+                    left => left.to_identifier_name(),
+                };
+                if let (Some((name, _)), Expression::StringLiteral(literal)) = (name, assignment.right.as_ref()) {
+                    if name == "styleName" {
+                        self.add_style_names(&literal.value, &literal.location);
+                    }
+                }
+            },
+            Expression::New(new_expression) => {
+                if let Some(name) = type_expression_name(&new_expression.base) {
+                    self.used_types.insert(name);
+                }
+            },
+            _ => {},
+        }
+        true
+    }
+}
+
+/// Returns the unqualified name of a type expression such as
+/// `Button` or `spark.components.Button`.
+fn type_expression_name(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::Member(member) => member.identifier.to_identifier_name().map(|(name, _)| name),
+        _ => expression.to_identifier_name().map(|(name, _)| name),
+    }
+}
+
+/// Returns the compound selectors of a selector.
+fn compound_selectors(selector: &CssSelector) -> Vec<&CssBaseSelector> {
+    match selector {
+        CssSelector::Base(base) => vec![base],
+        CssSelector::Combinator(combinator) => {
+            let mut result = compound_selectors(&combinator.left);
+            result.extend(compound_selectors(&combinator.right));
+            result
+        },
+        CssSelector::Invalidated(_) => vec![],
+    }
+}
+
+fn unqualified_name(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_css_usage() {
+        let css = CompilationUnit::new(None, r#"
+            @namespace s "library://ns.adobe.com/flex/spark";
+            @namespace acme "com.acme.*";
+            @namespace x "http://example.com/unknown";
+            global { fontSize: 12 }
+            s|Button { color: red }
+            s|Label.title { color: blue }
+            s|Panel s|Button { color: green }
+            acme|Toolbar #main { color: gray }
+            x|Anything { color: white }
+            .warning, .error { color: orange }
+            @media (os-platform: "IOS") { s|TextInput { color: black } }
+        "#.into());
+        let document = CssParserFacade(&css, default()).parse_document();
+
+        let mxml = CompilationUnit::new(None, r#"
+            <s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark" xmlns:acme="com.acme.*">
+                <fx:Script><![CDATA[
+                    function f(): void { label.styleName = "warning"; styleName = 'missing'; }
+                ]]></fx:Script>
+                <s:Label id="label" styleName="title"/>
+                <acme:Toolbar id="main"/>
+                <s:Label styleName="{currentStyle}"/>
+            </s:Application>
+        "#.into());
+        let mxml = ParserFacade(&mxml, ParserOptions {
+            parse_mxml_scripts: true,
+            parse_mxml_bindings: true,
+            ..default()
+        }).parse_mxml();
+
+        let program = CompilationUnit::new(None, r#"
+            package com.acme {
+                import spark.components.*;
+                public class SubmitButton extends Button {
+                    public function SubmitButton() { new TextInput(); }
+                }
+            }
+        "#.into());
+        let program = ParserFacade(&program, default()).parse_program();
+
+        let mut manifest = FlexManifest::new();
+        for name in ["Application", "Button", "Label", "Panel", "TextInput"] {
+            manifest.add_component(name, &format!("spark.components.{name}"));
+        }
+        let mut resolver = MxmlNameResolver::new();
+        resolver.add_manifest("library://ns.adobe.com/flex/spark", manifest);
+
+        let mut analysis = CssUsageAnalysis::new(resolver);
+        analysis.add_style_sheet(&document);
+        analysis.add_mxml(&mxml);
+        analysis.add_program(&program);
+        analysis.add_used_type("com.acme.SubmitButton");
+        let report = analysis.report();
+
+        let unused: Vec<String> = report.unused_selectors.iter().map(|selector| selector.location().text()).collect();
+        assert_eq!(unused, ["s|Panel s|Button", ".error"]);
+        let undefined: Vec<String> = report.undefined_style_names.iter().map(|(name, location)| format!("{name} {}", location.text())).collect();
+        assert_eq!(undefined, ["missing 'missing'"]);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::ns::*;
+
+/// An index of the package-level definitions of a project,
+/// such as classes, interfaces, functions, variables and namespaces.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, r#"
+///     package com.acme.views { public class MainView {} }
+///     package com.acme.utils { public function trim(s: String): String { return s } }
+/// "#.into());
+/// let program = ParserFacade(&compilation_unit, default()).parse_program();
+///
+/// let mut index = DefinitionIndex::new();
+/// index.add_program(&program);
+/// assert!(index.has_definition("com.acme.views", "MainView"));
+/// assert!(index.has_package("com.acme.utils"));
+/// assert!(index.has_package_recursive("com.acme"));
+/// assert!(!index.has_package("com.acme"));
+/// ```
+#[derive(Clone, Default)]
+pub struct DefinitionIndex {
+    /// Definition names by package name.
+    packages: HashMap<String, HashSet<String>>,
+}
+
+impl DefinitionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a package, which may have no definitions.
+    pub fn add_package(&mut self, package_name: &str) {
+        self.packages.entry(package_name.to_owned()).or_default();
+    }
+
+    /// Adds a definition, such as `("flash.display", "Sprite")`.
+    /// The top-level package is named by the empty string.
+    pub fn add_definition(&mut self, package_name: &str, name: &str) {
+        self.packages.entry(package_name.to_owned()).or_default().insert(name.to_owned());
+    }
+
+    /// Adds the package-level definitions of a program.
+    pub fn add_program(&mut self, program: &Program) {
+        for package in program.packages.iter() {
+            let package_name = package.name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".");
+            self.add_package(&package_name);
+            for name in Self::definition_names(&package.block.directives) {
+                self.add_definition(&package_name, &name);
+            }
+        }
+    }
+
+    /// Returns the names of the definitions among directives.
+    pub fn definition_names(directives: &[Rc<Directive>]) -> Vec<String> {
+        let mut names = vec![];
+        for directive in directives.iter() {
+            match directive.as_ref() {
+                Directive::ClassDefinition(defn) => names.push(defn.name.0.clone()),
+                Directive::InterfaceDefinition(defn) => names.push(defn.name.0.clone()),
+                Directive::EnumDefinition(defn) => names.push(defn.name.0.clone()),
+                Directive::NamespaceDefinition(defn) => names.push(defn.left.0.clone()),
+                Directive::TypeDefinition(defn) => names.push(defn.left.0.clone()),
+                Directive::FunctionDefinition(defn) => {
+                    if let FunctionName::Identifier((name, _)) | FunctionName::Getter((name, _)) | FunctionName::Setter((name, _)) = &defn.name {
+                        names.push(name.clone());
+                    }
+                },
+                Directive::VariableDefinition(defn) => {
+                    for binding in defn.bindings.iter() {
+                        if let Some((name, _)) = binding.destructuring.destructuring.to_identifier_name() {
+                            names.push(name);
+                        }
+                    }
+                },
+                Directive::ConfigurationDirective(d) => {
+                    names.extend(Self::definition_names(std::slice::from_ref(&d.directive)));
+                },
+                Directive::NormalConfigurationDirective(d) => {
+                    names.extend(Self::definition_names(std::slice::from_ref(&d.directive)));
+                },
+                Directive::Block(block) => names.extend(Self::definition_names(&block.directives)),
+                Directive::IncludeDirective(d) => names.extend(Self::definition_names(&d.nested_directives)),
+                _ => {},
+            }
+        }
+        names
+    }
+
+    pub fn has_package(&self, package_name: &str) -> bool {
+        self.packages.contains_key(package_name)
+    }
+
+    /// Indicates whether a package or any of its subpackages exists.
+    pub fn has_package_recursive(&self, package_name: &str) -> bool {
+        let prefix = format!("{package_name}.");
+        self.packages.keys().any(|name| name == package_name || name.starts_with(&prefix))
+    }
+
+    pub fn has_definition(&self, package_name: &str, name: &str) -> bool {
+        self.packages.get(package_name).is_some_and(|names| names.contains(name))
+    }
+
+    /// Indicates whether a package or any of its subpackages defines a name.
+    pub fn has_definition_recursive(&self, package_name: &str, name: &str) -> bool {
+        let prefix = format!("{package_name}.");
+        self.packages.iter().any(|(p, names)| (p == package_name || p.starts_with(&prefix)) && names.contains(name))
+    }
+}
@@ -0,0 +1,76 @@
+use crate::ns::*;
+
+/// A Flex component manifest, mapping the component names of
+/// a MXML namespace to fully qualified class names.
+///
+/// Manifests consist of `<component>` elements inside
+/// a `<componentPackage>` root element:
+///
+/// ```xml
+/// <componentPackage>
+///     <component id="Button" class="spark.components.Button"/>
+/// </componentPackage>
+/// ```
+///
+/// The `id` attribute defaults to the unqualified name of the class.
+#[derive(Clone, Default)]
+pub struct FlexManifest {
+    components: HashMap<String, String>,
+}
+
+impl FlexManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a manifest from a compilation unit, reporting syntax
+    /// errors and invalid components to the compilation unit.
+    pub fn parse(compilation_unit: &Rc<CompilationUnit>) -> Self {
+        let document = ParserFacade(compilation_unit, default()).parse_mxml();
+        let mut manifest = Self::new();
+        let root = document.content.iter().find_map(|content| {
+            if let MxmlContent::Element(element) = content.as_ref() { Some(element.clone()) } else { None }
+        });
+        let Some(root) = root else {
+            return manifest;
+        };
+        for content in root.content.iter().flatten() {
+            let MxmlContent::Element(element) = content.as_ref() else {
+                continue;
+            };
+            if element.name.name != "component" {
+                continue;
+            }
+            let Some((class_name, _)) = element.attribute_value("class") else {
+                compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&element.name.location, DiagnosticKind::ManifestComponentMustSpecifyClass, vec![]));
+                continue;
+            };
+            let id = element.attribute_value("id").map_or_else(|| {
+                class_name.rsplit(['.', ':']).next().unwrap().to_owned()
+            }, |(id, _)| id.clone());
+            manifest.add_component(&id, &class_name.replace(':', "."));
+        }
+        manifest
+    }
+
+    /// Reads and parses a manifest file, returning the manifest
+    /// and the compilation unit containing its diagnostics.
+    pub fn load(file_path: &str) -> std::io::Result<(Self, Rc<CompilationUnit>)> {
+        let content = std::fs::read_to_string(file_path)?;
+        let compilation_unit = CompilationUnit::new(Some(file_path.to_owned()), content);
+        Ok((Self::parse(&compilation_unit), compilation_unit))
+    }
+
+    pub fn add_component(&mut self, id: &str, class_name: &str) {
+        self.components.insert(id.to_owned(), class_name.to_owned());
+    }
+
+    /// Returns the fully qualified class name of a component.
+    pub fn get(&self, id: &str) -> Option<String> {
+        self.components.get(id).cloned()
+    }
+
+    pub fn components(&self) -> &HashMap<String, String> {
+        &self.components
+    }
+}
@@ -0,0 +1,338 @@
+use crate::ns::*;
+
+/// The result of organizing the imports of a scope.
+pub struct OrganizedImports {
+    /// The range from the first to the last import directive of the scope,
+    /// which the organized imports replace.
+    pub location: Location,
+    /// The organized import directives, one per line, with lines
+    /// after the first indented as the first import directive.
+    pub text: String,
+}
+
+/// Resolves import directives against a `DefinitionIndex`
+/// and detects unnecessary imports.
+///
+/// Imports are checked per scope, that is, the directives of a package
+/// block or the top-level directives of a program, considering only the
+/// import directives appearing directly in the scope. The following are reported:
+///
+/// - Imports of unknown packages or definitions, as verify errors.
+/// - Duplicate imports, as warnings.
+/// - Imports shadowed by a definition of the same name in the scope, as warnings.
+/// - Imports whose names are never referenced in the scope, as warnings.
+///
+/// A `import a.b.C` directive is used by a `C` or `a.b.C` reference; an aliased
+/// `import x = a.b.C` directive is used by a `x` reference; a `import a.b.*`
+/// directive is used by a reference to a definition of `a.b` not defined in the scope,
+/// and a `import a.**` directive is used likewise for `a` and its subpackages.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, r#"
+///     package com.acme {
+///         import flash.display.Sprite;
+///         import flash.events.Event;
+///         public class Main extends Sprite {}
+///     }
+/// "#.into());
+/// let program = ParserFacade(&compilation_unit, default()).parse_program();
+///
+/// let mut index = DefinitionIndex::new();
+/// index.add_definition("flash.display", "Sprite");
+/// index.add_definition("flash.events", "Event");
+/// ImportAnalysis(&index).check_program(&program);
+///
+/// let diagnostics = compilation_unit.diagnostics();
+/// assert_eq!(diagnostics.len(), 1);
+/// assert!(diagnostics[0].kind() == DiagnosticKind::UnusedImport);
+///
+/// let organized = ImportAnalysis(&index).organize_imports(&program.packages[0].block.directives).unwrap();
+/// assert_eq!(organized.text, "import flash.display.Sprite;");
+/// ```
+pub struct ImportAnalysis<'a>(pub &'a DefinitionIndex);
+
+/// State of an import directive within its scope.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ImportState {
+    Used,
+    Unresolved,
+    Duplicate,
+    Shadowed,
+    Unused,
+}
+
+impl<'a> ImportAnalysis<'a> {
+    /// Checks the imports of every scope of a program.
+    pub fn check_program(&self, program: &Program) {
+        for package in program.packages.iter() {
+            self.check_scope(&package.block.directives);
+        }
+        self.check_scope(&program.directives);
+    }
+
+    /// Checks the imports appearing directly among the directives of a scope.
+    pub fn check_scope(&self, directives: &[Rc<Directive>]) {
+        for (import, state) in self.import_states(directives) {
+            let compilation_unit = import.location.compilation_unit();
+            let name = import_name(&import);
+            match state {
+                ImportState::Used => {},
+                ImportState::Unresolved => {
+                    let package_name = package_name(&import);
+                    let package_location = import.package_name[0].1.combine_with(import.package_name.last().unwrap().1.clone());
+                    match &import.import_specifier {
+                        ImportSpecifier::Identifier((name, location)) if self.0.has_package(&package_name) => {
+                            compilation_unit.add_diagnostic(Diagnostic::new_verify_error(location, DiagnosticKind::UnknownImportDefinition, diagarg![name.clone(), package_name]));
+                        },
+                        _ => {
+                            compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&package_location, DiagnosticKind::UnknownImportPackage, diagarg![package_name]));
+                        },
+                    }
+                },
+                ImportState::Duplicate => {
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&import.location, DiagnosticKind::DuplicateImport, diagarg![name]));
+                },
+                ImportState::Shadowed => {
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&import.location, DiagnosticKind::ShadowedImport, diagarg![name]));
+                },
+                ImportState::Unused => {
+                    compilation_unit.add_diagnostic(Diagnostic::new_warning(&import.location, DiagnosticKind::UnusedImport, diagarg![name]));
+                },
+            }
+        }
+    }
+
+    /// Rewrites the import directives of a scope sorted and grouped by
+    /// their first package name segment, with groups separated by an empty line.
+    /// Duplicate, shadowed and unused imports are removed; unresolved imports are kept.
+    ///
+    /// Returns `None` if the scope has no imports or if other directives appear
+    /// between its import directives.
+    pub fn organize_imports(&self, directives: &[Rc<Directive>]) -> Option<OrganizedImports> {
+        let first = directives.iter().position(|d| matches!(d.as_ref(), Directive::ImportDirective(_)))?;
+        let last = directives.iter().rposition(|d| matches!(d.as_ref(), Directive::ImportDirective(_)))?;
+        if directives[first..=last].iter().any(|d| !matches!(d.as_ref(), Directive::ImportDirective(_))) {
+            return None;
+        }
+        let location = directives[first].location().combine_with(directives[last].location());
+
+        let mut lines: Vec<(String, String)> = self.import_states(directives).into_iter()
+            .filter(|(_, state)| matches!(state, ImportState::Used | ImportState::Unresolved))
+            .map(|(import, _)| {
+                let group = import.package_name[0].0.clone();
+                let line = match &import.alias {
+                    Some((alias, _)) => format!("import {alias} = {};", import_name(&import)),
+                    None => format!("import {};", import_name(&import)),
+                };
+                (group, line)
+            })
+            .collect();
+        lines.sort_by(|(group_1, line_1), (group_2, line_2)| group_1.cmp(group_2).then_with(|| line_1.cmp(line_2)));
+
+        let compilation_unit = location.compilation_unit();
+        let text = &compilation_unit.text()[..location.first_offset()];
+        let indent: String = text[text.rfind('\n').map_or(0, |i| i + 1)..].chars().take_while(|ch| ch.is_whitespace()).collect();
+
+        let mut output = String::new();
+        let mut last_group: Option<&str> = None;
+        for (group, line) in lines.iter() {
+            if let Some(last_group) = last_group {
+                output.push('\n');
+                if last_group != group {
+                    output.push('\n');
+                }
+                output.push_str(&indent);
+            }
+            output.push_str(line);
+            last_group = Some(group);
+        }
+        Some(OrganizedImports { location, text: output })
+    }
+
+    /// Returns the import directives appearing directly in a scope along with their state.
+    fn import_states(&self, directives: &[Rc<Directive>]) -> Vec<(ImportDirective, ImportState)> {
+        let mut references = ImportReferenceCollector::default();
+        TreeWalker::walk_directives(&mut references, directives);
+        let local_names: HashSet<String> = DefinitionIndex::definition_names(directives).into_iter().collect();
+
+        let mut seen = HashSet::new();
+        let mut result = vec![];
+        for directive in directives.iter() {
+            let Directive::ImportDirective(import) = directive.as_ref() else {
+                continue;
+            };
+            let package_name = package_name(import);
+            let name = import_name(import);
+            let state = if !seen.insert((import.alias.as_ref().map(|(alias, _)| alias.clone()), name.clone())) {
+                ImportState::Duplicate
+            } else if !self.resolves(import) {
+                ImportState::Unresolved
+            } else if let Some((alias, _)) = &import.alias {
+                if local_names.contains(alias) {
+                    ImportState::Shadowed
+                } else if references.names.contains(alias) {
+                    ImportState::Used
+                } else {
+                    ImportState::Unused
+                }
+            } else {
+                match &import.import_specifier {
+                    ImportSpecifier::Identifier((simple_name, _)) => {
+                        if local_names.contains(simple_name) {
+                            ImportState::Shadowed
+                        } else if references.names.contains(simple_name) || references.qualified_names.contains(&name) {
+                            ImportState::Used
+                        } else {
+                            ImportState::Unused
+                        }
+                    },
+                    ImportSpecifier::Wildcard(_) => {
+                        let used = references.names.iter().any(|n| !local_names.contains(n) && self.0.has_definition(&package_name, n))
+                            || references.qualified_names.iter().any(|n| n.rsplit_once('.').is_some_and(|(p, _)| p == package_name));
+                        if used { ImportState::Used } else { ImportState::Unused }
+                    },
+                    ImportSpecifier::Recursive(_) => {
+                        let prefix = format!("{package_name}.");
+                        let used = references.names.iter().any(|n| !local_names.contains(n) && self.0.has_definition_recursive(&package_name, n))
+                            || references.qualified_names.iter().any(|n| n.starts_with(&prefix));
+                        if used { ImportState::Used } else { ImportState::Unused }
+                    },
+                }
+            };
+            result.push((import.clone(), state));
+        }
+        result
+    }
+
+    fn resolves(&self, import: &ImportDirective) -> bool {
+        let package_name = package_name(import);
+        match &import.import_specifier {
+            ImportSpecifier::Identifier((name, _)) => self.0.has_definition(&package_name, name),
+            ImportSpecifier::Wildcard(_) => self.0.has_package(&package_name),
+            ImportSpecifier::Recursive(_) => self.0.has_package_recursive(&package_name),
+        }
+    }
+}
+
+/// Collects the names referenced by expressions.
+#[derive(Default)]
+struct ImportReferenceCollector {
+    /// Unqualified identifiers, such as `Sprite`.
+    names: HashSet<String>,
+    /// Dotted names, such as `flash.display.Sprite`.
+    qualified_names: HashSet<String>,
+}
+
+impl TreeVisitor for ImportReferenceCollector {
+    fn visit_expression(&mut self, expression: &Rc<Expression>) -> bool {
+        match expression.as_ref() {
+            Expression::QualifiedIdentifier(id) => {
+                if let Some((name, _)) = id.to_identifier_name() {
+                    self.names.insert(name);
+                }
+            },
+            Expression::Member(_) => {
+                if let Some(name) = dotted_name(expression) {
+                    self.qualified_names.insert(name);
+                }
+            },
+            _ => {},
+        }
+        true
+    }
+}
+
+fn dotted_name(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::QualifiedIdentifier(id) => id.to_identifier_name().map(|(name, _)| name),
+        Expression::Member(member) => {
+            let (name, _) = member.identifier.to_identifier_name()?;
+            Some(format!("{}.{name}", dotted_name(&member.base)?))
+        },
+        _ => None,
+    }
+}
+
+fn package_name(import: &ImportDirective) -> String {
+    import.package_name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".")
+}
+
+/// Returns the imported name, such as `a.b.C`, `a.b.*` or `a.**`.
+fn import_name(import: &ImportDirective) -> String {
+    let specifier = match &import.import_specifier {
+        ImportSpecifier::Identifier((name, _)) => name.as_str(),
+        ImportSpecifier::Wildcard(_) => "*",
+        ImportSpecifier::Recursive(_) => "**",
+    };
+    format!("{}.{specifier}", package_name(import))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_imports() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+package com.acme {
+    import mx.core.UIComponent;
+    import flash.events.*;
+    import flash.display.Sprite;
+    import com.acme.utils.trim;
+    import flash.display.Sprite;
+    import com.unknown.Thing;
+    import flash.display.Nope;
+    import geom = flash.geom.Point;
+    import flash.**;
+    import com.acme.views.Toolbar;
+    import spark.components.Button;
+
+    public class Toolbar extends flash.display.Sprite {
+        function f(event: Event): void {
+            trim(new geom(0, 0).toString());
+        }
+    }
+}"#.into());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        assert!(!compilation_unit.invalidated());
+
+        let mut index = DefinitionIndex::new();
+        index.add_definition("mx.core", "UIComponent");
+        index.add_definition("flash.events", "Event");
+        index.add_definition("flash.display", "Sprite");
+        index.add_definition("flash.geom", "Point");
+        index.add_definition("com.acme.utils", "trim");
+        index.add_definition("com.acme.views", "Toolbar");
+        index.add_definition("spark.components", "Button");
+        index.add_program(&program);
+        ImportAnalysis(&index).check_program(&program);
+
+        let summary: Vec<String> = compilation_unit.diagnostics().iter().map(|d| format!("{} {}", d.id(), d.location().text())).collect();
+        let id = |kind: DiagnosticKind| kind.id();
+        assert_eq!(summary, [
+            format!("{} import mx.core.UIComponent;", id(DiagnosticKind::UnusedImport)),
+            format!("{} import flash.display.Sprite;", id(DiagnosticKind::DuplicateImport)),
+            format!("{} com.unknown", id(DiagnosticKind::UnknownImportPackage)),
+            format!("{} Nope", id(DiagnosticKind::UnknownImportDefinition)),
+            format!("{} import com.acme.views.Toolbar;", id(DiagnosticKind::ShadowedImport)),
+            format!("{} import spark.components.Button;", id(DiagnosticKind::UnusedImport)),
+        ]);
+
+        let organized = ImportAnalysis(&index).organize_imports(&program.packages[0].block.directives).unwrap();
+        assert_eq!(organized.location.first_line_number(), 3);
+        assert_eq!(organized.text, [
+            "import com.acme.utils.trim;",
+            "    import com.unknown.Thing;",
+            "",
+            "    import flash.**;",
+            "    import flash.display.Nope;",
+            "    import flash.display.Sprite;",
+            "    import flash.events.*;",
+            "    import geom = flash.geom.Point;",
+        ].join("\n"));
+    }
+}
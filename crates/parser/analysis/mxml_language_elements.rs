@@ -0,0 +1,473 @@
+use crate::ns::*;
+
+/// The MXML language elements of a MXML document, such as
+/// `<fx:Declarations>` and `<fx:Component>`.
+///
+/// The following elements are recognized in either the MXML 2009
+/// or MXML 2006 namespace:
+///
+/// - `<fx:Declarations>`, containing non visual components.
+/// - `<fx:Component>`, defining an inline component with its own
+///   class scope, optionally named by a `className` attribute.
+/// - `<fx:Model>`, containing a data model.
+/// - `<fx:XML>` and `<fx:XMLList>`, whose content is parsed as E4X.
+/// - `<fx:Binding>`, whose `source` and `destination` attributes are
+///   parsed as expressions.
+/// - `<fx:Library>`, containing `<fx:Definition>` elements.
+/// - `<fx:Metadata>`, whose content is parsed as meta-data.
+/// - `<fx:Reparent>`, moving a component to a different parent
+///   in certain states.
+///
+/// `<fx:Declarations>`, `<fx:Library>`, `<fx:Metadata>` and `<fx:Binding>`
+/// must be children of the document root or of the root of an inline
+/// component. Misplaced and malformed elements are reported as syntax errors.
+pub struct MxmlLanguageElements {
+    pub elements: Vec<Rc<MxmlLanguageElement>>,
+    semantics: TreeSemantics<Rc<MxmlLanguageElement>>,
+}
+
+pub enum MxmlLanguageElement {
+    Declarations(MxmlDeclarationsElement),
+    Component(MxmlComponentElement),
+    Model(MxmlModelElement),
+    Xml(MxmlXmlElement),
+    Binding(MxmlBindingElement),
+    Library(MxmlLibraryElement),
+    Metadata(MxmlMetadataElement),
+    Reparent(MxmlReparentElement),
+}
+
+/// A `<fx:Declarations>` element.
+pub struct MxmlDeclarationsElement {
+    pub element: Rc<MxmlElement>,
+    pub declarations: Vec<Rc<MxmlElement>>,
+}
+
+/// A `<fx:Component>` element.
+pub struct MxmlComponentElement {
+    pub element: Rc<MxmlElement>,
+    /// The name of the class defined by the component, given
+    /// by the `className` attribute.
+    pub class_name: Option<(String, Location)>,
+    /// The root element of the component.
+    pub root: Option<Rc<MxmlElement>>,
+}
+
+/// A `<fx:Model>` element.
+pub struct MxmlModelElement {
+    pub element: Rc<MxmlElement>,
+    /// The file containing the model, given by the `source` attribute.
+    pub source: Option<(String, Location)>,
+    /// The root element of the model, if given inline.
+    pub root: Option<Rc<MxmlElement>>,
+}
+
+/// A `<fx:XML>` or `<fx:XMLList>` element.
+pub struct MxmlXmlElement {
+    pub element: Rc<MxmlElement>,
+    /// Indicates whether the element is a `<fx:XMLList>` element.
+    pub list: bool,
+    /// The file containing the XML, given by the `source` attribute.
+    pub source: Option<(String, Location)>,
+    /// The inline XML elements, parsed as E4X expressions.
+    pub content: Vec<Rc<Expression>>,
+}
+
+/// A `<fx:Binding>` element.
+pub struct MxmlBindingElement {
+    pub element: Rc<MxmlElement>,
+    pub source: Option<Rc<Expression>>,
+    pub destination: Option<Rc<Expression>>,
+    /// Indicates whether the binding is two-way, as given
+    /// by the `twoWay` attribute.
+    pub two_way: bool,
+}
+
+/// A `<fx:Library>` element.
+pub struct MxmlLibraryElement {
+    pub element: Rc<MxmlElement>,
+    pub definitions: Vec<Rc<MxmlDefinitionElement>>,
+}
+
+/// A `<fx:Definition>` element.
+pub struct MxmlDefinitionElement {
+    pub element: Rc<MxmlElement>,
+    pub name: Option<(String, Location)>,
+    /// The root element of the definition.
+    pub root: Option<Rc<MxmlElement>>,
+}
+
+/// A `<fx:Metadata>` element.
+pub struct MxmlMetadataElement {
+    pub element: Rc<MxmlElement>,
+    pub metadata: Vec<Rc<Metadata>>,
+}
+
+/// A `<fx:Reparent>` element.
+pub struct MxmlReparentElement {
+    pub element: Rc<MxmlElement>,
+    /// The `id` of the component to reparent, given by the `target` attribute.
+    pub target: Option<(String, Location)>,
+}
+
+impl MxmlLanguageElement {
+    pub fn element(&self) -> Rc<MxmlElement> {
+        match self {
+            Self::Declarations(e) => e.element.clone(),
+            Self::Component(e) => e.element.clone(),
+            Self::Model(e) => e.element.clone(),
+            Self::Xml(e) => e.element.clone(),
+            Self::Binding(e) => e.element.clone(),
+            Self::Library(e) => e.element.clone(),
+            Self::Metadata(e) => e.element.clone(),
+            Self::Reparent(e) => e.element.clone(),
+        }
+    }
+}
+
+impl MxmlLanguageElements {
+    /// Analyzes the language elements of a MXML document, reporting
+    /// misplaced and malformed elements to the compilation unit.
+    pub fn analyze(document: &Mxml) -> Self {
+        let mut analysis = Self {
+            elements: vec![],
+            semantics: TreeSemantics::new(),
+        };
+        let root = document.content.iter().find_map(|content| {
+            if let MxmlContent::Element(element) = content.as_ref() { Some(element.clone()) } else { None }
+        });
+        if let Some(root) = root {
+            analysis.analyze_component_root(&root, true);
+        }
+        analysis
+    }
+
+    /// Returns the language element information of an element.
+    pub fn get(&self, element: &Rc<MxmlElement>) -> Option<Rc<MxmlLanguageElement>> {
+        self.semantics.get(element)
+    }
+
+    fn add(&mut self, element: &Rc<MxmlElement>, language_element: MxmlLanguageElement) {
+        let language_element = Rc::new(language_element);
+        self.semantics.set(element, Some(language_element.clone()));
+        self.elements.push(language_element);
+    }
+
+    fn analyze_component_root(&mut self, root: &Rc<MxmlElement>, document_root: bool) {
+        for (i, child) in child_elements(root).iter().enumerate() {
+            if language_element_name(child).as_deref() == Some("Library") && (!document_root || i != 0) {
+                add_syntax_error(&child.name.location, DiagnosticKind::MxmlLibraryMustBeFirstChild, diagarg![source_name(&child.name)]);
+            }
+            self.analyze_element(child, true);
+        }
+    }
+
+    fn analyze_children(&mut self, element: &Rc<MxmlElement>) {
+        for child in child_elements(element).iter() {
+            self.analyze_element(child, false);
+        }
+    }
+
+    fn analyze_element(&mut self, element: &Rc<MxmlElement>, root_child: bool) {
+        let Some(name) = language_element_name(element) else {
+            self.analyze_children(element);
+            return;
+        };
+        if ["Declarations", "Library", "Metadata", "Binding"].contains(&name.as_str()) && !root_child {
+            add_syntax_error(&element.name.location, DiagnosticKind::MxmlLanguageElementMustBeRootChild, diagarg![source_name(&element.name)]);
+        }
+        match name.as_str() {
+            "Declarations" => {
+                let declarations = child_elements(element);
+                self.add(element, MxmlLanguageElement::Declarations(MxmlDeclarationsElement {
+                    element: element.clone(),
+                    declarations,
+                }));
+                self.analyze_children(element);
+            },
+            "Component" => {
+                let class_name = element.attribute_value("className").map(|(value, location)| (value.trim().to_owned(), location.clone()));
+                if let Some((class_name, location)) = &class_name {
+                    if !is_identifier(class_name) {
+                        add_syntax_error(location, DiagnosticKind::InvalidMxmlClassName, diagarg![class_name.clone()]);
+                    }
+                }
+                let root = single_child_element(element);
+                if let Some(root) = &root {
+                    self.analyze_component_root(root, false);
+                }
+                self.add(element, MxmlLanguageElement::Component(MxmlComponentElement {
+                    element: element.clone(),
+                    class_name,
+                    root,
+                }));
+            },
+            "Model" => {
+                let source = element.attribute_value("source").cloned();
+                let root = if source.is_some() {
+                    verify_no_content(element);
+                    None
+                } else {
+                    let children = child_elements(element);
+                    if children.len() > 1 {
+                        add_syntax_error(&element.name.location, DiagnosticKind::MxmlElementMustHaveSingleChild, diagarg![source_name(&element.name)]);
+                    }
+                    children.first().cloned()
+                };
+                self.add(element, MxmlLanguageElement::Model(MxmlModelElement {
+                    element: element.clone(),
+                    source,
+                    root,
+                }));
+            },
+            "XML" | "XMLList" => {
+                let list = name == "XMLList";
+                let source = element.attribute_value("source").cloned();
+                let mut content = vec![];
+                if source.is_some() {
+                    verify_no_content(element);
+                } else {
+                    let children = child_elements(element);
+                    if !list && children.len() != 1 {
+                        add_syntax_error(&element.name.location, DiagnosticKind::MxmlElementMustHaveSingleChild, diagarg![source_name(&element.name)]);
+                    }
+                    for child in children.iter() {
+                        // Element locations start at the tag name, after the `<` character.
+                        let compilation_unit = child.location.compilation_unit();
+                        let first_offset = child.location.first_offset().saturating_sub(1);
+                        content.push(ParserFacade(&compilation_unit, ParserOptions {
+                            byte_range: Some((first_offset, child.location.last_offset())),
+                            ..default()
+                        }).parse_expression());
+                    }
+                }
+                self.add(element, MxmlLanguageElement::Xml(MxmlXmlElement {
+                    element: element.clone(),
+                    list,
+                    source,
+                    content,
+                }));
+            },
+            "Binding" => {
+                let source = required_attribute(element, "source").map(|value| parse_attribute_expression(&value));
+                let destination = required_attribute(element, "destination").map(|value| parse_attribute_expression(&value));
+                let two_way = element.attribute_value("twoWay").is_some_and(|(value, _)| value.trim() == "true");
+                self.add(element, MxmlLanguageElement::Binding(MxmlBindingElement {
+                    element: element.clone(),
+                    source,
+                    destination,
+                    two_way,
+                }));
+            },
+            "Library" => {
+                let mut definitions = vec![];
+                for child in child_elements(element).iter() {
+                    if language_element_name(child).as_deref() != Some("Definition") {
+                        add_syntax_error(&child.name.location, DiagnosticKind::UnexpectedMxmlElement, diagarg![source_name(&child.name)]);
+                        continue;
+                    }
+                    let name = required_attribute(child, "name").map(|(value, location)| (value.trim().to_owned(), location));
+                    let root = single_child_element(child);
+                    if let Some(root) = &root {
+                        self.analyze_component_root(root, false);
+                    }
+                    definitions.push(Rc::new(MxmlDefinitionElement {
+                        element: child.clone(),
+                        name,
+                        root,
+                    }));
+                }
+                self.add(element, MxmlLanguageElement::Library(MxmlLibraryElement {
+                    element: element.clone(),
+                    definitions,
+                }));
+            },
+            "Definition" => {
+                add_syntax_error(&element.name.location, DiagnosticKind::UnexpectedMxmlElement, diagarg![source_name(&element.name)]);
+            },
+            "Metadata" => {
+                let metadata = parse_metadata_content(element);
+                self.add(element, MxmlLanguageElement::Metadata(MxmlMetadataElement {
+                    element: element.clone(),
+                    metadata,
+                }));
+            },
+            "Reparent" => {
+                let target = required_attribute(element, "target").map(|(value, location)| (value.trim().to_owned(), location));
+                if element.attribute_value("includeIn").is_none() && element.attribute_value("excludeFrom").is_none() {
+                    add_syntax_error(&element.name.location, DiagnosticKind::MxmlReparentMustSpecifyState, diagarg![source_name(&element.name)]);
+                }
+                self.add(element, MxmlLanguageElement::Reparent(MxmlReparentElement {
+                    element: element.clone(),
+                    target,
+                }));
+            },
+            "Script" | "Style" | "Private" => {},
+            _ => {
+                self.analyze_children(element);
+            },
+        }
+    }
+}
+
+/// Returns the local name of a MXML language element.
+fn language_element_name(element: &MxmlElement) -> Option<String> {
+    let (uri, local_name) = element.name.resolve_name(&element.namespace).ok()?;
+    if uri == MxmlNamespace::MXML_2009 || uri == MxmlNamespace::MXML_2006 { Some(local_name) } else { None }
+}
+
+/// Returns a name as written in the source, such as `fx:Binding`.
+fn source_name(name: &MxmlName) -> String {
+    if let Some(prefix) = &name.prefix { format!("{prefix}:{}", name.name) } else { name.name.clone() }
+}
+
+fn add_syntax_error(location: &Location, kind: DiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+    location.compilation_unit().add_diagnostic(Diagnostic::new_syntax_error(location, kind, arguments));
+}
+
+fn child_elements(element: &MxmlElement) -> Vec<Rc<MxmlElement>> {
+    element.content.iter().flatten().filter_map(|content| {
+        if let MxmlContent::Element(child) = content.as_ref() { Some(child.clone()) } else { None }
+    }).collect()
+}
+
+/// Returns the single child element of an element, reporting
+/// an error if it has zero or multiple child elements.
+fn single_child_element(element: &MxmlElement) -> Option<Rc<MxmlElement>> {
+    let children = child_elements(element);
+    if children.len() != 1 {
+        add_syntax_error(&element.name.location, DiagnosticKind::MxmlElementMustHaveSingleChild, diagarg![source_name(&element.name)]);
+    }
+    children.first().cloned()
+}
+
+/// Returns the value of a required attribute, reporting
+/// an error if it is missing.
+fn required_attribute(element: &MxmlElement, name: &str) -> Option<(String, Location)> {
+    let value = element.attribute_value(name).cloned();
+    if value.is_none() {
+        add_syntax_error(&element.name.location, DiagnosticKind::MissingMxmlAttribute, diagarg![source_name(&element.name), name.to_owned()]);
+    }
+    value
+}
+
+/// Reports an error if an element specifying a `source` attribute has content.
+fn verify_no_content(element: &MxmlElement) {
+    let has_content = element.content.iter().flatten().any(|content| match content.as_ref() {
+        MxmlContent::Characters((text, _)) => !text.trim().is_empty(),
+        MxmlContent::Comment(_) | MxmlContent::ProcessingInstruction { .. } => false,
+        _ => true,
+    });
+    if has_content {
+        add_syntax_error(&element.name.location, DiagnosticKind::MxmlSourceAndContentAreMutuallyExclusive, diagarg![source_name(&element.name)]);
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(CharacterValidator::is_identifier_start) && chars.all(CharacterValidator::is_identifier_part)
+}
+
+/// Parses an attribute value as an expression. Values containing
+/// entities are parsed from a separate compilation unit consisting
+/// of the unescaped value.
+fn parse_attribute_expression((value, location): &(String, Location)) -> Rc<Expression> {
+    let compilation_unit = location.compilation_unit();
+    let (first_offset, last_offset) = (location.first_offset() + 1, location.last_offset() - 1);
+    if compilation_unit.text()[first_offset..last_offset] == *value {
+        return ParserFacade(&compilation_unit, ParserOptions {
+            byte_range: Some((first_offset, last_offset)),
+            ..default()
+        }).parse_expression();
+    }
+    let embedded = EmbeddedCompilationUnit::new(&compilation_unit, &[(value.clone(), first_offset)]);
+    let exp = ParserFacade(&embedded.compilation_unit(), default()).parse_expression();
+    embedded.transfer_diagnostics();
+    exp
+}
+
+/// Parses the content of a `<fx:Metadata>` element as meta-data.
+fn parse_metadata_content(element: &MxmlElement) -> Vec<Rc<Metadata>> {
+    let compilation_unit = element.location.compilation_unit();
+    let fragments = mxml_text_fragments(element);
+    if fragments.is_empty() {
+        return vec![];
+    }
+    if let [(text, offset)] = fragments.as_slice() {
+        if compilation_unit.text()[*offset..].starts_with(text.as_str()) {
+            return ParserFacade(&compilation_unit, ParserOptions {
+                byte_range: Some((*offset, offset + text.len())),
+                ..default()
+            }).parse_metadata_list();
+        }
+    }
+    let embedded = EmbeddedCompilationUnit::new(&compilation_unit, &fragments);
+    let metadata = ParserFacade(&embedded.compilation_unit(), default()).parse_metadata_list();
+    embedded.transfer_diagnostics();
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_language_elements() {
+        let compilation_unit = CompilationUnit::new(None, r#"<?xml version="1.0"?>
+<s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark">
+    <fx:Metadata>
+        [Event(name="saved", type="flash.events.Event")]
+        [Style(name="accent", type="uint", format="Color")]
+    </fx:Metadata>
+    <fx:Declarations>
+        <fx:Model id="user"><user><name>Ann</name></user></fx:Model>
+        <fx:XML id="menu"><menu><item label="Open"/></menu></fx:XML>
+        <fx:XMLList id="items"><item/><item/></fx:XMLList>
+        <fx:Component className="ItemRenderer">
+            <s:ItemRenderer>
+                <fx:Declarations/>
+            </s:ItemRenderer>
+        </fx:Component>
+        <fx:Component className="1Invalid"/>
+    </fx:Declarations>
+    <fx:Binding source="a.text" destination="b.text" twoWay="true"/>
+    <fx:Binding source="a.text"/>
+    <s:Group>
+        <fx:Declarations/>
+    </s:Group>
+    <fx:Library/>
+    <fx:Reparent target="button"/>
+</s:Application>"#.into());
+        let document = ParserFacade(&compilation_unit, default()).parse_mxml();
+        let analysis = MxmlLanguageElements::analyze(&document);
+        assert_eq!(analysis.elements.len(), 13);
+
+        let MxmlLanguageElement::Metadata(metadata) = analysis.elements[0].as_ref() else { panic!() };
+        let names: Vec<String> = metadata.metadata.iter().map(|m| m.name.0.clone()).collect();
+        assert_eq!(names, ["Event", "Style"]);
+
+        let MxmlLanguageElement::Xml(xml) = analysis.elements[3].as_ref() else { panic!() };
+        assert!(!xml.list);
+        assert!(matches!(xml.content[0].as_ref(), Expression::Xml(_)));
+        let MxmlLanguageElement::Xml(xml_list) = analysis.elements[4].as_ref() else { panic!() };
+        assert_eq!(xml_list.content.len(), 2);
+
+        let MxmlLanguageElement::Component(component) = analysis.elements[6].as_ref() else { panic!() };
+        assert_eq!(component.class_name.as_ref().unwrap().0, "ItemRenderer");
+        assert!(component.root.is_some());
+
+        let MxmlLanguageElement::Binding(binding) = analysis.elements[8].as_ref() else { panic!() };
+        assert!(binding.two_way);
+        assert_eq!(binding.destination.as_ref().unwrap().location().text(), "b.text");
+
+        let diagnostics: Vec<(DiagnosticKind, usize)> = compilation_unit.diagnostics().iter()
+            .map(|d| (d.kind(), d.location().first_line_number())).collect();
+        assert_eq!(diagnostics.len(), 6);
+        assert!(diagnostics[0] == (DiagnosticKind::InvalidMxmlClassName, 16));
+        assert!(diagnostics[1] == (DiagnosticKind::MxmlElementMustHaveSingleChild, 16));
+        assert!(diagnostics[2] == (DiagnosticKind::MissingMxmlAttribute, 19));
+        assert!(diagnostics[3] == (DiagnosticKind::MxmlLanguageElementMustBeRootChild, 21));
+        assert!(diagnostics[4] == (DiagnosticKind::MxmlLibraryMustBeFirstChild, 23));
+        assert!(diagnostics[5] == (DiagnosticKind::MxmlReparentMustSpecifyState, 24));
+    }
+}
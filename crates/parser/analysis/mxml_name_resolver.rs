@@ -0,0 +1,213 @@
+use crate::ns::*;
+
+/// Resolves the fully qualified class names of MXML components.
+///
+/// Namespaces are resolved as follows:
+///
+/// - A namespace URI associated with a manifest, such as
+///   `library://ns.adobe.com/flex/spark`, resolves components
+///   through the manifest.
+/// - The `*` namespace resolves components in the package of the MXML
+///   document, as given by `set_document_package()`, which is the
+///   top-level package by default.
+/// - A package wildcard namespace, such as `com.acme.views.*`,
+///   resolves components in the given package.
+/// - The MXML 2009 language namespace resolves built-in types such
+///   as `<fx:String>` and `<fx:Array>`.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let mut manifest = FlexManifest::new();
+/// manifest.add_component("Button", "spark.components.Button");
+///
+/// let mut resolver = MxmlNameResolver::new();
+/// resolver.add_manifest("library://ns.adobe.com/flex/spark", manifest);
+///
+/// assert_eq!(resolver.resolve("library://ns.adobe.com/flex/spark", "Button"), Some("spark.components.Button".into()));
+/// assert_eq!(resolver.resolve("com.acme.views.*", "MainView"), Some("com.acme.views.MainView".into()));
+/// assert_eq!(resolver.resolve("*", "MainView"), Some("MainView".into()));
+///
+/// resolver.set_document_package("com.acme");
+/// assert_eq!(resolver.resolve("*", "MainView"), Some("com.acme.MainView".into()));
+/// ```
+#[derive(Clone, Default)]
+pub struct MxmlNameResolver {
+    manifests: HashMap<String, Vec<FlexManifest>>,
+    /// The package of the MXML document, relative to which
+    /// the `*` namespace resolves components.
+    document_package: String,
+}
+
+/// A component element along with its fully qualified class name.
+pub struct ResolvedMxmlComponent {
+    pub element: Rc<MxmlElement>,
+    pub class_name: String,
+}
+
+impl MxmlNameResolver {
+    /// Types resolved through the MXML 2009 language namespace.
+    pub const LANGUAGE_TYPES: [&'static str; 13] = [
+        "Array", "Boolean", "Class", "Date", "Function", "int", "Number",
+        "Object", "RegExp", "String", "uint", "Vector", "XML",
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Associates a manifest with a namespace URI. Multiple manifests
+    /// may be associated with the same namespace URI, in which case
+    /// they are searched in the order they were added.
+    pub fn add_manifest(&mut self, uri: &str, manifest: FlexManifest) {
+        self.manifests.entry(uri.to_owned()).or_default().push(manifest);
+    }
+
+    /// Sets the package of the MXML document being resolved, which
+    /// corresponds to its directory relative to the source path,
+    /// such as `"com.acme.views"`.
+    pub fn set_document_package(&mut self, package_name: &str) {
+        self.document_package = package_name.to_owned();
+    }
+
+    /// Resolves the fully qualified class name of a component
+    /// given its namespace URI and local name.
+    pub fn resolve(&self, uri: &str, local_name: &str) -> Option<String> {
+        if let Some(manifests) = self.manifests.get(uri) {
+            return manifests.iter().find_map(|manifest| manifest.get(local_name));
+        }
+        if uri == "*" {
+            if self.document_package.is_empty() {
+                return Some(local_name.to_owned());
+            }
+            return Some(format!("{}.{local_name}", self.document_package));
+        }
+        if let Some(package_name) = uri.strip_suffix(".*") {
+            if is_package_name(package_name) {
+                return Some(format!("{package_name}.{local_name}"));
+            }
+        }
+        if uri == MxmlNamespace::MXML_2009 && Self::LANGUAGE_TYPES.contains(&local_name) {
+            return Some(local_name.to_owned());
+        }
+        None
+    }
+
+    /// Resolves the fully qualified class name of a component element.
+    pub fn resolve_element(&self, element: &MxmlElement) -> Option<String> {
+        let (uri, local_name) = element.name.resolve_name(&element.namespace).ok()?;
+        self.resolve(&uri, &local_name)
+    }
+
+    /// Resolves every component element of a MXML document, reporting
+    /// unknown components as verify errors.
+    ///
+    /// Language elements other than `<fx:Declarations>`, `<fx:Component>`,
+    /// `<fx:Definition>` and built-in types are skipped along with
+    /// their content, and so are property elements, which share the
+    /// namespace of their parent and start with a lowercase letter, such as
+    /// `<s:layout>`, although their children are resolved.
+    pub fn resolve_document(&self, document: &Mxml) -> Vec<ResolvedMxmlComponent> {
+        let mut output = vec![];
+        for content in document.content.iter() {
+            if let MxmlContent::Element(element) = content.as_ref() {
+                self.resolve_component_element(element, &mut output);
+            }
+        }
+        output
+    }
+
+    fn resolve_component_element(&self, element: &Rc<MxmlElement>, output: &mut Vec<ResolvedMxmlComponent>) {
+        if let Some(class_name) = self.resolve_element(element) {
+            output.push(ResolvedMxmlComponent {
+                element: element.clone(),
+                class_name,
+            });
+        } else {
+            let compilation_unit = element.location.compilation_unit();
+            compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&element.name.location, DiagnosticKind::UnknownMxmlComponent, diagarg![element.name.to_string(&element.namespace)]));
+        }
+        self.resolve_children(element, output);
+    }
+
+    fn resolve_children(&self, element: &MxmlElement, output: &mut Vec<ResolvedMxmlComponent>) {
+        let element_uri = element.name.resolve_prefix(&element.namespace).ok();
+        for content in element.content.iter().flatten() {
+            let MxmlContent::Element(child) = content.as_ref() else {
+                continue;
+            };
+            let child_uri = child.name.resolve_prefix(&child.namespace).ok();
+            let is_language_element = matches!(child_uri.as_deref(), Some(MxmlNamespace::MXML_2009 | MxmlNamespace::MXML_2006));
+            if is_language_element && !Self::LANGUAGE_TYPES.contains(&child.name.name.as_str()) {
+                if ["Declarations", "Component", "Definition"].contains(&child.name.name.as_str()) {
+                    self.resolve_children(child, output);
+                }
+                continue;
+            }
+            if child_uri == element_uri && child.name.name.starts_with(|ch: char| ch.is_lowercase()) {
+                self.resolve_children(child, output);
+                continue;
+            }
+            self.resolve_component_element(child, output);
+        }
+    }
+}
+
+fn is_package_name(name: &str) -> bool {
+    name.split('.').all(|name| {
+        let mut chars = name.chars();
+        chars.next().is_some_and(|ch| ch.is_alphabetic() || ch == '_' || ch == '$')
+            && chars.all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_manifest_resolution() {
+        let manifest = CompilationUnit::new(None, r#"<?xml version="1.0"?>
+<componentPackage>
+    <component id="Application" class="spark.components.Application"/>
+    <component class="spark.components.Button"/>
+    <component id="Invalid"/>
+</componentPackage>"#.into());
+        let mut resolver = MxmlNameResolver::new();
+        resolver.add_manifest("library://ns.adobe.com/flex/spark", FlexManifest::parse(&manifest));
+        let diagnostics = manifest.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind() == DiagnosticKind::ManifestComponentMustSpecifyClass);
+
+        let compilation_unit = CompilationUnit::new(None, r#"<?xml version="1.0"?>
+<s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark" xmlns:views="com.acme.views.*" xmlns:local="*">
+    <fx:Declarations>
+        <fx:String id="title">Title</fx:String>
+    </fx:Declarations>
+    <fx:Script>
+        <![CDATA[ var x = 10; ]]>
+    </fx:Script>
+    <s:layout>
+        <s:Unknown/>
+    </s:layout>
+    <views:MainView/>
+    <local:Panel/>
+    <s:Button/>
+</s:Application>"#.into());
+        let document = ParserFacade(&compilation_unit, default()).parse_mxml();
+        let resolved = resolver.resolve_document(&document);
+        let class_names: Vec<String> = resolved.iter().map(|component| component.class_name.clone()).collect();
+        assert_eq!(class_names, ["spark.components.Application", "String", "com.acme.views.MainView", "Panel", "spark.components.Button"]);
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind() == DiagnosticKind::UnknownMxmlComponent);
+        assert_eq!(diagnostics[0].location().first_line_number(), 10);
+
+        // A document in the com.acme.views package
+        resolver.set_document_package("com.acme.views");
+        let class_names: Vec<String> = resolver.resolve_document(&document).iter().map(|component| component.class_name.clone()).collect();
+        assert_eq!(class_names, ["spark.components.Application", "String", "com.acme.views.MainView", "com.acme.views.Panel", "spark.components.Button"]);
+    }
+}
@@ -0,0 +1,332 @@
+use crate::ns::*;
+
+/// The view states of a MXML document.
+///
+/// States are declared by the `<s:states>` element of the root
+/// element, each as a `<s:State>` element with a `name` and
+/// optional `stateGroups` attribute. Elements refer to states
+/// or state groups through:
+///
+/// - The `includeIn` and `excludeFrom` attributes.
+/// - State-specific attributes, such as `label.editing="Save"`.
+/// - State-specific property elements, such as `<s:layout.editing>`.
+///
+/// References to undeclared states are reported as verify errors.
+/// The content of `<fx:Component>` and `<fx:Definition>` elements
+/// is not analyzed, since it belongs to a separate component.
+pub struct MxmlStates {
+    pub states: Vec<Rc<MxmlState>>,
+    elements: TreeSemantics<Rc<MxmlElementStates>>,
+}
+
+/// A state declared by a `<s:State>` element.
+pub struct MxmlState {
+    pub element: Rc<MxmlElement>,
+    pub name: (String, Location),
+    pub state_groups: Vec<(String, Location)>,
+}
+
+/// The state information of an element.
+#[derive(Default)]
+pub struct MxmlElementStates {
+    /// The states or state groups given by the `includeIn` attribute.
+    pub include_in: Option<Vec<(String, Location)>>,
+    /// The states or state groups given by the `excludeFrom` attribute.
+    pub exclude_from: Option<Vec<(String, Location)>>,
+    pub item_creation_policy: Option<(MxmlItemCreationPolicy, Location)>,
+    pub overrides: Vec<MxmlStateOverride>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MxmlItemCreationPolicy {
+    Deferred,
+    Immediate,
+}
+
+/// A state-specific property value.
+pub struct MxmlStateOverride {
+    pub property: (String, Location),
+    /// The state or state group the value applies to.
+    pub state: (String, Location),
+    pub value: MxmlStateOverrideValue,
+}
+
+#[derive(Clone)]
+pub enum MxmlStateOverrideValue {
+    /// A state-specific attribute, such as `label.editing="Save"`.
+    Attribute(Rc<MxmlAttribute>),
+    /// A state-specific property element, such as `<s:layout.editing>`.
+    Element(Rc<MxmlElement>),
+}
+
+impl MxmlStates {
+    /// Analyzes the states of a MXML document, reporting
+    /// invalid state references to the compilation unit.
+    pub fn analyze(document: &Mxml) -> Self {
+        let mut analysis = Self {
+            states: vec![],
+            elements: TreeSemantics::new(),
+        };
+        let root = document.content.iter().find_map(|content| {
+            if let MxmlContent::Element(element) = content.as_ref() { Some(element.clone()) } else { None }
+        });
+        let Some(root) = root else {
+            return analysis;
+        };
+        if let Some(states_element) = states_element(&root) {
+            analysis.collect_states(&states_element);
+        }
+        analysis.analyze_element(&root);
+        analysis
+    }
+
+    fn collect_states(&mut self, states_element: &MxmlElement) {
+        for content in states_element.content.iter().flatten() {
+            let MxmlContent::Element(element) = content.as_ref() else {
+                continue;
+            };
+            let Some(name) = element.attribute_value("name").map(|(value, location)| (value.trim().to_owned(), location.clone())) else {
+                continue;
+            };
+            if self.state(&name.0).is_some() {
+                name.1.compilation_unit().add_diagnostic(Diagnostic::new_verify_error(&name.1, DiagnosticKind::DuplicateMxmlState, diagarg![name.0.clone()]));
+                continue;
+            }
+            let state_groups = element.attributes.iter()
+                .find(|attrib| !attrib.xmlns && attrib.name.prefix.is_none() && attrib.name.name == "stateGroups")
+                .map_or(vec![], |attrib| split_state_names(attrib));
+            self.states.push(Rc::new(MxmlState {
+                element: element.clone(),
+                name,
+                state_groups,
+            }));
+        }
+    }
+
+    fn analyze_element(&mut self, element: &Rc<MxmlElement>) {
+        let mut element_states = MxmlElementStates::default();
+        let compilation_unit = element.location.compilation_unit();
+
+        for attribute in element.attributes.iter() {
+            if attribute.xmlns || attribute.name.prefix.is_some() {
+                continue;
+            }
+            let name = &attribute.name.name;
+            if name == "includeIn" {
+                element_states.include_in = Some(self.verify_state_names(split_state_names(attribute)));
+            } else if name == "excludeFrom" {
+                element_states.exclude_from = Some(self.verify_state_names(split_state_names(attribute)));
+            } else if name == "itemCreationPolicy" {
+                let (value, location) = &attribute.value;
+                let policy = match value.trim() {
+                    "deferred" => MxmlItemCreationPolicy::Deferred,
+                    "immediate" => MxmlItemCreationPolicy::Immediate,
+                    _ => {
+                        compilation_unit.add_diagnostic(Diagnostic::new_verify_error(location, DiagnosticKind::InvalidItemCreationPolicy, diagarg![value.clone()]));
+                        continue;
+                    },
+                };
+                element_states.item_creation_policy = Some((policy, location.clone()));
+            } else if let Some((property, state)) = split_state_specific_name(&attribute.name) {
+                let state = self.verify_state_name(state);
+                element_states.overrides.push(MxmlStateOverride {
+                    property,
+                    state,
+                    value: MxmlStateOverrideValue::Attribute(attribute.clone()),
+                });
+            }
+        }
+
+        if let (Some(_), Some(exclude_from)) = (&element_states.include_in, &element_states.exclude_from) {
+            let location = exclude_from.first().map_or(element.name.location.clone(), |(_, location)| location.clone());
+            compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&location, DiagnosticKind::IncludeInAndExcludeFromAreMutuallyExclusive, vec![]));
+        }
+
+        let element_uri = element.name.resolve_prefix(&element.namespace).ok();
+        let states_element = states_element(element);
+        for content in element.content.iter().flatten() {
+            let MxmlContent::Element(child) = content.as_ref() else {
+                continue;
+            };
+            if child.name.resolve_prefix(&child.namespace).ok() == element_uri {
+                if let Some((property, state)) = split_state_specific_name(&child.name) {
+                    let state = self.verify_state_name(state);
+                    element_states.overrides.push(MxmlStateOverride {
+                        property,
+                        state,
+                        value: MxmlStateOverrideValue::Element(child.clone()),
+                    });
+                }
+            }
+            let skipped = ["Script", "Style", "Metadata", "Model", "XML", "XMLList", "Component", "Definition", "Library", "Private"]
+                .iter().any(|name| child.is_language_element(name));
+            if !skipped && !states_element.as_ref().is_some_and(|states_element| Rc::ptr_eq(states_element, child)) {
+                self.analyze_element(child);
+            }
+        }
+
+        self.elements.set(element, Some(Rc::new(element_states)));
+    }
+
+    /// Returns a declared state.
+    pub fn state(&self, name: &str) -> Option<Rc<MxmlState>> {
+        self.states.iter().find(|state| state.name.0 == name).cloned()
+    }
+
+    /// Returns the names of the declared state groups.
+    pub fn state_groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = vec![];
+        for state in self.states.iter() {
+            for (group, _) in state.state_groups.iter() {
+                if !groups.contains(group) {
+                    groups.push(group.clone());
+                }
+            }
+        }
+        groups
+    }
+
+    /// Indicates whether a name is a declared state or state group.
+    pub fn is_declared(&self, name: &str) -> bool {
+        self.state(name).is_some() || self.states.iter().any(|state| state.state_groups.iter().any(|(group, _)| group == name))
+    }
+
+    /// Indicates whether a name denotes a given state, either
+    /// as the state itself or as a state group containing it.
+    pub fn denotes_state(&self, name: &str, state: &str) -> bool {
+        name == state || self.state(state).is_some_and(|state| state.state_groups.iter().any(|(group, _)| group == name))
+    }
+
+    /// Returns the state information of an element.
+    pub fn element_states(&self, element: &Rc<MxmlElement>) -> Option<Rc<MxmlElementStates>> {
+        self.elements.get(element)
+    }
+
+    /// Indicates whether an element is included in a given state.
+    pub fn is_included_in(&self, element: &Rc<MxmlElement>, state: &str) -> bool {
+        let Some(element_states) = self.element_states(element) else {
+            return true;
+        };
+        if let Some(include_in) = &element_states.include_in {
+            return include_in.iter().any(|(name, _)| self.denotes_state(name, state));
+        }
+        if let Some(exclude_from) = &element_states.exclude_from {
+            return !exclude_from.iter().any(|(name, _)| self.denotes_state(name, state));
+        }
+        true
+    }
+
+    /// Returns the property values of an element that apply to a given state.
+    pub fn overrides_for_state(&self, element: &Rc<MxmlElement>, state: &str) -> Vec<(String, MxmlStateOverrideValue)> {
+        let Some(element_states) = self.element_states(element) else {
+            return vec![];
+        };
+        element_states.overrides.iter()
+            .filter(|o| self.denotes_state(&o.state.0, state))
+            .map(|o| (o.property.0.clone(), o.value.clone()))
+            .collect()
+    }
+
+    fn verify_state_names(&self, names: Vec<(String, Location)>) -> Vec<(String, Location)> {
+        names.into_iter().map(|name| self.verify_state_name(name)).collect()
+    }
+
+    fn verify_state_name(&self, name: (String, Location)) -> (String, Location) {
+        if !self.is_declared(&name.0) {
+            name.1.compilation_unit().add_diagnostic(Diagnostic::new_verify_error(&name.1, DiagnosticKind::UndeclaredMxmlState, diagarg![name.0.clone()]));
+        }
+        name
+    }
+}
+
+/// Returns the `<s:states>` child of an element.
+fn states_element(element: &MxmlElement) -> Option<Rc<MxmlElement>> {
+    let element_uri = element.name.resolve_prefix(&element.namespace).ok();
+    element.content.iter().flatten().find_map(|content| match content.as_ref() {
+        MxmlContent::Element(child) if child.name.name == "states" && child.name.resolve_prefix(&child.namespace).ok() == element_uri => Some(child.clone()),
+        _ => None,
+    })
+}
+
+/// Splits a comma-separated list of state names from an attribute value,
+/// locating each name.
+fn split_state_names(attribute: &MxmlAttribute) -> Vec<(String, Location)> {
+    let location = &attribute.value.1;
+    let compilation_unit = location.compilation_unit();
+    let first_offset = location.first_offset() + 1;
+    let text = &compilation_unit.text()[first_offset..(location.last_offset() - 1)];
+    let mut names = vec![];
+    let mut offset = first_offset;
+    for name in text.split(',') {
+        let leading = name.len() - name.trim_start().len();
+        let trimmed = name.trim();
+        if !trimmed.is_empty() {
+            let name_offset = offset + leading;
+            names.push((trimmed.to_owned(), Location::with_offsets(&compilation_unit, name_offset, name_offset + trimmed.len())));
+        }
+        offset += name.len() + 1;
+    }
+    names
+}
+
+/// Splits a state-specific name, such as `label.editing`, into
+/// the property and state names.
+fn split_state_specific_name(name: &MxmlName) -> Option<((String, Location), (String, Location))> {
+    let dot = name.name.find('.')?;
+    let compilation_unit = name.location.compilation_unit();
+    let first_offset = name.location.first_offset() + name.prefix.as_ref().map_or(0, |prefix| prefix.len() + 1);
+    let property = (name.name[..dot].to_owned(), Location::with_offsets(&compilation_unit, first_offset, first_offset + dot));
+    let state = (name.name[(dot + 1)..].to_owned(), Location::with_offsets(&compilation_unit, first_offset + dot + 1, first_offset + name.name.len()));
+    Some((property, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_states() {
+        let compilation_unit = CompilationUnit::new(None, r#"<?xml version="1.0"?>
+<s:Skin xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark">
+    <s:states>
+        <s:State name="normal"/>
+        <s:State name="editing" stateGroups="active"/>
+        <s:State name="disabled"/>
+    </s:states>
+    <s:Button label="Edit" label.editing="Save" includeIn="normal, active"/>
+    <s:Label excludeFrom="disabled" itemCreationPolicy="immediate">
+        <s:text.normal>Normal</s:text.normal>
+    </s:Label>
+    <s:Label includeIn="edting" alpha.disabld="0.5"/>
+</s:Skin>"#.into());
+        let document = ParserFacade(&compilation_unit, default()).parse_mxml();
+        let states = MxmlStates::analyze(&document);
+        let names: Vec<String> = states.states.iter().map(|state| state.name.0.clone()).collect();
+        assert_eq!(names, ["normal", "editing", "disabled"]);
+        assert_eq!(states.state_groups(), ["active"]);
+
+        let MxmlContent::Element(root) = document.content.iter().find(|c| matches!(c.as_ref(), MxmlContent::Element(_))).unwrap().as_ref() else {
+            panic!();
+        };
+        let children: Vec<Rc<MxmlElement>> = root.content.iter().flatten().filter_map(|c| if let MxmlContent::Element(e) = c.as_ref() { Some(e.clone()) } else { None }).collect();
+        let button = &children[1];
+        assert!(states.is_included_in(button, "editing"));
+        assert!(!states.is_included_in(button, "disabled"));
+        let overrides = states.overrides_for_state(button, "editing");
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].0, "label");
+
+        let label = &children[2];
+        assert!(!states.is_included_in(label, "disabled"));
+        let label_states = states.element_states(label).unwrap();
+        assert_eq!(label_states.item_creation_policy.as_ref().unwrap().0, MxmlItemCreationPolicy::Immediate);
+        assert_eq!(states.overrides_for_state(label, "normal").len(), 1);
+
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|d| d.kind() == DiagnosticKind::UndeclaredMxmlState));
+        assert_eq!(diagnostics[0].location().first_line_number(), 12);
+        assert_eq!(diagnostics[0].location().first_column(), 24);
+        assert_eq!(diagnostics[1].location().first_column(), 38);
+    }
+}
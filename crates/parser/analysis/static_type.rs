@@ -0,0 +1,145 @@
+use crate::ns::*;
+use std::fmt;
+
+/// The static type of an ActionScript 3 expression,
+/// as inferred by the [`TypeChecker`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum StaticType {
+    /// The `*` type, also the type of unannotated
+    /// variables and of expressions that could not be inferred.
+    Any,
+    Void,
+    /// The type of the `null` literal.
+    Null,
+    /// A class or interface, given its fully qualified name,
+    /// such as `Number` or `com.acme.Shape`.
+    Class(String),
+    /// `T?`
+    Nullable(Box<StaticType>),
+    /// `[T]`
+    Array(Box<StaticType>),
+    /// `[T1, T2]`
+    Tuple(Vec<StaticType>),
+    /// `function(T1, T2=, ...): R`
+    Function {
+        parameters: Vec<(ParameterKind, StaticType)>,
+        result: Box<StaticType>,
+    },
+    /// A type with type arguments, such as `Vector.<T>`.
+    Generic(String, Vec<StaticType>),
+}
+
+impl StaticType {
+    /// Top-level types known to the type checker.
+    pub const BUILTIN_TYPES: [&'static str; 16] = [
+        "Array", "Boolean", "Class", "Date", "Error", "Function", "int", "Namespace",
+        "Number", "Object", "QName", "RegExp", "String", "uint", "XML", "XMLList",
+    ];
+
+    pub fn class(name: &str) -> Self {
+        Self::Class(name.to_owned())
+    }
+
+    pub fn is_any(&self) -> bool {
+        matches!(self, Self::Any)
+    }
+
+    pub fn is_void(&self) -> bool {
+        matches!(self, Self::Void)
+    }
+
+    /// Indicates whether the type is `Number`, `int` or `uint`.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Self::Class(name) if is_numeric_name(name))
+    }
+
+    /// Indicates whether the type is `Boolean`, `Number`, `int` or `uint`,
+    /// to which `null` converts as `false` or zero.
+    pub fn is_primitive(&self) -> bool {
+        matches!(self, Self::Class(name) if name == "Boolean" || is_numeric_name(name))
+    }
+
+    /// Returns the type without a nullable wrapper.
+    pub fn non_nullable(&self) -> &Self {
+        if let Self::Nullable(base) = self { base.as_ref() } else { self }
+    }
+
+    /// Indicates whether a value of this type may be assigned to
+    /// a variable of another type. Types not known to the class hierarchy
+    /// are assumed to be compatible with other class types.
+    pub fn is_assignable_to(&self, target: &Self, hierarchy: &ClassHierarchy) -> bool {
+        match (self, target) {
+            (Self::Any, _) | (_, Self::Any) => true,
+            (Self::Void, _) | (_, Self::Void) => false,
+            (Self::Null, _) => true,
+            (Self::Nullable(base), target) => base.is_assignable_to(target, hierarchy),
+            (source, Self::Nullable(base)) => source.is_assignable_to(base, hierarchy),
+            (_, Self::Class(name)) if name == "Object" => true,
+            (Self::Class(source), Self::Class(target)) => {
+                if source == target || (is_numeric_name(source) && is_numeric_name(target)) {
+                    return true;
+                }
+                let source_known = hierarchy.has_type(source) || Self::BUILTIN_TYPES.contains(&source.as_str());
+                let target_known = hierarchy.has_type(target) || Self::BUILTIN_TYPES.contains(&target.as_str());
+                if !(source_known && target_known) {
+                    return true;
+                }
+                // A supertype outside the hierarchy is either a builtin type,
+                // which only has `Object` as supertype, or an unknown type.
+                hierarchy.is_subtype(source, target) || hierarchy.unresolved_supertypes(source).iter().any(|name| {
+                    name == target || !Self::BUILTIN_TYPES.contains(&name.as_str())
+                })
+            },
+            (Self::Array(source), Self::Array(target)) => source.is_assignable_to(target, hierarchy),
+            (Self::Tuple(source), Self::Tuple(target)) => {
+                source.len() == target.len() && source.iter().zip(target.iter()).all(|(s, t)| s.is_assignable_to(t, hierarchy))
+            },
+            (Self::Array(_) | Self::Tuple(_), Self::Class(name)) => name == "Array",
+            (Self::Class(name), Self::Array(_) | Self::Tuple(_)) => name == "Array",
+            (Self::Tuple(source), Self::Array(target)) => source.iter().all(|s| s.is_assignable_to(target, hierarchy)),
+            (Self::Function { .. }, Self::Class(name)) => name == "Function",
+            (Self::Class(name), Self::Function { .. }) => name == "Function",
+            (Self::Function { parameters: source_parameters, result: source_result },
+             Self::Function { parameters: target_parameters, result: target_result }) => {
+                let required = |parameters: &[(ParameterKind, StaticType)]| parameters.iter().filter(|(kind, _)| *kind == ParameterKind::Required).count();
+                required(source_parameters) <= target_parameters.len().max(required(target_parameters))
+                    && (target_result.is_void() || source_result.is_assignable_to(target_result, hierarchy))
+            },
+            (Self::Generic(source, source_arguments), Self::Generic(target, target_arguments)) => {
+                source == target && source_arguments.len() == target_arguments.len()
+                    && source_arguments.iter().zip(target_arguments.iter()).all(|(s, t)| s.is_any() || t.is_any() || s == t)
+            },
+            (Self::Generic(source, _), Self::Class(target)) | (Self::Class(source), Self::Generic(target, _)) => source == target,
+            _ => false,
+        }
+    }
+}
+
+fn is_numeric_name(name: &str) -> bool {
+    ["Number", "int", "uint"].contains(&name)
+}
+
+impl fmt::Display for StaticType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "*"),
+            Self::Void => write!(f, "void"),
+            Self::Null => write!(f, "null"),
+            Self::Class(name) => write!(f, "{name}"),
+            Self::Nullable(base) => write!(f, "{base}?"),
+            Self::Array(element) => write!(f, "[{element}]"),
+            Self::Tuple(elements) => write!(f, "[{}]", elements.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")),
+            Self::Function { parameters, result } => {
+                let parameters: Vec<String> = parameters.iter().map(|(kind, t)| match kind {
+                    ParameterKind::Required => t.to_string(),
+                    ParameterKind::Optional => format!("{t}="),
+                    ParameterKind::Rest => if t.is_any() { "...".into() } else { format!("...{t}") },
+                }).collect();
+                write!(f, "function({}): {result}", parameters.join(", "))
+            },
+            Self::Generic(name, arguments) => {
+                write!(f, "{name}.<{}>", arguments.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", "))
+            },
+        }
+    }
+}
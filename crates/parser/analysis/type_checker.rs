@@ -0,0 +1,1065 @@
+use crate::ns::*;
+
+/// A member of a class or interface, or a package-level definition.
+#[derive(Clone)]
+enum CheckedMember {
+    Method(Rc<FunctionCommon>),
+    Accessor {
+        getter: Option<Rc<FunctionCommon>>,
+        setter: Option<Rc<FunctionCommon>>,
+    },
+    Variable(Option<Rc<Expression>>),
+}
+
+/// The context type expressions are resolved in.
+#[derive(Clone)]
+struct ResolutionContext {
+    package_name: String,
+    imports: Rc<Vec<ImportDirective>>,
+}
+
+struct CheckedClass {
+    context: ResolutionContext,
+    is_interface: bool,
+    is_dynamic: bool,
+    has_extends_clause: bool,
+    constructor: Option<Rc<FunctionCommon>>,
+    instance_members: HashMap<String, CheckedMember>,
+    static_members: HashMap<String, CheckedMember>,
+}
+
+/// State of the check of a function or class body.
+struct TypeCheckerScope {
+    context: ResolutionContext,
+    /// The enclosing class, if any.
+    class: Option<String>,
+    is_static: bool,
+    /// Local variables, innermost function last.
+    locals: Vec<HashMap<String, StaticType>>,
+    /// Result types of the enclosing functions, innermost last.
+    result_types: Vec<StaticType>,
+}
+
+/// An optional static type checker for annotated ActionScript 3 code.
+///
+/// Types are inferred from literals, type annotations, member lookups
+/// and calls. Unannotated variables and expressions whose types cannot be
+/// inferred have the `*` type and are not checked. The following are
+/// reported as verify errors:
+///
+/// - Assignments, initializers, arguments and return values of a type
+///   not assignable to the target type.
+/// - Calls with a wrong number of arguments.
+/// - Accesses to undefined instance members of sealed classes, that is,
+///   non-`dynamic` classes whose superclasses are all known and sealed.
+/// - `void` results used as values.
+///
+/// Classes, interfaces and package-level definitions are known to the
+/// type checker once added through `add_program()`, which also adds them
+/// to its `ClassHierarchy`.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, r#"
+///     package com.acme {
+///         public class Point {
+///             public var x: Number = 0;
+///             public function translate(dx: Number): void { x += dx }
+///         }
+///         public function test(p: Point): void {
+///             var s: String = p.x;
+///             p.translate(1, 2);
+///             p.rotate(90);
+///             var r: Number = p.translate(1);
+///         }
+///     }
+/// "#.into());
+/// let program = ParserFacade(&compilation_unit, default()).parse_program();
+///
+/// let mut checker = TypeChecker::new();
+/// checker.add_program(&program);
+/// checker.check_program(&program);
+///
+/// let kinds: Vec<i32> = compilation_unit.diagnostics().iter().map(|d| d.id()).collect();
+/// assert_eq!(kinds, [
+///     DiagnosticKind::IncompatibleTypes.id(),
+///     DiagnosticKind::IncorrectArgumentCount.id(),
+///     DiagnosticKind::UndefinedProperty.id(),
+///     DiagnosticKind::VoidUsedAsValue.id(),
+/// ]);
+/// ```
+#[derive(Default)]
+pub struct TypeChecker {
+    hierarchy: ClassHierarchy,
+    classes: HashMap<String, CheckedClass>,
+    /// Package-level functions and variables by package name.
+    package_definitions: HashMap<String, HashMap<String, CheckedMember>>,
+}
+
+/// Members of `Object` available on every class.
+const OBJECT_MEMBERS: [&str; 8] = [
+    "constructor", "hasOwnProperty", "isPrototypeOf", "propertyIsEnumerable",
+    "setPropertyIsEnumerable", "toLocaleString", "toString", "valueOf",
+];
+
+impl TypeChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn hierarchy(&self) -> &ClassHierarchy {
+        &self.hierarchy
+    }
+
+    /// Adds the definitions of the packages of a program.
+    pub fn add_program(&mut self, program: &Program) {
+        self.hierarchy.add_program(program);
+        for package in program.packages.iter() {
+            let context = ResolutionContext {
+                package_name: package.name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join("."),
+                imports: Rc::new(collect_imports(&package.block.directives)),
+            };
+            for directive in package.block.directives.iter() {
+                self.add_definition(directive, &context);
+            }
+        }
+    }
+
+    fn add_definition(&mut self, directive: &Rc<Directive>, context: &ResolutionContext) {
+        match directive.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                let mut class = CheckedClass {
+                    context: context.clone(),
+                    is_interface: false,
+                    is_dynamic: Attribute::find_dynamic(&defn.attributes).is_some(),
+                    has_extends_clause: defn.extends_clause.is_some(),
+                    constructor: None,
+                    instance_members: HashMap::new(),
+                    static_members: HashMap::new(),
+                };
+                for directive in defn.block.directives.iter() {
+                    add_member(directive, &mut class);
+                }
+                self.classes.insert(qualify(&context.package_name, &defn.name.0), class);
+            },
+            Directive::InterfaceDefinition(defn) => {
+                let mut class = CheckedClass {
+                    context: context.clone(),
+                    is_interface: true,
+                    is_dynamic: false,
+                    has_extends_clause: defn.extends_clause.is_some(),
+                    constructor: None,
+                    instance_members: HashMap::new(),
+                    static_members: HashMap::new(),
+                };
+                for directive in defn.block.directives.iter() {
+                    add_member(directive, &mut class);
+                }
+                self.classes.insert(qualify(&context.package_name, &defn.name.0), class);
+            },
+            Directive::FunctionDefinition(_) | Directive::VariableDefinition(_) => {
+                let definitions = self.package_definitions.entry(context.package_name.clone()).or_default();
+                add_definition_member(directive, definitions);
+            },
+            _ => {},
+        }
+    }
+
+    /// Checks the function bodies and variable initializers of a program.
+    pub fn check_program(&self, program: &Program) {
+        for package in program.packages.iter() {
+            let mut scope = TypeCheckerScope {
+                context: ResolutionContext {
+                    package_name: package.name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join("."),
+                    imports: Rc::new(collect_imports(&package.block.directives)),
+                },
+                class: None,
+                is_static: false,
+                locals: vec![],
+                result_types: vec![],
+            };
+            self.check_directives(&package.block.directives, &mut scope);
+        }
+        let mut scope = TypeCheckerScope {
+            context: ResolutionContext {
+                package_name: String::new(),
+                imports: Rc::new(collect_imports(&program.directives)),
+            },
+            class: None,
+            is_static: false,
+            locals: vec![HashMap::new()],
+            result_types: vec![StaticType::Any],
+        };
+        self.check_directives(&program.directives, &mut scope);
+    }
+
+    /// Resolves a type annotation.
+    fn resolve_type(&self, expression: &Expression, context: &ResolutionContext) -> StaticType {
+        match expression {
+            Expression::AnyType(_) => StaticType::Any,
+            Expression::VoidType(_) => StaticType::Void,
+            Expression::Paren(e) => self.resolve_type(&e.expression, context),
+            Expression::NullableType(e) => StaticType::Nullable(Box::new(self.resolve_type(&e.base, context))),
+            Expression::NonNullableType(e) => self.resolve_type(&e.base, context).non_nullable().clone(),
+            Expression::ArrayType(e) => StaticType::Array(Box::new(self.resolve_type(&e.expression, context))),
+            Expression::TupleType(e) => StaticType::Tuple(e.expressions.iter().map(|e| self.resolve_type(e, context)).collect()),
+            Expression::FunctionType(e) => StaticType::Function {
+                parameters: e.parameters.iter().map(|p| {
+                    (p.kind, p.type_expression.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, context)))
+                }).collect(),
+                result: Box::new(e.result_type.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, context))),
+            },
+            Expression::WithTypeArguments(e) => {
+                let StaticType::Class(name) = self.resolve_type(&e.base, context) else {
+                    return StaticType::Any;
+                };
+                StaticType::Generic(name, e.arguments.iter().map(|a| self.resolve_type(a, context)).collect())
+            },
+            _ => match dotted_name(expression) {
+                Some(name) => StaticType::Class(self.resolve_class_name(&name, context)),
+                None => StaticType::Any,
+            },
+        }
+    }
+
+    fn resolve_class_name(&self, name: &str, context: &ResolutionContext) -> String {
+        self.hierarchy.resolve_name(name, &context.package_name, &context.imports).unwrap_or_else(|| name.to_owned())
+    }
+
+    /// Resolves an expression that designates a class or interface, such as
+    /// the base of a `new` expression or of a static member access.
+    fn resolve_type_reference(&self, expression: &Expression, scope: &TypeCheckerScope) -> Option<StaticType> {
+        if let Expression::WithTypeArguments(e) = expression {
+            self.resolve_type_reference(&e.base, scope)?;
+            return Some(self.resolve_type(expression, &scope.context));
+        }
+        let name = dotted_name(expression)?;
+        if !name.contains('.') && self.lookup_variable(&name, scope).is_some() {
+            return None;
+        }
+        let qualified_name = self.hierarchy.resolve_name(&name, &scope.context.package_name, &scope.context.imports);
+        if let Some(qualified_name) = qualified_name {
+            return Some(StaticType::Class(qualified_name));
+        }
+        (StaticType::BUILTIN_TYPES.contains(&name.as_str()) || name == "Vector").then_some(StaticType::Class(name))
+    }
+
+    fn function_type(&self, common: &FunctionCommon, context: &ResolutionContext) -> StaticType {
+        StaticType::Function {
+            parameters: common.signature.parameters.iter().map(|p| {
+                (p.kind, p.destructuring.type_annotation.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, context)))
+            }).collect(),
+            result: Box::new(common.signature.result_type.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, context))),
+        }
+    }
+
+    fn member_type(&self, member: &CheckedMember, context: &ResolutionContext) -> StaticType {
+        match member {
+            CheckedMember::Method(common) => self.function_type(common, context),
+            CheckedMember::Accessor { getter: Some(getter), .. } => {
+                getter.signature.result_type.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, context))
+            },
+            CheckedMember::Accessor { setter: Some(setter), .. } => {
+                setter.signature.parameters.first()
+                    .and_then(|p| p.destructuring.type_annotation.as_ref())
+                    .map_or(StaticType::Any, |t| self.resolve_type(t, context))
+            },
+            CheckedMember::Accessor { .. } => StaticType::Any,
+            CheckedMember::Variable(annotation) => annotation.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, context)),
+        }
+    }
+
+    /// Looks up an instance or static member of a class through its superclasses,
+    /// or an instance member of an interface through the interfaces it extends.
+    fn lookup_member(&self, class_name: &str, name: &str, is_static: bool) -> Option<StaticType> {
+        self.lookup_member_visiting(class_name, name, is_static, &mut HashSet::new())
+    }
+
+    fn lookup_member_visiting(&self, class_name: &str, name: &str, is_static: bool, visited: &mut HashSet<String>) -> Option<StaticType> {
+        if !visited.insert(class_name.to_owned()) {
+            return None;
+        }
+        let class = self.classes.get(class_name)?;
+        let members = if is_static { &class.static_members } else { &class.instance_members };
+        if let Some(member) = members.get(name) {
+            return Some(self.member_type(member, &class.context));
+        }
+        if class.is_interface {
+            return self.hierarchy.interfaces(class_name).iter().find_map(|i| self.lookup_member_visiting(i, name, false, visited));
+        }
+        self.lookup_member_visiting(&self.hierarchy.superclass(class_name)?, name, is_static, visited)
+    }
+
+    /// Indicates whether a class and all of its superclasses
+    /// are known and not `dynamic`.
+    fn is_sealed(&self, class_name: &str) -> bool {
+        let mut class_name = class_name.to_owned();
+        let mut visited = HashSet::new();
+        loop {
+            let Some(class) = self.classes.get(&class_name) else {
+                return false;
+            };
+            if class.is_interface || class.is_dynamic || !visited.insert(class_name.clone()) {
+                return false;
+            }
+            if !class.has_extends_clause {
+                return true;
+            }
+            let Some(superclass) = self.hierarchy.superclass(&class_name) else {
+                return false;
+            };
+            class_name = superclass;
+        }
+    }
+
+    fn lookup_variable(&self, name: &str, scope: &TypeCheckerScope) -> Option<StaticType> {
+        if let Some(t) = scope.locals.iter().rev().find_map(|locals| locals.get(name)) {
+            return Some(t.clone());
+        }
+        if let Some(class_name) = &scope.class {
+            if !scope.is_static {
+                if let Some(t) = self.lookup_member(class_name, name, false) {
+                    return Some(t);
+                }
+            }
+            if let Some(t) = self.lookup_member(class_name, name, true) {
+                return Some(t);
+            }
+        }
+        let mut packages = vec![scope.context.package_name.clone()];
+        for import in scope.context.imports.iter() {
+            let package_name = import.package_name.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".");
+            match &import.import_specifier {
+                ImportSpecifier::Identifier((imported, _)) if imported == name && import.alias.is_none() => packages.push(package_name),
+                ImportSpecifier::Wildcard(_) if import.alias.is_none() => packages.push(package_name),
+                _ => {},
+            }
+        }
+        packages.push(String::new());
+        packages.iter().find_map(|package_name| {
+            let member = self.package_definitions.get(package_name)?.get(name)?;
+            let context = ResolutionContext {
+                package_name: package_name.clone(),
+                imports: Rc::new(vec![]),
+            };
+            Some(self.member_type(member, if *package_name == scope.context.package_name { &scope.context } else { &context }))
+        })
+    }
+
+    fn check_directives(&self, directives: &[Rc<Directive>], scope: &mut TypeCheckerScope) {
+        for directive in directives.iter() {
+            self.check_directive(directive, scope);
+        }
+    }
+
+    fn check_directive(&self, directive: &Rc<Directive>, scope: &mut TypeCheckerScope) {
+        match directive.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                let class = scope.class.replace(qualify(&scope.context.package_name, &defn.name.0));
+                for directive in defn.block.directives.iter() {
+                    let is_static = match directive.as_ref() {
+                        Directive::FunctionDefinition(d) => Attribute::find_static(&d.attributes).is_some(),
+                        Directive::VariableDefinition(d) => Attribute::find_static(&d.attributes).is_some(),
+                        _ => false,
+                    };
+                    scope.is_static = is_static;
+                    self.check_directive(directive, scope);
+                }
+                scope.is_static = false;
+                scope.class = class;
+            },
+            Directive::FunctionDefinition(defn) => {
+                self.check_function(&defn.common, scope);
+                if let (FunctionName::Identifier((name, _)), false) = (&defn.name, scope.locals.is_empty()) {
+                    let t = self.function_type(&defn.common, &scope.context);
+                    scope.locals.last_mut().unwrap().insert(name.clone(), t);
+                }
+            },
+            Directive::VariableDefinition(defn) => self.check_variable_bindings(&defn.bindings, scope),
+            Directive::ExpressionStatement(d) => self.infer_statement_expression(&d.expression, scope),
+            Directive::Block(block) => self.check_directives(&block.directives, scope),
+            Directive::LabeledStatement(d) => self.check_directive(&d.substatement, scope),
+            Directive::IfStatement(d) => {
+                self.infer_value(&d.test, scope);
+                self.check_directive(&d.consequent, scope);
+                if let Some(alternative) = &d.alternative {
+                    self.check_directive(alternative, scope);
+                }
+            },
+            Directive::WhileStatement(d) => {
+                self.infer_value(&d.test, scope);
+                self.check_directive(&d.body, scope);
+            },
+            Directive::DoStatement(d) => {
+                self.check_directive(&d.body, scope);
+                self.infer_value(&d.test, scope);
+            },
+            Directive::ForStatement(d) => {
+                match &d.init {
+                    Some(ForInitializer::Expression(exp)) => {
+                        self.infer_expression(exp, scope);
+                    },
+                    Some(ForInitializer::VariableDefinition(defn)) => self.check_variable_bindings(&defn.bindings, scope),
+                    None => {},
+                }
+                if let Some(test) = &d.test {
+                    self.infer_value(test, scope);
+                }
+                if let Some(update) = &d.update {
+                    self.infer_expression(update, scope);
+                }
+                self.check_directive(&d.body, scope);
+            },
+            Directive::ForInStatement(d) => {
+                if let ForInBinding::VariableDefinition(defn) = &d.left {
+                    self.check_variable_bindings(&defn.bindings, scope);
+                }
+                self.infer_value(&d.right, scope);
+                self.check_directive(&d.body, scope);
+            },
+            Directive::ReturnStatement(d) => {
+                let Some(exp) = &d.expression else {
+                    return;
+                };
+                let t = self.infer_value(exp, scope);
+                let result_type = scope.result_types.last().cloned().unwrap_or(StaticType::Any);
+                if !result_type.is_void() {
+                    self.check_assignable(&t, &result_type, &exp.location());
+                }
+            },
+            Directive::ThrowStatement(d) => {
+                self.infer_value(&d.expression, scope);
+            },
+            Directive::SwitchStatement(d) => {
+                self.infer_value(&d.discriminant, scope);
+                for case in d.cases.iter() {
+                    for label in case.labels.iter() {
+                        if let CaseLabel::Case((exp, _)) = label {
+                            self.infer_value(exp, scope);
+                        }
+                    }
+                    self.check_directives(&case.directives, scope);
+                }
+            },
+            Directive::TryStatement(d) => {
+                self.check_directives(&d.block.directives, scope);
+                for catch_clause in d.catch_clauses.iter() {
+                    self.declare(&catch_clause.parameter, scope);
+                    self.check_directives(&catch_clause.block.directives, scope);
+                }
+                if let Some(finally_clause) = &d.finally_clause {
+                    self.check_directives(&finally_clause.block.directives, scope);
+                }
+            },
+            Directive::ConfigurationDirective(d) => self.check_directive(&d.directive, scope),
+            Directive::NormalConfigurationDirective(d) => self.check_directive(&d.directive, scope),
+            _ => {},
+        }
+    }
+
+    fn check_variable_bindings(&self, bindings: &[Rc<VariableBinding>], scope: &mut TypeCheckerScope) {
+        for binding in bindings.iter() {
+            let declared = binding.destructuring.type_annotation.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, &scope.context));
+            if let Some(initializer) = &binding.initializer {
+                let t = self.infer_value(initializer, scope);
+                self.check_assignable(&t, &declared, &initializer.location());
+            }
+            self.declare(&binding.destructuring, scope);
+        }
+    }
+
+    /// Declares a local variable, unless checking a class block.
+    fn declare(&self, destructuring: &TypedDestructuring, scope: &mut TypeCheckerScope) {
+        let Some((name, _)) = destructuring.destructuring.to_identifier_name() else {
+            return;
+        };
+        let t = destructuring.type_annotation.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, &scope.context));
+        if let Some(locals) = scope.locals.last_mut() {
+            locals.insert(name, t);
+        }
+    }
+
+    fn check_function(&self, common: &FunctionCommon, scope: &mut TypeCheckerScope) {
+        let mut locals = HashMap::new();
+        for parameter in common.signature.parameters.iter() {
+            let declared = parameter.destructuring.type_annotation.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, &scope.context));
+            if let Some(default_value) = &parameter.default_value {
+                let t = self.infer_value(default_value, scope);
+                self.check_assignable(&t, &declared, &default_value.location());
+            }
+            if let Some((name, _)) = parameter.destructuring.destructuring.to_identifier_name() {
+                let declared = if parameter.kind == ParameterKind::Rest && declared.is_any() { StaticType::class("Array") } else { declared };
+                locals.insert(name, declared);
+            }
+        }
+        let mut hoisted = LocalDeclarationCollector::default();
+        if let Some(FunctionBody::Block(block)) = &common.body {
+            TreeWalker::walk_directives(&mut hoisted, &block.directives);
+        }
+        for destructuring in hoisted.variables.iter() {
+            if let Some((name, _)) = destructuring.destructuring.to_identifier_name() {
+                let t = destructuring.type_annotation.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, &scope.context));
+                locals.insert(name, t);
+            }
+        }
+        for (name, function) in hoisted.functions.iter() {
+            locals.insert(name.clone(), self.function_type(function, &scope.context));
+        }
+        let result_type = common.signature.result_type.as_ref().map_or(StaticType::Any, |t| self.resolve_type(t, &scope.context));
+        scope.locals.push(locals);
+        scope.result_types.push(result_type.clone());
+        match &common.body {
+            Some(FunctionBody::Block(block)) => self.check_directives(&block.directives, scope),
+            Some(FunctionBody::Expression(exp)) => {
+                let t = self.infer_value(exp, scope);
+                if !result_type.is_void() {
+                    self.check_assignable(&t, &result_type, &exp.location());
+                }
+            },
+            None => {},
+        }
+        scope.result_types.pop();
+        scope.locals.pop();
+    }
+
+    fn check_assignable(&self, t: &StaticType, target: &StaticType, location: &Location) {
+        if !t.is_void() && !t.is_assignable_to(target, &self.hierarchy) {
+            report(location, DiagnosticKind::IncompatibleTypes, diagarg![t.to_string(), target.to_string()]);
+        } else if *t == StaticType::Null && target.is_primitive() {
+            location.compilation_unit().add_diagnostic(Diagnostic::new_warning(location, DiagnosticKind::NullToPrimitive, diagarg![target.to_string()]));
+        }
+    }
+
+    /// Infers the type of an expression used as a value,
+    /// reporting `void` results.
+    fn infer_value(&self, expression: &Rc<Expression>, scope: &mut TypeCheckerScope) -> StaticType {
+        let t = self.infer_expression(expression, scope);
+        if t.is_void() {
+            report(&expression.location(), DiagnosticKind::VoidUsedAsValue, vec![]);
+            return StaticType::Any;
+        }
+        t
+    }
+
+    /// Checks an expression whose result is discarded, allowing
+    /// `void` results in the branches of `&&`, `||`, `?:` and `,`.
+    fn infer_statement_expression(&self, expression: &Rc<Expression>, scope: &mut TypeCheckerScope) {
+        match expression.as_ref() {
+            Expression::Binary(e) if matches!(e.operator, Operator::LogicalAnd | Operator::LogicalOr) => {
+                self.infer_value(&e.left, scope);
+                self.infer_statement_expression(&e.right, scope);
+            },
+            Expression::Conditional(e) => {
+                self.infer_value(&e.test, scope);
+                self.infer_statement_expression(&e.consequent, scope);
+                self.infer_statement_expression(&e.alternative, scope);
+            },
+            Expression::Sequence(e) => {
+                self.infer_statement_expression(&e.left, scope);
+                self.infer_statement_expression(&e.right, scope);
+            },
+            _ => {
+                self.infer_expression(expression, scope);
+            },
+        }
+    }
+
+    /// Infers the type of an expression, checking its subexpressions.
+    fn infer_expression(&self, expression: &Rc<Expression>, scope: &mut TypeCheckerScope) -> StaticType {
+        match expression.as_ref() {
+            Expression::NullLiteral(_) => StaticType::Null,
+            Expression::BooleanLiteral(_) => StaticType::class("Boolean"),
+            Expression::NumericLiteral(_) => StaticType::class("Number"),
+            Expression::StringLiteral(_) => StaticType::class("String"),
+            Expression::RegExpLiteral(_) => StaticType::class("RegExp"),
+            Expression::Xml(_) | Expression::XmlMarkup(_) => StaticType::class("XML"),
+            Expression::XmlList(_) => StaticType::class("XMLList"),
+            Expression::ThisLiteral(_) => match &scope.class {
+                Some(class_name) if !scope.is_static => StaticType::Class(class_name.clone()),
+                _ => StaticType::Any,
+            },
+            Expression::Paren(e) => self.infer_expression(&e.expression, scope),
+            Expression::QualifiedIdentifier(id) => {
+                let Some((name, _)) = id.to_identifier_name() else {
+                    return StaticType::Any;
+                };
+                if let Some(t) = self.lookup_variable(&name, scope) {
+                    return t;
+                }
+                if self.resolve_type_reference(expression, scope).is_some() {
+                    return StaticType::class("Class");
+                }
+                StaticType::Any
+            },
+            Expression::ArrayLiteral(e) => {
+                let mut types = vec![];
+                let mut is_tuple = e.elements.len() >= 2;
+                for element in e.elements.iter() {
+                    match element {
+                        Element::Expression(exp) => types.push(self.infer_value(exp, scope)),
+                        Element::Rest((exp, _)) => {
+                            self.infer_value(exp, scope);
+                            is_tuple = false;
+                        },
+                        Element::Elision => is_tuple = false,
+                    }
+                }
+                if is_tuple {
+                    StaticType::Tuple(types)
+                } else if types.len() == 1 {
+                    StaticType::Array(Box::new(types.remove(0)))
+                } else {
+                    StaticType::Array(Box::new(StaticType::Any))
+                }
+            },
+            Expression::VectorLiteral(e) => {
+                let element_type = self.resolve_type(&e.element_type, &scope.context);
+                for element in e.elements.iter() {
+                    if let Element::Expression(exp) = element {
+                        let t = self.infer_value(exp, scope);
+                        self.check_assignable(&t, &element_type, &exp.location());
+                    }
+                }
+                StaticType::Generic("Vector".into(), vec![element_type])
+            },
+            Expression::ObjectInitializer(e) => {
+                for field in e.fields.iter() {
+                    match field.as_ref() {
+                        InitializerField::Field { value: Some(value), .. } => {
+                            self.infer_value(value, scope);
+                        },
+                        InitializerField::Rest((exp, _)) => {
+                            self.infer_value(exp, scope);
+                        },
+                        _ => {},
+                    }
+                }
+                StaticType::class("Object")
+            },
+            Expression::Function(e) => {
+                self.check_function(&e.common, scope);
+                self.function_type(&e.common, &scope.context)
+            },
+            Expression::New(e) => {
+                let arguments = e.arguments.as_deref().unwrap_or_default();
+                let Some(t) = self.resolve_type_reference(&e.base, scope) else {
+                    self.infer_value(&e.base, scope);
+                    self.infer_arguments(arguments, scope);
+                    return StaticType::Any;
+                };
+                let constructor = match &t {
+                    StaticType::Class(class_name) => self.classes.get(class_name).and_then(|class| {
+                        Some((class.constructor.clone()?, class.context.clone()))
+                    }),
+                    _ => None,
+                };
+                if let Some((constructor, context)) = constructor {
+                    let StaticType::Function { parameters, .. } = self.function_type(&constructor, &context) else {
+                        unreachable!();
+                    };
+                    self.check_arguments(&parameters, arguments, &e.location, scope);
+                } else {
+                    self.infer_arguments(arguments, scope);
+                }
+                t
+            },
+            Expression::Member(e) => {
+                let Some((name, name_location)) = e.identifier.to_identifier_name() else {
+                    self.infer_value(&e.base, scope);
+                    return StaticType::Any;
+                };
+                if let Some(StaticType::Class(class_name)) = self.resolve_type_reference(&e.base, scope) {
+                    return self.lookup_member(&class_name, &name, true).unwrap_or(StaticType::Any);
+                }
+                let base_type = self.infer_value(&e.base, scope);
+                self.member_access_type(&base_type, &name, &name_location)
+            },
+            Expression::ComputedMember(e) => {
+                let base_type = self.infer_value(&e.base, scope);
+                self.infer_value(&e.key, scope);
+                match base_type.non_nullable() {
+                    StaticType::Array(element_type) => element_type.as_ref().clone(),
+                    StaticType::Generic(name, arguments) if name == "Vector" && arguments.len() == 1 => arguments[0].clone(),
+                    _ => StaticType::Any,
+                }
+            },
+            Expression::Call(e) => {
+                if let Some(t) = self.resolve_type_reference(&e.base, scope) {
+                    // Type conversion, such as `int(x)`
+                    self.infer_arguments(&e.arguments, scope);
+                    return t;
+                }
+                let base_type = self.infer_value(&e.base, scope);
+                match base_type.non_nullable() {
+                    StaticType::Function { parameters, result } => {
+                        self.check_arguments(parameters, &e.arguments, &e.location, scope);
+                        result.as_ref().clone()
+                    },
+                    _ => {
+                        self.infer_arguments(&e.arguments, scope);
+                        StaticType::Any
+                    },
+                }
+            },
+            Expression::WithTypeArguments(_) => StaticType::class("Class"),
+            Expression::Unary(e) => {
+                let t = self.infer_value(&e.expression, scope);
+                match e.operator {
+                    Operator::LogicalNot | Operator::Delete => StaticType::class("Boolean"),
+                    Operator::Typeof => StaticType::class("String"),
+                    Operator::Void => StaticType::Any,
+                    Operator::NonNull => t.non_nullable().clone(),
+                    Operator::Await | Operator::Yield => StaticType::Any,
+                    Operator::BitwiseNot => StaticType::class("int"),
+                    _ => StaticType::class("Number"),
+                }
+            },
+            Expression::Binary(e) => {
+                let left = self.infer_value(&e.left, scope);
+                match e.operator {
+                    Operator::As => {
+                        return match self.resolve_type_reference(&e.right, scope) {
+                            Some(t) => t,
+                            None => {
+                                self.infer_value(&e.right, scope);
+                                StaticType::Any
+                            },
+                        };
+                    },
+                    Operator::Is | Operator::IsNot | Operator::Instanceof => {
+                        if self.resolve_type_reference(&e.right, scope).is_none() {
+                            self.infer_value(&e.right, scope);
+                        }
+                        return StaticType::class("Boolean");
+                    },
+                    _ => {},
+                }
+                let right = self.infer_value(&e.right, scope);
+                match e.operator {
+                    Operator::Add => {
+                        if left == StaticType::class("String") || right == StaticType::class("String") {
+                            StaticType::class("String")
+                        } else if left.is_numeric() && right.is_numeric() {
+                            StaticType::class("Number")
+                        } else {
+                            StaticType::Any
+                        }
+                    },
+                    Operator::Power | Operator::Multiply | Operator::Divide | Operator::Remainder | Operator::Subtract => StaticType::class("Number"),
+                    Operator::ShiftLeft | Operator::ShiftRight | Operator::BitwiseAnd | Operator::BitwiseXor | Operator::BitwiseOr => StaticType::class("int"),
+                    Operator::ShiftRightUnsigned => StaticType::class("uint"),
+                    Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge |
+                    Operator::Equals | Operator::NotEquals | Operator::StrictEquals | Operator::StrictNotEquals |
+                    Operator::In | Operator::NotIn | Operator::LogicalXor => StaticType::class("Boolean"),
+                    _ => if left == right { left } else { StaticType::Any },
+                }
+            },
+            Expression::Conditional(e) => {
+                self.infer_value(&e.test, scope);
+                let consequent = self.infer_value(&e.consequent, scope);
+                let alternative = self.infer_value(&e.alternative, scope);
+                if consequent == alternative { consequent } else { StaticType::Any }
+            },
+            Expression::Assignment(e) => {
+                let right = self.infer_value(&e.right, scope);
+                let left = match e.left.as_ref() {
+                    Expression::ArrayLiteral(_) | Expression::ObjectInitializer(_) => StaticType::Any,
+                    _ => self.infer_expression(&e.left, scope),
+                };
+                if e.compound.is_none() {
+                    self.check_assignable(&right, &left, &e.right.location());
+                }
+                right
+            },
+            Expression::Sequence(e) => {
+                self.infer_expression(&e.left, scope);
+                self.infer_expression(&e.right, scope)
+            },
+            Expression::OptionalChaining(e) => {
+                self.infer_value(&e.base, scope);
+                StaticType::Any
+            },
+            Expression::Super(e) => {
+                if let Some(object) = &e.object {
+                    self.infer_arguments(object, scope);
+                }
+                StaticType::Any
+            },
+            _ => StaticType::Any,
+        }
+    }
+
+    /// Returns the type of a member accessed through a value of a given type,
+    /// reporting undefined members of sealed classes.
+    fn member_access_type(&self, base_type: &StaticType, name: &str, location: &Location) -> StaticType {
+        match base_type.non_nullable() {
+            StaticType::Class(class_name) if self.classes.contains_key(class_name) => {
+                if let Some(t) = self.lookup_member(class_name, name, false) {
+                    return t;
+                }
+                if self.is_sealed(class_name) && !OBJECT_MEMBERS.contains(&name) {
+                    report(location, DiagnosticKind::UndefinedProperty, diagarg![name.to_owned(), base_type.to_string()]);
+                }
+                StaticType::Any
+            },
+            StaticType::Array(_) | StaticType::Tuple(_) if name == "length" => StaticType::class("uint"),
+            StaticType::Class(class_name) if class_name == "Array" && name == "length" => StaticType::class("uint"),
+            StaticType::Class(class_name) if class_name == "String" && name == "length" => StaticType::class("int"),
+            _ => StaticType::Any,
+        }
+    }
+
+    fn infer_arguments(&self, arguments: &[Rc<Expression>], scope: &mut TypeCheckerScope) {
+        for argument in arguments.iter() {
+            self.infer_value(argument, scope);
+        }
+    }
+
+    fn check_arguments(&self, parameters: &[(ParameterKind, StaticType)], arguments: &[Rc<Expression>], location: &Location, scope: &mut TypeCheckerScope) {
+        let required = parameters.iter().filter(|(kind, _)| *kind == ParameterKind::Required).count();
+        let has_rest = parameters.iter().any(|(kind, _)| *kind == ParameterKind::Rest);
+        let maximum = parameters.iter().filter(|(kind, _)| *kind != ParameterKind::Rest).count();
+        for (i, argument) in arguments.iter().enumerate() {
+            let t = self.infer_value(argument, scope);
+            if let Some((ParameterKind::Required | ParameterKind::Optional, parameter_type)) = parameters.get(i) {
+                self.check_assignable(&t, parameter_type, &argument.location());
+            }
+        }
+        if arguments.len() < required || (!has_rest && arguments.len() > maximum) {
+            let expected = if has_rest {
+                format!("at least {required}")
+            } else if required == maximum {
+                required.to_string()
+            } else {
+                format!("{required} to {maximum}")
+            };
+            report(location, DiagnosticKind::IncorrectArgumentCount, diagarg![expected]);
+        }
+    }
+}
+
+/// Collects the variables and functions declared in a function body,
+/// excluding nested functions.
+#[derive(Default)]
+struct LocalDeclarationCollector {
+    variables: Vec<TypedDestructuring>,
+    functions: Vec<(String, Rc<FunctionCommon>)>,
+}
+
+impl TreeVisitor for LocalDeclarationCollector {
+    fn visit_directive(&mut self, directive: &Rc<Directive>) -> bool {
+        match directive.as_ref() {
+            Directive::VariableDefinition(defn) => {
+                self.variables.extend(defn.bindings.iter().map(|binding| binding.destructuring.clone()));
+                false
+            },
+            Directive::FunctionDefinition(defn) => {
+                if let FunctionName::Identifier((name, _)) = &defn.name {
+                    self.functions.push((name.clone(), defn.common.clone()));
+                }
+                false
+            },
+            _ => true,
+        }
+    }
+
+    fn visit_expression(&mut self, _expression: &Rc<Expression>) -> bool {
+        false
+    }
+}
+
+fn add_member(directive: &Rc<Directive>, class: &mut CheckedClass) {
+    match directive.as_ref() {
+        Directive::FunctionDefinition(defn) => {
+            if let FunctionName::Constructor(_) = defn.name {
+                class.constructor = Some(defn.common.clone());
+                return;
+            }
+            let members = if Attribute::find_static(&defn.attributes).is_some() { &mut class.static_members } else { &mut class.instance_members };
+            add_definition_member(directive, members);
+        },
+        Directive::VariableDefinition(defn) => {
+            let members = if Attribute::find_static(&defn.attributes).is_some() { &mut class.static_members } else { &mut class.instance_members };
+            add_definition_member(directive, members);
+        },
+        Directive::ConfigurationDirective(d) => add_member(&d.directive, class),
+        Directive::NormalConfigurationDirective(d) => add_member(&d.directive, class),
+        Directive::Block(block) => {
+            for directive in block.directives.iter() {
+                add_member(directive, class);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn add_definition_member(directive: &Rc<Directive>, members: &mut HashMap<String, CheckedMember>) {
+    match directive.as_ref() {
+        Directive::FunctionDefinition(defn) => match &defn.name {
+            FunctionName::Identifier((name, _)) => {
+                members.insert(name.clone(), CheckedMember::Method(defn.common.clone()));
+            },
+            FunctionName::Getter((name, _)) | FunctionName::Setter((name, _)) => {
+                let member = members.entry(name.clone()).or_insert(CheckedMember::Accessor { getter: None, setter: None });
+                if let CheckedMember::Accessor { getter, setter } = member {
+                    if defn.is_getter() {
+                        *getter = Some(defn.common.clone());
+                    } else {
+                        *setter = Some(defn.common.clone());
+                    }
+                }
+            },
+            FunctionName::Constructor(_) => {},
+        },
+        Directive::VariableDefinition(defn) => {
+            for binding in defn.bindings.iter() {
+                if let Some((name, _)) = binding.destructuring.destructuring.to_identifier_name() {
+                    members.insert(name, CheckedMember::Variable(binding.destructuring.type_annotation.clone()));
+                }
+            }
+        },
+        _ => {},
+    }
+}
+
+fn collect_imports(directives: &[Rc<Directive>]) -> Vec<ImportDirective> {
+    directives.iter().filter_map(|directive| {
+        if let Directive::ImportDirective(import) = directive.as_ref() { Some(import.clone()) } else { None }
+    }).collect()
+}
+
+fn report(location: &Location, kind: DiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+    location.compilation_unit().add_diagnostic(Diagnostic::new_verify_error(location, kind, arguments));
+}
+
+fn dotted_name(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::QualifiedIdentifier(id) => id.to_identifier_name().map(|(name, _)| name),
+        Expression::Member(member) => {
+            let (name, _) = member.identifier.to_identifier_name()?;
+            Some(format!("{}.{name}", dotted_name(&member.base)?))
+        },
+        _ => None,
+    }
+}
+
+fn qualify(package_name: &str, name: &str) -> String {
+    if package_name.is_empty() { name.to_owned() } else { format!("{package_name}.{name}") }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_type_checker() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+package com.acme {
+    public class Shape {
+        public var name: String;
+        public function get area(): Number { return 0 }
+        public function Shape(name: String) { this.name = name }
+        public function scale(factor: Number, ...rest): Shape { return this }
+    }
+    public dynamic class Bag {}
+    public class Circle extends Shape {
+        public var radius: Number = "1";
+        public function Circle() { super("circle") }
+        public function describe(): String {
+            var a: Number = area;
+            var n: int = name;
+            var s: ?Shape = null;
+            var b: Boolean = null;
+            var list: [Shape] = [this, new Shape("a")];
+            var pair: [Number, String] = [1, "x"];
+            var wrong: [Number, String] = ["x", 1];
+            var f: function(Number): Shape = scale;
+            var v: Vector.<Number> = new <Number>[1, "2"];
+            var bag: Bag = new Bag();
+            bag.anything = 1;
+            scale();
+            scale(1, 2, 3);
+            f("x");
+            new Shape();
+            this.perimeter();
+            toString();
+            var c: Boolean = true;
+            c && nothing();
+            c ? nothing() : nothing();
+            c || nothing(), nothing();
+            var u: * = nothing();
+            var first: Shape = list[0];
+            var count: String = list.length;
+            return 1;
+        }
+        private function nothing(): void {}
+    }
+}"#.into());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        assert!(!compilation_unit.invalidated());
+        let mut checker = TypeChecker::new();
+        checker.add_program(&program);
+        checker.check_program(&program);
+
+        let summary: Vec<String> = compilation_unit.diagnostics().iter().map(|d| {
+            format!("{}: {}", d.location().text(), d.format_message_english())
+        }).collect();
+        assert_eq!(summary, [
+            "\"1\": Implicit coercion of a value of type 'String' to an unrelated type 'Number'.",
+            "name: Implicit coercion of a value of type 'String' to an unrelated type 'int'.",
+            "null: 'null' converts to the default value of type 'Boolean'.",
+            "[\"x\", 1]: Implicit coercion of a value of type '[String, Number]' to an unrelated type '[Number, String]'.",
+            "\"2\": Implicit coercion of a value of type 'String' to an unrelated type 'Number'.",
+            "scale(): Incorrect number of arguments; expected at least 1.",
+            "\"x\": Implicit coercion of a value of type 'String' to an unrelated type 'Number'.",
+            "new Shape(): Incorrect number of arguments; expected 1.",
+            "perimeter: Access of possibly undefined property 'perimeter' through a reference with static type 'com.acme.Circle'.",
+            "nothing(): A 'void' result cannot be used as a value.",
+            "list.length: Implicit coercion of a value of type 'uint' to an unrelated type 'String'.",
+            "1: Implicit coercion of a value of type 'Number' to an unrelated type 'String'.",
+        ]);
+    }
+
+    #[test]
+    fn test_type_checker_builtin_superclasses() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+package com.acme {
+    public class MyError extends Error {}
+    public class Items extends Array {}
+    public class Widget extends flash.display.Sprite {}
+    public class Checker {
+        public function check(): Error {
+            var e: Error = new MyError();
+            var items: Array = new Items();
+            var sprite: flash.display.Sprite = new Widget();
+            var wrong: String = new MyError();
+            return new MyError();
+        }
+    }
+}"#.into());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        let mut checker = TypeChecker::new();
+        checker.add_program(&program);
+        checker.check_program(&program);
+        let summary: Vec<String> = compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect();
+        assert_eq!(summary, ["Implicit coercion of a value of type 'com.acme.MyError' to an unrelated type 'String'."]);
+    }
+
+    #[test]
+    fn test_type_checker_circular_inheritance() {
+        let compilation_unit = CompilationUnit::new(None, r#"
+package {
+    public class A extends B {}
+    public class B extends A {}
+    function f(a: A): void { a.foo(); }
+}"#.into());
+        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        let mut checker = TypeChecker::new();
+        checker.add_program(&program);
+        checker.check_program(&program);
+        assert!(!compilation_unit.invalidated());
+    }
+}
@@ -5,4 +5,6 @@ pub use compilation_unit::*;
 mod comment;
 pub use comment::*;
 mod location;
-pub use location::*;
\ No newline at end of file
+pub use location::*;
+mod embedded_compilation_unit;
+pub use embedded_compilation_unit::*;
\ No newline at end of file
@@ -0,0 +1,87 @@
+use crate::ns::*;
+
+/// A compilation unit whose source text is assembled from fragments
+/// of a host compilation unit, such as code examples inside ASDoc
+/// comments, where the fragments are not contiguous in the host.
+///
+/// Offsets in the embedded source text are mapped back to
+/// offsets in the host compilation unit.
+pub struct EmbeddedCompilationUnit {
+    host: Rc<CompilationUnit>,
+    compilation_unit: Rc<CompilationUnit>,
+    /// Pairs of embedded offset and host offset, one for
+    /// the start of each fragment, in ascending order.
+    mappings: Vec<(usize, usize)>,
+}
+
+impl EmbeddedCompilationUnit {
+    /// Constructs an embedded compilation unit by concatenating fragments,
+    /// each consisting of a text and the host offset it starts at.
+    pub fn new(host: &Rc<CompilationUnit>, fragments: &[(String, usize)]) -> Self {
+        let mut text = String::new();
+        let mut mappings = vec![];
+        for (fragment, host_offset) in fragments {
+            mappings.push((text.len(), *host_offset));
+            text.push_str(fragment);
+        }
+        let compilation_unit = CompilationUnit::new(host.file_path(), text);
+        compilation_unit.set_compiler_options(host.compiler_options());
+        Self {
+            host: host.clone(),
+            compilation_unit,
+            mappings,
+        }
+    }
+
+    /// The host compilation unit.
+    pub fn host(&self) -> Rc<CompilationUnit> {
+        self.host.clone()
+    }
+
+    /// The compilation unit consisting of the embedded source text.
+    pub fn compilation_unit(&self) -> Rc<CompilationUnit> {
+        self.compilation_unit.clone()
+    }
+
+    /// Maps an offset of the embedded source text to
+    /// an offset of the host compilation unit.
+    pub fn host_offset(&self, offset: usize) -> usize {
+        let i = self.mappings.partition_point(|(embedded_offset, _)| *embedded_offset <= offset);
+        if i == 0 {
+            return self.mappings.first().map_or(0, |(_, host_offset)| *host_offset);
+        }
+        let (embedded_offset, host_offset) = self.mappings[i - 1];
+        (host_offset + (offset - embedded_offset)).min(self.host.text().len())
+    }
+
+    /// Maps a location of the embedded source text to
+    /// a location of the host compilation unit.
+    pub fn host_location(&self, location: &Location) -> Location {
+        let first_offset = self.host_offset(location.first_offset());
+        let last_offset = self.host_offset(location.last_offset()).max(first_offset);
+        Location::with_offsets(&self.host, first_offset, last_offset)
+    }
+
+    /// Contributes the diagnostics of the embedded compilation unit
+    /// to the host compilation unit, mapping their locations.
+    pub fn transfer_diagnostics(&self) {
+        for diagnostic in self.compilation_unit.diagnostics() {
+            let location = self.host_location(&diagnostic.location);
+            if diagnostic.is_warning() {
+                if self.host.prevent_equal_offset_warning(&location) {
+                    continue;
+                }
+            } else if self.host.prevent_equal_offset_error(&location) {
+                continue;
+            }
+            self.host.add_diagnostic(Diagnostic {
+                location,
+                kind: diagnostic.kind,
+                is_warning: diagnostic.is_warning,
+                is_verify_error: diagnostic.is_verify_error,
+                arguments: diagnostic.arguments.clone(),
+                custom_kind: RefCell::new(diagnostic.custom_kind()),
+            });
+        }
+    }
+}
@@ -68,6 +68,63 @@ pub enum DiagnosticKind {
     ExpectingStatement = 1088,
     Unexpected = 1089,
     XmlClosingTagNameMustBeEquals = 1090,
+    UnterminatedMxmlBinding = 1091,
+    TwoWayMxmlBindingMustBeSingleExpression = 1092,
+    UnknownMxmlComponent = 1093,
+    MxmlComponentHasNoDefaultProperty = 1094,
+    ManifestComponentMustSpecifyClass = 1095,
+    UndeclaredMxmlState = 1096,
+    DuplicateMxmlState = 1097,
+    IncludeInAndExcludeFromAreMutuallyExclusive = 1098,
+    InvalidItemCreationPolicy = 1099,
+    FxgRootMustBeGraphic = 1100,
+    UnknownFxgElement = 1101,
+    FxgElementNotAllowedHere = 1102,
+    UnknownFxgAttribute = 1103,
+    InvalidFxgAttributeValue = 1104,
+    InvalidFxgPathData = 1105,
+    MxmlLanguageElementMustBeRootChild = 1106,
+    MxmlLibraryMustBeFirstChild = 1107,
+    MxmlElementMustHaveSingleChild = 1108,
+    InvalidMxmlClassName = 1109,
+    MissingMxmlAttribute = 1110,
+    UnexpectedMxmlElement = 1111,
+    MxmlSourceAndContentAreMutuallyExclusive = 1112,
+    MxmlReparentMustSpecifyState = 1113,
+    UnknownMediaFeature = 1114,
+    InvalidMediaFeatureValue = 1115,
+    UnknownStyle = 1116,
+    InvalidStyleValue = 1117,
+    StyleValueNotInEnumeration = 1118,
+    UnresolvedCssClassReference = 1119,
+    CircularCssImport = 1120,
+    AssetNotFound = 1121,
+    MissingEmbedSource = 1122,
+    UnknownEmbedEntry = 1123,
+    InvalidEmbedEntryValue = 1124,
+    UnknownImportPackage = 1125,
+    UnknownImportDefinition = 1126,
+    UnusedImport = 1127,
+    DuplicateImport = 1128,
+    ShadowedImport = 1129,
+    CircularInheritance = 1130,
+    ExtendingFinalClass = 1131,
+    ClassExpected = 1132,
+    InterfaceExpected = 1133,
+    MissingInterfaceMember = 1134,
+    MissingOverride = 1135,
+    UnnecessaryOverride = 1136,
+    IncompatibleTypes = 1137,
+    IncorrectArgumentCount = 1138,
+    UndefinedProperty = 1139,
+    VoidUsedAsValue = 1140,
+    DivisionByZero = 1141,
+    ConstantOverflow = 1142,
+    NotAConstant = 1143,
+    InvalidMxmlNumericValue = 1144,
+    IncompatibleOverride = 1145,
+    NullToPrimitive = 1146,
+    UnsupportedCssSelector = 1147,
 }
 
 impl DiagnosticKind {
@@ -71,6 +71,63 @@ lazy_static! {
         DiagnosticKind::ExpectingStatement.id() => "Expecting statement before {1}.".into(),
         DiagnosticKind::Unexpected.id() => "Unexpected {1}.".into(),
         DiagnosticKind::XmlClosingTagNameMustBeEquals.id() => "Closing tag name must be equals '{1}'.".into(),
+        DiagnosticKind::UnterminatedMxmlBinding.id() => "Unterminated data binding expression.".into(),
+        DiagnosticKind::TwoWayMxmlBindingMustBeSingleExpression.id() => "A two-way data binding must consist of exactly one expression.".into(),
+        DiagnosticKind::UnknownMxmlComponent.id() => "Unknown component '{1}'.".into(),
+        DiagnosticKind::MxmlComponentHasNoDefaultProperty.id() => "Component '{1}' does not declare a default property.".into(),
+        DiagnosticKind::ManifestComponentMustSpecifyClass.id() => "A manifest component must specify a 'class' attribute.".into(),
+        DiagnosticKind::UndeclaredMxmlState.id() => "'{1}' is not a declared state or state group.".into(),
+        DiagnosticKind::DuplicateMxmlState.id() => "Duplicate state '{1}'.".into(),
+        DiagnosticKind::IncludeInAndExcludeFromAreMutuallyExclusive.id() => "The 'includeIn' and 'excludeFrom' attributes are mutually exclusive.".into(),
+        DiagnosticKind::InvalidItemCreationPolicy.id() => "Invalid item creation policy '{1}'; expecting 'deferred' or 'immediate'.".into(),
+        DiagnosticKind::FxgRootMustBeGraphic.id() => "The root element of a FXG document must be 'Graphic'.".into(),
+        DiagnosticKind::UnknownFxgElement.id() => "Unknown FXG element '{1}'.".into(),
+        DiagnosticKind::FxgElementNotAllowedHere.id() => "Element '{1}' is not allowed here.".into(),
+        DiagnosticKind::UnknownFxgAttribute.id() => "Unknown attribute '{1}' for FXG element '{2}'.".into(),
+        DiagnosticKind::InvalidFxgAttributeValue.id() => "Invalid value '{1}' for attribute '{2}'; expecting {3}.".into(),
+        DiagnosticKind::InvalidFxgPathData.id() => "Invalid path data.".into(),
+        DiagnosticKind::MxmlLanguageElementMustBeRootChild.id() => "Element '{1}' must be a child of the document root.".into(),
+        DiagnosticKind::MxmlLibraryMustBeFirstChild.id() => "Element '{1}' must be the first child of the document root.".into(),
+        DiagnosticKind::MxmlElementMustHaveSingleChild.id() => "Element '{1}' must contain exactly one child element.".into(),
+        DiagnosticKind::InvalidMxmlClassName.id() => "'{1}' is not a valid class name.".into(),
+        DiagnosticKind::MissingMxmlAttribute.id() => "Element '{1}' requires attribute '{2}'.".into(),
+        DiagnosticKind::UnexpectedMxmlElement.id() => "Element '{1}' is not allowed here.".into(),
+        DiagnosticKind::MxmlSourceAndContentAreMutuallyExclusive.id() => "Element '{1}' cannot specify both a 'source' attribute and content.".into(),
+        DiagnosticKind::MxmlReparentMustSpecifyState.id() => "Element '{1}' must specify either an 'includeIn' or an 'excludeFrom' attribute.".into(),
+        DiagnosticKind::UnknownMediaFeature.id() => "Unknown media feature '{1}'.".into(),
+        DiagnosticKind::InvalidMediaFeatureValue.id() => "Invalid value for media feature '{1}'; expecting {2}.".into(),
+        DiagnosticKind::UnknownStyle.id() => "Style '{1}' is not declared by component '{2}'.".into(),
+        DiagnosticKind::InvalidStyleValue.id() => "Invalid value for style '{1}'; expecting {2}.".into(),
+        DiagnosticKind::StyleValueNotInEnumeration.id() => "Invalid value '{1}' for style '{2}'; expecting one of {3}.".into(),
+        DiagnosticKind::UnresolvedCssClassReference.id() => "Class '{1}' could not be resolved.".into(),
+        DiagnosticKind::CircularCssImport.id() => "Circular import of '{1}'.".into(),
+        DiagnosticKind::AssetNotFound.id() => "Asset '{1}' not found.".into(),
+        DiagnosticKind::MissingEmbedSource.id() => "Embed is missing the 'source' entry.".into(),
+        DiagnosticKind::UnknownEmbedEntry.id() => "Unknown Embed entry '{1}'.".into(),
+        DiagnosticKind::InvalidEmbedEntryValue.id() => "Invalid value for Embed entry '{1}'; expecting {2}.".into(),
+        DiagnosticKind::UnknownImportPackage.id() => "Package '{1}' not found.".into(),
+        DiagnosticKind::UnknownImportDefinition.id() => "Definition '{1}' not found in package '{2}'.".into(),
+        DiagnosticKind::UnusedImport.id() => "Unused import '{1}'.".into(),
+        DiagnosticKind::DuplicateImport.id() => "Duplicate import '{1}'.".into(),
+        DiagnosticKind::ShadowedImport.id() => "Import '{1}' is shadowed by a definition of the same name.".into(),
+        DiagnosticKind::CircularInheritance.id() => "Circular inheritance involving '{1}'.".into(),
+        DiagnosticKind::ExtendingFinalClass.id() => "Cannot extend final class '{1}'.".into(),
+        DiagnosticKind::ClassExpected.id() => "'{1}' is not a class.".into(),
+        DiagnosticKind::InterfaceExpected.id() => "'{1}' is not an interface.".into(),
+        DiagnosticKind::MissingInterfaceMember.id() => "Class '{1}' does not implement {2} '{3}' of interface '{4}'.".into(),
+        DiagnosticKind::MissingOverride.id() => "'{1}' redefines a superclass member and requires the 'override' attribute.".into(),
+        DiagnosticKind::UnnecessaryOverride.id() => "'{1}' does not override a superclass member.".into(),
+        DiagnosticKind::IncompatibleTypes.id() => "Implicit coercion of a value of type '{1}' to an unrelated type '{2}'.".into(),
+        DiagnosticKind::IncorrectArgumentCount.id() => "Incorrect number of arguments; expected {1}.".into(),
+        DiagnosticKind::UndefinedProperty.id() => "Access of possibly undefined property '{1}' through a reference with static type '{2}'.".into(),
+        DiagnosticKind::VoidUsedAsValue.id() => "A 'void' result cannot be used as a value.".into(),
+        DiagnosticKind::DivisionByZero.id() => "Division by zero.".into(),
+        DiagnosticKind::ConstantOverflow.id() => "The constant value overflows the type '{1}'.".into(),
+        DiagnosticKind::NotAConstant.id() => "The expression is not a compile-time constant.".into(),
+        DiagnosticKind::InvalidMxmlNumericValue.id() => "'{1}' is not a valid value for the property '{2}' of type '{3}'.".into(),
+        DiagnosticKind::IncompatibleOverride.id() => "Incompatible override of '{1}', which is a {2} in the superclass.".into(),
+        DiagnosticKind::NullToPrimitive.id() => "'null' converts to the default value of type '{1}'.".into(),
+        DiagnosticKind::UnsupportedCssSelector.id() => "Unsupported selector syntax '{1}'; the selector is ignored.".into(),
         // DiagnosticKind::K.id() => ".".into(),
     };
 }
@@ -0,0 +1,13 @@
+//! Defines generators producing source text and syntactic trees from other trees.
+
+mod as3_nodes;
+mod as3_printer;
+pub use as3_printer::*;
+mod css_serializer;
+pub use css_serializer::*;
+mod css_style_manager_generator;
+pub use css_style_manager_generator::*;
+mod mxml_lowering;
+pub use mxml_lowering::*;
+mod mxml_serializer;
+pub use mxml_serializer::*;
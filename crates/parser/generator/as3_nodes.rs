@@ -0,0 +1,143 @@
+//! Constructors of ActionScript 3 nodes shared by generators.
+
+use crate::ns::*;
+
+pub(crate) fn add_expression(left: Rc<Expression>, right: Rc<Expression>, loc: &Location) -> Rc<Expression> {
+    binary_expression(Operator::Add, left, right, loc)
+}
+
+pub(crate) fn binary_expression(operator: Operator, left: Rc<Expression>, right: Rc<Expression>, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::Binary(BinaryExpression {
+        location: loc.clone(),
+        operator,
+        left,
+        right,
+    }))
+}
+
+pub(crate) fn identifier_expression(name: &str, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::QualifiedIdentifier(QualifiedIdentifier {
+        location: loc.clone(),
+        attribute: false,
+        qualifier: None,
+        id: QualifiedIdentifierIdentifier::Id((name.to_owned(), loc.clone())),
+    }))
+}
+
+/// Returns an expression referring to a fully qualified name,
+/// such as `spark.components.Button`.
+pub(crate) fn qualified_name_expression(name: &str, loc: &Location) -> Rc<Expression> {
+    let mut names = name.split('.');
+    let first = identifier_expression(names.next().unwrap(), loc);
+    names.fold(first, |base, name| member_expression(&base, name, loc))
+}
+
+pub(crate) fn this_expression(loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::ThisLiteral(ThisLiteral {
+        location: loc.clone(),
+    }))
+}
+
+pub(crate) fn member_expression(base: &Rc<Expression>, name: &str, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::Member(MemberExpression {
+        location: loc.clone(),
+        base: base.clone(),
+        identifier: QualifiedIdentifier {
+            location: loc.clone(),
+            attribute: false,
+            qualifier: None,
+            id: QualifiedIdentifierIdentifier::Id((name.to_owned(), loc.clone())),
+        },
+    }))
+}
+
+pub(crate) fn call_expression(base: Rc<Expression>, arguments: Vec<Rc<Expression>>, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::Call(CallExpression {
+        location: loc.clone(),
+        base,
+        arguments,
+    }))
+}
+
+pub(crate) fn assignment_expression(left: Rc<Expression>, right: Rc<Expression>, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::Assignment(AssignmentExpression {
+        location: loc.clone(),
+        compound: None,
+        left,
+        right,
+    }))
+}
+
+pub(crate) fn paren_expression(expression: Rc<Expression>, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::Paren(ParenExpression {
+        location: loc.clone(),
+        expression,
+    }))
+}
+
+pub(crate) fn string_literal(value: &str, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::StringLiteral(StringLiteral {
+        location: loc.clone(),
+        value: value.to_owned(),
+    }))
+}
+
+pub(crate) fn numeric_literal(value: &str, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::NumericLiteral(NumericLiteral {
+        location: loc.clone(),
+        value: value.to_owned(),
+        suffix: NumberSuffix::None,
+    }))
+}
+
+pub(crate) fn expression_statement(expression: Rc<Expression>) -> Rc<Directive> {
+    Rc::new(Directive::ExpressionStatement(ExpressionStatement {
+        location: expression.location(),
+        expression,
+    }))
+}
+
+pub(crate) fn variable_definition(attributes: Vec<Attribute>, name: &str, type_annotation: Option<Rc<Expression>>, initializer: Option<Rc<Expression>>, loc: &Location) -> Rc<Directive> {
+    Rc::new(Directive::VariableDefinition(VariableDefinition {
+        location: loc.clone(),
+        asdoc: None,
+        attributes,
+        kind: (VariableDefinitionKind::Var, loc.clone()),
+        bindings: vec![Rc::new(VariableBinding {
+            destructuring: TypedDestructuring {
+                location: loc.clone(),
+                destructuring: identifier_expression(name, loc),
+                type_annotation,
+            },
+            initializer,
+        })],
+    }))
+}
+
+pub(crate) fn function_common(parameters: Vec<Rc<Parameter>>, result_type: Option<Rc<Expression>>, body: Vec<Rc<Directive>>, loc: &Location) -> Rc<FunctionCommon> {
+    Rc::new(FunctionCommon {
+        location: loc.clone(),
+        contains_yield: false,
+        contains_await: false,
+        signature: FunctionSignature {
+            location: loc.clone(),
+            parameters,
+            result_type,
+        },
+        body: Some(FunctionBody::Block(Rc::new(Block {
+            location: loc.clone(),
+            directives: body,
+        }))),
+    })
+}
+
+pub(crate) fn import_directive(name: &str, loc: &Location) -> Rc<Directive> {
+    let mut names: Vec<(String, Location)> = name.split('.').map(|name| (name.to_owned(), loc.clone())).collect();
+    let last = names.pop().unwrap();
+    Rc::new(Directive::ImportDirective(ImportDirective {
+        location: loc.clone(),
+        alias: None,
+        package_name: names,
+        import_specifier: ImportSpecifier::Identifier(last),
+    }))
+}
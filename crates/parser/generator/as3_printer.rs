@@ -0,0 +1,1023 @@
+use crate::ns::*;
+
+/// Prints ActionScript 3 syntactic trees as source text.
+///
+/// Parentheses are printed only where `ParenExpression` nodes appear
+/// in the tree. ASDoc comments are not printed.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(None, "x  =  y+1".into());
+/// let expression = ParserFacade(&compilation_unit, default()).parse_expression();
+/// assert_eq!(As3Printer::print_expression(&expression), "x = y + 1");
+/// ```
+pub struct As3Printer {
+    output: String,
+    indent: usize,
+}
+
+impl As3Printer {
+    /// The characters used for one level of indentation.
+    pub const INDENT: &'static str = "    ";
+
+    fn new() -> Self {
+        Self {
+            output: String::new(),
+            indent: 0,
+        }
+    }
+
+    /// Prints a program, including its packages.
+    pub fn print_program(program: &Program) -> String {
+        let mut printer = Self::new();
+        for pckg in program.packages.iter() {
+            printer.write_package(pckg);
+        }
+        for directive in program.directives.iter() {
+            printer.write_directive(directive);
+        }
+        printer.output
+    }
+
+    /// Prints a sequence of directives.
+    pub fn print_directives(directives: &[Rc<Directive>]) -> String {
+        let mut printer = Self::new();
+        for directive in directives.iter() {
+            printer.write_directive(directive);
+        }
+        printer.output
+    }
+
+    /// Prints an expression.
+    pub fn print_expression(expression: &Expression) -> String {
+        let mut printer = Self::new();
+        printer.write_expression(expression);
+        printer.output
+    }
+
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn write_indent(&mut self) {
+        for _ in 0..self.indent {
+            self.output.push_str(Self::INDENT);
+        }
+    }
+
+    fn write_line(&mut self, text: &str) {
+        self.write_indent();
+        self.write(text);
+        self.write("\n");
+    }
+
+    fn write_package(&mut self, pckg: &PackageDefinition) {
+        self.write_indent();
+        self.write("package");
+        if !pckg.name.is_empty() {
+            self.write(" ");
+            self.write(&join_names(&pckg.name));
+        }
+        self.write(" ");
+        self.write_block(&pckg.block);
+        self.write("\n");
+    }
+
+    /// Writes a block starting at the current position, leaving
+    /// the cursor after the closing brace.
+    fn write_block(&mut self, block: &Block) {
+        self.write_block_directives(&block.directives);
+    }
+
+    fn write_block_directives(&mut self, directives: &[Rc<Directive>]) {
+        self.write("{\n");
+        self.indent += 1;
+        for directive in directives.iter() {
+            self.write_directive(directive);
+        }
+        self.indent -= 1;
+        self.write_indent();
+        self.write("}");
+    }
+
+    /// Writes a substatement, such as the body of a loop, following
+    /// a header on the current line.
+    fn write_substatement(&mut self, directive: &Directive) {
+        if let Directive::Block(block) = directive {
+            self.write(" ");
+            self.write_block(block);
+            self.write("\n");
+        } else {
+            self.write("\n");
+            self.indent += 1;
+            self.write_directive(directive);
+            self.indent -= 1;
+        }
+    }
+
+    fn write_directive(&mut self, directive: &Directive) {
+        match directive {
+            Directive::EmptyStatement(_) => {
+                self.write_line(";");
+            },
+            Directive::ExpressionStatement(d) => {
+                self.write_indent();
+                self.write_expression(&d.expression);
+                self.write(";\n");
+            },
+            Directive::SuperStatement(d) => {
+                self.write_indent();
+                self.write("super");
+                self.write_arguments(&d.arguments);
+                self.write(";\n");
+            },
+            Directive::Block(d) => {
+                self.write_indent();
+                self.write_block(d);
+                self.write("\n");
+            },
+            Directive::LabeledStatement(d) => {
+                self.write_indent();
+                self.write(&d.label.0);
+                self.write(":");
+                self.write_substatement(&d.substatement);
+            },
+            Directive::IfStatement(d) => {
+                self.write_indent();
+                self.write_if_statement(d);
+            },
+            Directive::SwitchStatement(d) => {
+                self.write_indent();
+                self.write("switch (");
+                self.write_expression(&d.discriminant);
+                self.write(") {\n");
+                self.indent += 1;
+                for case in d.cases.iter() {
+                    for label in case.labels.iter() {
+                        match label {
+                            CaseLabel::Case((exp, _)) => {
+                                self.write_indent();
+                                self.write("case ");
+                                self.write_expression(exp);
+                                self.write(":\n");
+                            },
+                            CaseLabel::Default(_) => {
+                                self.write_line("default:");
+                            },
+                        }
+                    }
+                    self.indent += 1;
+                    for directive in case.directives.iter() {
+                        self.write_directive(directive);
+                    }
+                    self.indent -= 1;
+                }
+                self.indent -= 1;
+                self.write_line("}");
+            },
+            Directive::SwitchTypeStatement(d) => {
+                self.write_indent();
+                self.write("switch type (");
+                self.write_expression(&d.discriminant);
+                self.write(") {\n");
+                self.indent += 1;
+                for case in d.cases.iter() {
+                    self.write_indent();
+                    if let Some(parameter) = &case.parameter {
+                        self.write("case (");
+                        self.write_typed_destructuring(parameter);
+                        self.write(") ");
+                    } else {
+                        self.write("default ");
+                    }
+                    self.write_block(&case.block);
+                    self.write("\n");
+                }
+                self.indent -= 1;
+                self.write_line("}");
+            },
+            Directive::DoStatement(d) => {
+                self.write_indent();
+                self.write("do");
+                if let Directive::Block(block) = d.body.as_ref() {
+                    self.write(" ");
+                    self.write_block(block);
+                    self.write(" ");
+                } else {
+                    self.write_substatement(&d.body);
+                    self.write_indent();
+                }
+                self.write("while (");
+                self.write_expression(&d.test);
+                self.write(");\n");
+            },
+            Directive::WhileStatement(d) => {
+                self.write_indent();
+                self.write("while (");
+                self.write_expression(&d.test);
+                self.write(")");
+                self.write_substatement(&d.body);
+            },
+            Directive::ForStatement(d) => {
+                self.write_indent();
+                self.write("for (");
+                match &d.init {
+                    Some(ForInitializer::Expression(exp)) => self.write_expression(exp),
+                    Some(ForInitializer::VariableDefinition(defn)) => self.write_simple_variable_definition(defn),
+                    None => {},
+                }
+                self.write(";");
+                if let Some(test) = &d.test {
+                    self.write(" ");
+                    self.write_expression(test);
+                }
+                self.write(";");
+                if let Some(update) = &d.update {
+                    self.write(" ");
+                    self.write_expression(update);
+                }
+                self.write(")");
+                self.write_substatement(&d.body);
+            },
+            Directive::ForInStatement(d) => {
+                self.write_indent();
+                self.write(if d.each { "for each (" } else { "for (" });
+                match &d.left {
+                    ForInBinding::Expression(exp) => self.write_expression(exp),
+                    ForInBinding::VariableDefinition(defn) => self.write_simple_variable_definition(defn),
+                }
+                self.write(" in ");
+                self.write_expression(&d.right);
+                self.write(")");
+                self.write_substatement(&d.body);
+            },
+            Directive::BreakStatement(d) => {
+                self.write_indent();
+                self.write("break");
+                if let Some((label, _)) = &d.label {
+                    self.write(" ");
+                    self.write(label);
+                }
+                self.write(";\n");
+            },
+            Directive::ContinueStatement(d) => {
+                self.write_indent();
+                self.write("continue");
+                if let Some((label, _)) = &d.label {
+                    self.write(" ");
+                    self.write(label);
+                }
+                self.write(";\n");
+            },
+            Directive::WithStatement(d) => {
+                self.write_indent();
+                self.write("with (");
+                self.write_expression(&d.object);
+                self.write(")");
+                self.write_substatement(&d.body);
+            },
+            Directive::ReturnStatement(d) => {
+                self.write_indent();
+                self.write("return");
+                if let Some(exp) = &d.expression {
+                    self.write(" ");
+                    self.write_expression(exp);
+                }
+                self.write(";\n");
+            },
+            Directive::ThrowStatement(d) => {
+                self.write_indent();
+                self.write("throw ");
+                self.write_expression(&d.expression);
+                self.write(";\n");
+            },
+            Directive::DefaultXmlNamespaceStatement(d) => {
+                self.write_indent();
+                self.write("default xml namespace = ");
+                self.write_expression(&d.right);
+                self.write(";\n");
+            },
+            Directive::TryStatement(d) => {
+                self.write_indent();
+                self.write("try ");
+                self.write_block(&d.block);
+                for catch_clause in d.catch_clauses.iter() {
+                    self.write(" catch (");
+                    self.write_typed_destructuring(&catch_clause.parameter);
+                    self.write(") ");
+                    self.write_block(&catch_clause.block);
+                }
+                if let Some(finally_clause) = &d.finally_clause {
+                    self.write(" finally ");
+                    self.write_block(&finally_clause.block);
+                }
+                self.write("\n");
+            },
+            Directive::Invalidated(_) => {},
+            Directive::ConfigurationDirective(d) => {
+                self.write_indent();
+                self.write("configuration ");
+                if let Directive::Block(block) = d.directive.as_ref() {
+                    self.write_block(block);
+                } else {
+                    self.write_block_directives(std::slice::from_ref(&d.directive));
+                }
+                self.write("\n");
+            },
+            Directive::ImportDirective(d) => {
+                self.write_indent();
+                self.write("import ");
+                if let Some((alias, _)) = &d.alias {
+                    self.write(alias);
+                    self.write(" = ");
+                }
+                self.write_import_name(&d.package_name, &d.import_specifier);
+                self.write(";\n");
+            },
+            Directive::UseNamespaceDirective(d) => {
+                self.write_indent();
+                self.write("use namespace ");
+                self.write_expression(&d.expression);
+                self.write(";\n");
+            },
+            Directive::IncludeDirective(d) => {
+                self.write_indent();
+                self.write("include ");
+                self.write(&quote_string(&d.source));
+                self.write(";\n");
+            },
+            Directive::NormalConfigurationDirective(d) => {
+                self.write_indent();
+                self.write(&d.namespace.0);
+                self.write("::");
+                self.write(&d.constant_name.0);
+                if let Directive::Block(block) = d.directive.as_ref() {
+                    self.write(" ");
+                    self.write_block(block);
+                    self.write("\n");
+                } else {
+                    self.write("\n");
+                    self.write_directive(&d.directive);
+                }
+            },
+            Directive::PackageConcatDirective(d) => {
+                self.write_indent();
+                self.write("public += ");
+                self.write_import_name(&d.package_name, &d.import_specifier);
+                self.write(";\n");
+            },
+            Directive::DirectiveInjection(d) => {
+                for directive in d.directives.borrow().iter() {
+                    self.write_directive(directive);
+                }
+            },
+            Directive::VariableDefinition(d) => {
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write_variable_definition(d.kind.0, &d.bindings);
+                self.write(";\n");
+            },
+            Directive::FunctionDefinition(d) => {
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write("function ");
+                match &d.name {
+                    FunctionName::Getter((name, _)) => {
+                        self.write("get ");
+                        self.write(name);
+                    },
+                    FunctionName::Setter((name, _)) => {
+                        self.write("set ");
+                        self.write(name);
+                    },
+                    FunctionName::Identifier((name, _)) |
+                    FunctionName::Constructor((name, _)) => {
+                        self.write(name);
+                    },
+                }
+                self.write_function_common(&d.common);
+                if !d.common.has_block_body() {
+                    self.write(";");
+                }
+                self.write("\n");
+            },
+            Directive::ClassDefinition(d) => {
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write("class ");
+                self.write(&d.name.0);
+                self.write_type_parameters(&d.type_parameters);
+                if let Some(extends_clause) = &d.extends_clause {
+                    self.write(" extends ");
+                    self.write_expression(extends_clause);
+                }
+                if let Some(implements_clause) = &d.implements_clause {
+                    self.write(" implements ");
+                    self.write_expression_list(implements_clause);
+                }
+                self.write(" ");
+                self.write_block(&d.block);
+                self.write("\n");
+            },
+            Directive::EnumDefinition(d) => {
+                if d.is_set {
+                    self.write_line("[Set]");
+                }
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write("enum ");
+                self.write(&d.name.0);
+                if let Some(as_clause) = &d.as_clause {
+                    self.write(" as ");
+                    self.write_expression(as_clause);
+                }
+                self.write(" ");
+                self.write_block(&d.block);
+                self.write("\n");
+            },
+            Directive::InterfaceDefinition(d) => {
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write("interface ");
+                self.write(&d.name.0);
+                self.write_type_parameters(&d.type_parameters);
+                if let Some(extends_clause) = &d.extends_clause {
+                    self.write(" extends ");
+                    self.write_expression_list(extends_clause);
+                }
+                self.write(" ");
+                self.write_block(&d.block);
+                self.write("\n");
+            },
+            Directive::TypeDefinition(d) => {
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write("type ");
+                self.write(&d.left.0);
+                self.write(" = ");
+                self.write_expression(&d.right);
+                self.write(";\n");
+            },
+            Directive::NamespaceDefinition(d) => {
+                self.write_attributes(&d.attributes);
+                self.write_indent();
+                self.write_modifiers(&d.attributes);
+                self.write("namespace ");
+                self.write(&d.left.0);
+                if let Some(right) = &d.right {
+                    self.write(" = ");
+                    self.write_expression(right);
+                }
+                self.write(";\n");
+            },
+        }
+    }
+
+    fn write_if_statement(&mut self, d: &IfStatement) {
+        self.write("if (");
+        self.write_expression(&d.test);
+        self.write(")");
+        self.write_substatement(&d.consequent);
+        if let Some(alternative) = &d.alternative {
+            self.write_indent();
+            self.write("else");
+            if let Directive::IfStatement(alternative) = alternative.as_ref() {
+                self.write(" ");
+                self.write_if_statement(alternative);
+            } else {
+                self.write_substatement(alternative);
+            }
+        }
+    }
+
+    fn write_import_name(&mut self, package_name: &[(String, Location)], import_specifier: &ImportSpecifier) {
+        for (name, _) in package_name.iter() {
+            self.write(name);
+            self.write(".");
+        }
+        match import_specifier {
+            ImportSpecifier::Wildcard(_) => self.write("*"),
+            ImportSpecifier::Recursive(_) => self.write("**"),
+            ImportSpecifier::Identifier((name, _)) => self.write(name),
+        }
+    }
+
+    /// Writes the meta-data attributes, each on its own line.
+    fn write_attributes(&mut self, attributes: &[Attribute]) {
+        for metadata in Attribute::find_metadata(attributes) {
+            self.write_indent();
+            self.write_metadata(&metadata);
+            self.write("\n");
+        }
+    }
+
+    fn write_metadata(&mut self, metadata: &Metadata) {
+        self.write("[");
+        self.write(&metadata.name.0);
+        if let Some(entries) = &metadata.entries {
+            self.write("(");
+            for (i, entry) in entries.iter().enumerate() {
+                if i != 0 {
+                    self.write(", ");
+                }
+                if let Some((key, _)) = &entry.key {
+                    self.write(key);
+                    self.write("=");
+                }
+                match entry.value.as_ref() {
+                    MetadataValue::IdentifierString((value, _)) => self.write(value),
+                    MetadataValue::String((value, _)) => self.write(&quote_string(value)),
+                }
+            }
+            self.write(")");
+        }
+        self.write("]");
+    }
+
+    /// Writes the non meta-data attributes, each followed by a space.
+    fn write_modifiers(&mut self, attributes: &[Attribute]) {
+        for attribute in attributes.iter() {
+            match attribute {
+                Attribute::Metadata(_) => continue,
+                Attribute::Expression(exp) => self.write_expression(exp),
+                Attribute::Public(_) => self.write("public"),
+                Attribute::Private(_) => self.write("private"),
+                Attribute::Protected(_) => self.write("protected"),
+                Attribute::Internal(_) => self.write("internal"),
+                Attribute::Final(_) => self.write("final"),
+                Attribute::Native(_) => self.write("native"),
+                Attribute::Static(_) => self.write("static"),
+                Attribute::Abstract(_) => self.write("abstract"),
+                Attribute::Override(_) => self.write("override"),
+                Attribute::Dynamic(_) => self.write("dynamic"),
+            }
+            self.write(" ");
+        }
+    }
+
+    fn write_type_parameters(&mut self, type_parameters: &Option<Vec<Rc<TypeParameter>>>) {
+        if let Some(type_parameters) = type_parameters {
+            self.write(".<");
+            for (i, type_parameter) in type_parameters.iter().enumerate() {
+                if i != 0 {
+                    self.write(", ");
+                }
+                self.write(&type_parameter.name.0);
+            }
+            self.write(">");
+        }
+    }
+
+    fn write_simple_variable_definition(&mut self, defn: &SimpleVariableDefinition) {
+        self.write_variable_definition(defn.kind.0, &defn.bindings);
+    }
+
+    fn write_variable_definition(&mut self, kind: VariableDefinitionKind, bindings: &[Rc<VariableBinding>]) {
+        self.write(match kind {
+            VariableDefinitionKind::Var => "var ",
+            VariableDefinitionKind::Const => "const ",
+        });
+        for (i, binding) in bindings.iter().enumerate() {
+            if i != 0 {
+                self.write(", ");
+            }
+            self.write_typed_destructuring(&binding.destructuring);
+            if let Some(initializer) = &binding.initializer {
+                self.write(" = ");
+                self.write_expression(initializer);
+            }
+        }
+    }
+
+    fn write_typed_destructuring(&mut self, destructuring: &TypedDestructuring) {
+        self.write_expression(&destructuring.destructuring);
+        if let Some(type_annotation) = &destructuring.type_annotation {
+            self.write(": ");
+            self.write_expression(type_annotation);
+        }
+    }
+
+    fn write_function_common(&mut self, common: &FunctionCommon) {
+        self.write("(");
+        for (i, parameter) in common.signature.parameters.iter().enumerate() {
+            if i != 0 {
+                self.write(", ");
+            }
+            if parameter.kind == ParameterKind::Rest {
+                self.write("...");
+            }
+            self.write_typed_destructuring(&parameter.destructuring);
+            if let Some(default_value) = &parameter.default_value {
+                self.write(" = ");
+                self.write_expression(default_value);
+            }
+        }
+        self.write(")");
+        if let Some(result_type) = &common.signature.result_type {
+            self.write(": ");
+            self.write_expression(result_type);
+        }
+        match &common.body {
+            Some(FunctionBody::Block(block)) => {
+                self.write(" ");
+                self.write_block(block);
+            },
+            Some(FunctionBody::Expression(exp)) => {
+                self.write(" ");
+                self.write_expression(exp);
+            },
+            None => {},
+        }
+    }
+
+    fn write_arguments(&mut self, arguments: &[Rc<Expression>]) {
+        self.write("(");
+        self.write_expression_list(arguments);
+        self.write(")");
+    }
+
+    fn write_expression_list(&mut self, expressions: &[Rc<Expression>]) {
+        for (i, exp) in expressions.iter().enumerate() {
+            if i != 0 {
+                self.write(", ");
+            }
+            self.write_expression(exp);
+        }
+    }
+
+    fn write_elements(&mut self, elements: &[Element]) {
+        self.write("[");
+        for (i, element) in elements.iter().enumerate() {
+            if i != 0 {
+                self.write(", ");
+            }
+            match element {
+                Element::Elision => {},
+                Element::Expression(exp) => self.write_expression(exp),
+                Element::Rest((exp, _)) => {
+                    self.write("...");
+                    self.write_expression(exp);
+                },
+            }
+        }
+        // A trailing elision requires an additional comma
+        if matches!(elements.last(), Some(Element::Elision)) {
+            self.write(",");
+        }
+        self.write("]");
+    }
+
+    fn write_qualified_identifier(&mut self, id: &QualifiedIdentifier) {
+        if id.attribute {
+            self.write("@");
+        }
+        if let Some(qualifier) = &id.qualifier {
+            self.write_expression(qualifier);
+            self.write("::");
+        }
+        match &id.id {
+            QualifiedIdentifierIdentifier::Id((name, _)) => self.write(name),
+            QualifiedIdentifierIdentifier::Brackets(exp) => {
+                self.write("[");
+                self.write_expression(exp);
+                self.write("]");
+            },
+        }
+    }
+
+    /// Writes the base of a postfix operation, or the optional
+    /// chaining operator if the base is a placeholder.
+    fn write_postfix_base(&mut self, base: &Expression, separator: &str) {
+        if matches!(base, Expression::OptionalChainingPlaceholder(_)) {
+            self.write("?.");
+        } else {
+            self.write_expression(base);
+            self.write(separator);
+        }
+    }
+
+    fn write_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::QualifiedIdentifier(e) => self.write_qualified_identifier(e),
+            Expression::Paren(e) => {
+                self.write("(");
+                self.write_expression(&e.expression);
+                self.write(")");
+            },
+            Expression::NullLiteral(_) => self.write("null"),
+            Expression::BooleanLiteral(e) => self.write(if e.value { "true" } else { "false" }),
+            Expression::NumericLiteral(e) => {
+                self.write(&e.value);
+                if e.suffix == NumberSuffix::F {
+                    self.write("f");
+                }
+            },
+            Expression::StringLiteral(e) => self.write(&quote_string(&e.value)),
+            Expression::ThisLiteral(_) => self.write("this"),
+            Expression::RegExpLiteral(e) => {
+                self.write("/");
+                self.write(&e.body);
+                self.write("/");
+                self.write(&e.flags);
+            },
+            Expression::Xml(e) => self.write_xml_element(&e.element),
+            Expression::XmlMarkup(e) => self.write(&e.markup),
+            Expression::XmlList(e) => {
+                self.write("<>");
+                self.write_xml_content(&e.content);
+                self.write("</>");
+            },
+            Expression::ArrayLiteral(e) => self.write_elements(&e.elements),
+            Expression::VectorLiteral(e) => {
+                self.write("new <");
+                self.write_expression(&e.element_type);
+                self.write(">");
+                self.write_elements(&e.elements);
+            },
+            Expression::ObjectInitializer(e) => {
+                if e.fields.is_empty() {
+                    self.write("{}");
+                    return;
+                }
+                self.write("{ ");
+                for (i, field) in e.fields.iter().enumerate() {
+                    if i != 0 {
+                        self.write(", ");
+                    }
+                    match field.as_ref() {
+                        InitializerField::Field { name, non_null, value } => {
+                            match &name.0 {
+                                FieldName::Identifier(id) => self.write_qualified_identifier(id),
+                                FieldName::Brackets(exp) => {
+                                    self.write("[");
+                                    self.write_expression(exp);
+                                    self.write("]");
+                                },
+                                FieldName::StringLiteral(exp) |
+                                FieldName::NumericLiteral(exp) => self.write_expression(exp),
+                            }
+                            if *non_null {
+                                self.write("!");
+                            }
+                            if let Some(value) = value {
+                                self.write(": ");
+                                self.write_expression(value);
+                            }
+                        },
+                        InitializerField::Rest((exp, _)) => {
+                            self.write("...");
+                            self.write_expression(exp);
+                        },
+                    }
+                }
+                self.write(" }");
+            },
+            Expression::Function(e) => {
+                self.write("function");
+                if let Some((name, _)) = &e.name {
+                    self.write(" ");
+                    self.write(name);
+                }
+                self.write_function_common(&e.common);
+            },
+            Expression::ImportMeta(_) => self.write("import.meta"),
+            Expression::New(e) => {
+                self.write("new ");
+                self.write_expression(&e.base);
+                if let Some(arguments) = &e.arguments {
+                    self.write_arguments(arguments);
+                }
+            },
+            Expression::Member(e) => {
+                self.write_postfix_base(&e.base, ".");
+                self.write_qualified_identifier(&e.identifier);
+            },
+            Expression::ComputedMember(e) => {
+                self.write_postfix_base(&e.base, "");
+                self.write("[");
+                self.write_expression(&e.key);
+                self.write("]");
+            },
+            Expression::Descendants(e) => {
+                self.write_postfix_base(&e.base, "");
+                self.write("..");
+                self.write_qualified_identifier(&e.identifier);
+            },
+            Expression::Filter(e) => {
+                self.write_postfix_base(&e.base, ".");
+                self.write("(");
+                self.write_expression(&e.test);
+                self.write(")");
+            },
+            Expression::Super(e) => {
+                self.write("super");
+                if let Some(object) = &e.object {
+                    self.write_arguments(object);
+                }
+            },
+            Expression::Call(e) => {
+                self.write_postfix_base(&e.base, "");
+                self.write_arguments(&e.arguments);
+            },
+            Expression::WithTypeArguments(e) => {
+                self.write_postfix_base(&e.base, "");
+                self.write(".<");
+                self.write_expression_list(&e.arguments);
+                self.write(">");
+            },
+            Expression::Unary(e) => {
+                if e.operator.is_postfix() {
+                    self.write_expression(&e.expression);
+                    self.write(&e.operator.to_string());
+                } else {
+                    let operator = e.operator.to_string();
+                    self.write(&operator);
+                    if operator.chars().all(|ch| ch.is_alphabetic()) {
+                        self.write(" ");
+                    }
+                    self.write_expression(&e.expression);
+                }
+            },
+            Expression::OptionalChaining(e) => {
+                self.write_expression(&e.base);
+                self.write_expression(&e.expression);
+            },
+            Expression::OptionalChainingPlaceholder(_) => {},
+            Expression::Binary(e) => {
+                self.write_expression(&e.left);
+                self.write(" ");
+                self.write(&e.operator.to_string());
+                self.write(" ");
+                self.write_expression(&e.right);
+            },
+            Expression::Conditional(e) => {
+                self.write_expression(&e.test);
+                self.write(" ? ");
+                self.write_expression(&e.consequent);
+                self.write(" : ");
+                self.write_expression(&e.alternative);
+            },
+            Expression::Assignment(e) => {
+                self.write_expression(&e.left);
+                self.write(" ");
+                if let Some(compound) = e.compound {
+                    self.write(&compound.to_string());
+                }
+                self.write("= ");
+                self.write_expression(&e.right);
+            },
+            Expression::Sequence(e) => {
+                self.write_expression(&e.left);
+                self.write(", ");
+                self.write_expression(&e.right);
+            },
+            Expression::NullableType(e) => {
+                self.write_expression(&e.base);
+                self.write("?");
+            },
+            Expression::NonNullableType(e) => {
+                self.write_expression(&e.base);
+                self.write("!");
+            },
+            Expression::AnyType(_) => self.write("*"),
+            Expression::VoidType(_) => self.write("void"),
+            Expression::ArrayType(e) => {
+                self.write("[");
+                self.write_expression(&e.expression);
+                self.write("]");
+            },
+            Expression::TupleType(e) => {
+                self.write("[");
+                self.write_expression_list(&e.expressions);
+                self.write("]");
+            },
+            Expression::FunctionType(e) => {
+                self.write("function(");
+                for (i, parameter) in e.parameters.iter().enumerate() {
+                    if i != 0 {
+                        self.write(", ");
+                    }
+                    match parameter.kind {
+                        ParameterKind::Rest => {
+                            self.write("...");
+                            if let Some(type_expression) = &parameter.type_expression {
+                                self.write_expression(type_expression);
+                            }
+                        },
+                        ParameterKind::Optional => {
+                            self.write_expression(parameter.type_expression.as_ref().unwrap());
+                            self.write("=");
+                        },
+                        ParameterKind::Required => {
+                            self.write_expression(parameter.type_expression.as_ref().unwrap());
+                        },
+                    }
+                }
+                self.write(")");
+                if let Some(result_type) = &e.result_type {
+                    self.write(": ");
+                    self.write_expression(result_type);
+                }
+            },
+            Expression::Invalidated(_) => {},
+            Expression::ReservedNamespace(e) => self.write(&e.to_string()),
+        }
+    }
+
+    fn write_xml_element(&mut self, element: &XmlElement) {
+        self.write("<");
+        self.write_xml_tag_name(&element.name);
+        for attribute in element.attributes.iter() {
+            self.write(" ");
+            self.write(&attribute.name.0);
+            self.write("=");
+            match &attribute.value {
+                XmlAttributeValue::Value((value, _)) => {
+                    self.write("\"");
+                    self.write(&escape_xml(value));
+                    self.write("\"");
+                },
+                XmlAttributeValue::Expression(exp) => {
+                    self.write("{");
+                    self.write_expression(exp);
+                    self.write("}");
+                },
+            }
+        }
+        if let Some(exp) = &element.attribute_expression {
+            self.write(" {");
+            self.write_expression(exp);
+            self.write("}");
+        }
+        let Some(content) = &element.content else {
+            self.write("/>");
+            return;
+        };
+        self.write(">");
+        self.write_xml_content(content);
+        self.write("</");
+        self.write_xml_tag_name(element.closing_name.as_ref().unwrap_or(&element.name));
+        self.write(">");
+    }
+
+    fn write_xml_tag_name(&mut self, name: &XmlTagName) {
+        match name {
+            XmlTagName::Name((name, _)) => self.write(name),
+            XmlTagName::Expression(exp) => {
+                self.write("{");
+                self.write_expression(exp);
+                self.write("}");
+            },
+        }
+    }
+
+    fn write_xml_content(&mut self, content: &[Rc<XmlContent>]) {
+        for node in content.iter() {
+            match node.as_ref() {
+                XmlContent::Characters((text, _)) => self.write(text),
+                XmlContent::Markup((markup, _)) => self.write(markup),
+                XmlContent::Element(element) => self.write_xml_element(element),
+                XmlContent::Expression(exp) => {
+                    self.write("{");
+                    self.write_expression(exp);
+                    self.write("}");
+                },
+            }
+        }
+    }
+}
+
+fn join_names(names: &[(String, Location)]) -> String {
+    names.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>().join(".")
+}
+
+/// Quotes a string using double quotes, escaping
+/// characters where necessary.
+fn quote_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    for ch in value.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{c}' => output.push_str("\\f"),
+            '\u{b}' => output.push_str("\\v"),
+            '\0' => output.push_str("\\x00"),
+            ch => output.push(ch),
+        }
+    }
+    output.push('"');
+    output
+}
@@ -0,0 +1,619 @@
+use crate::ns::*;
+
+/// Serializes CSS trees as source text.
+///
+/// In the formatting mode, given by `CssFormatOptions`, every property is
+/// written on its own line and directives are separated by blank lines.
+/// Colors are written as in the source.
+///
+/// In the minified mode, redundant whitespace is dropped, colors are
+/// written in their shortest form, and rules are merged as follows:
+///
+/// - A rule identical to a later rule, in both selectors and properties,
+///   is dropped.
+/// - Consecutive rules with identical properties are merged into
+///   a single rule with multiple selectors.
+///
+/// Property names are written in their source form, such as `font-size`,
+/// rather than in their normalized form.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let source = "s|Button { font-size: 12; color: #FF0000 }\n.primary { color: red; font-size: 12 }";
+/// let compilation_unit = CompilationUnit::new(None, source.into());
+/// let document = CssParserFacade(&compilation_unit, default()).parse_document();
+/// assert_eq!(CssSerializer::format(&document, &default()), "s|Button {\n    font-size: 12;\n    color: #FF0000;\n}\n\n.primary {\n    color: red;\n    font-size: 12;\n}\n");
+/// assert_eq!(CssSerializer::minify(&document), "s|Button{font-size:12;color:#f00}.primary{color:red;font-size:12}");
+/// ```
+pub struct CssSerializer<'a> {
+    output: String,
+    format: Option<&'a CssFormatOptions>,
+    indent: usize,
+}
+
+/// Options for formatting CSS.
+pub struct CssFormatOptions {
+    /// The characters used for one level of indentation. Default: four spaces.
+    pub indent: String,
+}
+
+impl Default for CssFormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: "    ".into(),
+        }
+    }
+}
+
+impl<'a> CssSerializer<'a> {
+    /// Formats a CSS document.
+    pub fn format(document: &CssDocument, options: &CssFormatOptions) -> String {
+        let mut serializer = CssSerializer { output: String::new(), format: Some(options), indent: 0 };
+        serializer.write_directives(&document.directives);
+        serializer.output
+    }
+
+    /// Formats a CSS directive.
+    pub fn format_directive(directive: &CssDirective, options: &CssFormatOptions) -> String {
+        let mut serializer = CssSerializer { output: String::new(), format: Some(options), indent: 0 };
+        serializer.write_directive(directive);
+        serializer.output
+    }
+
+    /// Minifies a CSS document.
+    pub fn minify(document: &CssDocument) -> String {
+        let mut serializer = CssSerializer { output: String::new(), format: None, indent: 0 };
+        serializer.write_directives(&document.directives);
+        serializer.output
+    }
+
+    /// Minifies a CSS directive.
+    pub fn minify_directive(directive: &CssDirective) -> String {
+        let mut serializer = CssSerializer { output: String::new(), format: None, indent: 0 };
+        serializer.write_directive(directive);
+        serializer.output
+    }
+
+    /// Writes a property value as in the formatting mode.
+    pub fn format_property_value(value: &CssPropertyValue) -> String {
+        let options = CssFormatOptions::default();
+        let mut serializer = CssSerializer { output: String::new(), format: Some(&options), indent: 0 };
+        serializer.write_property_value(value);
+        serializer.output
+    }
+
+    /// Writes a selector as in the formatting mode.
+    pub fn format_selector(selector: &CssSelector) -> String {
+        let options = CssFormatOptions::default();
+        let mut serializer = CssSerializer { output: String::new(), format: Some(&options), indent: 0 };
+        serializer.write_selector(selector);
+        serializer.output
+    }
+
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn write_indent(&mut self) {
+        if let Some(format) = self.format {
+            for _ in 0..self.indent {
+                self.output.push_str(&format.indent);
+            }
+        }
+    }
+
+    fn write_line_break(&mut self) {
+        if self.format.is_some() {
+            self.output.push('\n');
+        }
+    }
+
+    /// Writes a space in the formatting mode.
+    fn write_space(&mut self) {
+        if self.format.is_some() {
+            self.output.push(' ');
+        }
+    }
+
+    fn write_directives(&mut self, directives: &[Rc<CssDirective>]) {
+        let directives: Vec<Rc<CssDirective>> = directives.iter()
+            .filter(|directive| !matches!(directive.as_ref(), CssDirective::Invalidated(_)))
+            .cloned()
+            .collect();
+        if self.format.is_some() {
+            for (i, directive) in directives.iter().enumerate() {
+                if i != 0 {
+                    self.write_line_break();
+                }
+                self.write_directive(directive);
+            }
+            return;
+        }
+
+        // Merge rules in the minified mode, rules being consecutive
+        // runs of directives.
+        let mut i = 0;
+        while i < directives.len() {
+            let CssDirective::Rule(_) = directives[i].as_ref() else {
+                self.write_directive(&directives[i]);
+                i += 1;
+                continue;
+            };
+            let mut rules = vec![];
+            while let Some(CssDirective::Rule(rule)) = directives.get(i).map(|d| d.as_ref()) {
+                rules.push(rule.clone());
+                i += 1;
+            }
+            self.write_rules(&rules);
+        }
+    }
+
+    fn write_rules(&mut self, rules: &[CssRule]) {
+        let rules: Vec<(Vec<String>, String)> = rules.iter().map(|rule| {
+            let selectors = rule.selectors.iter().map(|selector| {
+                let mut serializer = CssSerializer { output: String::new(), format: None, indent: 0 };
+                serializer.write_selector(selector);
+                serializer.output
+            }).filter(|selector| !selector.is_empty()).collect();
+            let mut serializer = CssSerializer { output: String::new(), format: None, indent: 0 };
+            serializer.write_properties(&rule.properties);
+            (selectors, serializer.output)
+        }).collect();
+
+        // Drop rules identical to a later rule
+        let rules: Vec<&(Vec<String>, String)> = rules.iter().enumerate()
+            .filter(|(i, rule)| !rules[(i + 1)..].contains(rule))
+            .map(|(_, rule)| rule)
+            .collect();
+
+        // Merge consecutive rules with identical properties
+        let mut merged: Vec<(Vec<String>, String)> = vec![];
+        for (selectors, properties) in rules {
+            if let Some(last) = merged.last_mut().filter(|last| last.1 == *properties) {
+                for selector in selectors {
+                    if !last.0.contains(selector) {
+                        last.0.push(selector.clone());
+                    }
+                }
+                continue;
+            }
+            merged.push((selectors.clone(), properties.clone()));
+        }
+
+        for (selectors, properties) in merged {
+            self.write(&selectors.join(","));
+            self.write(&properties);
+        }
+    }
+
+    fn write_directive(&mut self, directive: &CssDirective) {
+        match directive {
+            CssDirective::Invalidated(_) => {},
+            CssDirective::Rule(rule) => {
+                self.write_indent();
+                self.write_rule(rule);
+            },
+            CssDirective::NamespaceDefinition(defn) => {
+                self.write_indent();
+                self.write("@namespace ");
+                if !defn.prefix.0.is_empty() {
+                    self.write(&defn.prefix.0);
+                    self.write(" ");
+                }
+                self.write(&quote_string(&defn.uri.0));
+                self.write(";");
+                self.write_line_break();
+            },
+            CssDirective::Import(import) => {
+                self.write_indent();
+                self.write("@import url(");
+                self.write(&quote_string(&import.url.0));
+                self.write(")");
+                for (i, condition) in import.media.iter().enumerate() {
+                    self.write(if i == 0 { " " } else { "," });
+                    if i != 0 {
+                        self.write_space();
+                    }
+                    self.write_media_query_condition(condition);
+                }
+                self.write(";");
+                self.write_line_break();
+            },
+            CssDirective::FontFace(font_face) => {
+                self.write_indent();
+                self.write("@font-face");
+                self.write_properties(&font_face.properties);
+                self.write_line_break();
+            },
+            CssDirective::MediaQuery(media_query) => {
+                self.write_indent();
+                self.write("@media ");
+                for (i, condition) in media_query.conditions.iter().enumerate() {
+                    if i != 0 {
+                        self.write(",");
+                        self.write_space();
+                    }
+                    self.write_media_query_condition(condition);
+                }
+                self.write_space();
+                self.write("{");
+                self.write_line_break();
+                self.indent += 1;
+                if self.format.is_some() {
+                    for (i, rule) in media_query.rules.iter().enumerate() {
+                        if i != 0 {
+                            self.write_line_break();
+                        }
+                        self.write_indent();
+                        self.write_rule(rule);
+                    }
+                } else {
+                    let rules: Vec<CssRule> = media_query.rules.iter().map(|rule| (**rule).clone()).collect();
+                    self.write_rules(&rules);
+                }
+                self.indent -= 1;
+                self.write_indent();
+                self.write("}");
+                self.write_line_break();
+            },
+        }
+    }
+
+    fn write_rule(&mut self, rule: &CssRule) {
+        let mut first = true;
+        for selector in rule.selectors.iter() {
+            if matches!(selector.as_ref(), CssSelector::Invalidated(_)) {
+                continue;
+            }
+            if !first {
+                self.write(",");
+                self.write_space();
+            }
+            self.write_selector(selector);
+            first = false;
+        }
+        self.write_properties(&rule.properties);
+        self.write_line_break();
+    }
+
+    /// Writes a declaration block, including braces.
+    fn write_properties(&mut self, properties: &[Rc<CssProperty>]) {
+        self.write_space();
+        self.write("{");
+        self.write_line_break();
+        self.indent += 1;
+        let properties: Vec<&Rc<CssProperty>> = properties.iter()
+            .filter(|property| !matches!(property.value.as_ref(), CssPropertyValue::Invalidated(_)))
+            .collect();
+        for (i, property) in properties.iter().enumerate() {
+            self.write_indent();
+            self.write_property(property);
+            // The last semicolon is dropped in the minified mode
+            if self.format.is_some() || i + 1 != properties.len() {
+                self.write(";");
+            }
+            self.write_line_break();
+        }
+        self.indent -= 1;
+        self.write_indent();
+        self.write("}");
+    }
+
+    fn write_property(&mut self, property: &CssProperty) {
+        self.write(&property_source_name(property));
+        self.write(":");
+        self.write_space();
+        self.write_property_value(&property.value);
+    }
+
+    fn write_property_value(&mut self, value: &CssPropertyValue) {
+        match value {
+            CssPropertyValue::Invalidated(_) => {},
+            CssPropertyValue::Array(array) => {
+                for (i, element) in array.elements.iter().enumerate() {
+                    if i != 0 {
+                        self.write(",");
+                        self.write_space();
+                    }
+                    self.write_property_value(element);
+                }
+            },
+            CssPropertyValue::MultiValue(multi_value) => {
+                for (i, value) in multi_value.values.iter().enumerate() {
+                    if i != 0 {
+                        self.write(" ");
+                    }
+                    self.write_property_value(value);
+                }
+            },
+            CssPropertyValue::Color(color) => {
+                let text = color.text();
+                if self.format.is_some() && !text.is_empty() {
+                    self.write(&text);
+                } else {
+                    let hex = shortest_hex_color(color.color());
+                    // Color names, such as "red", may be shorter
+                    if !text.is_empty() && !text.starts_with('#') && text.len() < hex.len() {
+                        self.write(&text);
+                    } else {
+                        self.write(&hex);
+                    }
+                }
+            },
+            CssPropertyValue::RgbColor(color) => {
+                let text = color.location.text();
+                if self.format.is_some() && !text.is_empty() {
+                    self.write(&text);
+                } else {
+                    self.write(&shortest_hex_color(color.color()));
+                }
+            },
+            CssPropertyValue::Number(number) => {
+                let mut value = number.value.to_string();
+                if self.format.is_none() {
+                    if let Some(rest) = value.strip_prefix("0.") {
+                        value = format!(".{rest}");
+                    } else if let Some(rest) = value.strip_prefix("-0.") {
+                        value = format!("-.{rest}");
+                    }
+                }
+                self.write(&value);
+                if let Some(unit) = &number.unit {
+                    self.write(unit);
+                }
+            },
+            CssPropertyValue::String(string) => {
+                self.write(&quote_string(&string.value));
+            },
+            CssPropertyValue::Identifier(identifier) => {
+                self.write(&identifier.value);
+            },
+            CssPropertyValue::ClassReference(reference) => {
+                self.write("ClassReference(");
+                if reference.name.0 == "null" {
+                    self.write("null");
+                } else {
+                    self.write(&quote_string(&reference.name.0));
+                }
+                self.write(")");
+            },
+            CssPropertyValue::PropertyReference(reference) => {
+                self.write("PropertyReference(");
+                self.write(&quote_string(&reference.name.0));
+                self.write(")");
+            },
+            CssPropertyValue::Url(url) => {
+                self.write("url(");
+                self.write(&quote_string(&url.url.0));
+                self.write(")");
+                if let Some(format) = &url.format {
+                    self.write(" format(");
+                    self.write(&quote_string(&format.0));
+                    self.write(")");
+                }
+            },
+            CssPropertyValue::Local(local) => {
+                self.write("local(");
+                self.write(&quote_string(&local.name.0));
+                self.write(")");
+            },
+            CssPropertyValue::Embed(embed) => {
+                self.write("Embed(");
+                for (i, entry) in embed.entries.iter().enumerate() {
+                    if i != 0 {
+                        self.write(",");
+                        self.write_space();
+                    }
+                    if let Some(key) = &entry.key {
+                        self.write(&key.0);
+                        self.write("=");
+                    }
+                    self.write(&quote_string(&entry.value.0));
+                }
+                self.write(")");
+            },
+        }
+    }
+
+    fn write_selector(&mut self, selector: &CssSelector) {
+        match selector {
+            CssSelector::Invalidated(_) => {},
+            CssSelector::Base(base) => {
+                if let Some(prefix) = &base.namespace_prefix {
+                    self.write(&prefix.0);
+                    self.write("|");
+                }
+                if let Some(element_name) = &base.element_name {
+                    self.write(&element_name.0);
+                }
+                for condition in base.conditions.iter() {
+                    self.write_selector_condition(condition);
+                }
+            },
+            CssSelector::Combinator(combinator) => {
+                self.write_selector(&combinator.left);
+                if combinator.combinator_type == CssCombinatorType::Descendant {
+                    self.write(" ");
+                } else {
+                    self.write_space();
+                    self.write(&combinator.combinator_type.to_string());
+                    self.write_space();
+                }
+                self.write_selector(&combinator.right);
+            },
+        }
+    }
+
+    fn write_selector_condition(&mut self, condition: &CssSelectorCondition) {
+        match condition {
+            CssSelectorCondition::Invalidated(_) => {},
+            CssSelectorCondition::Class((name, _)) => {
+                self.write(".");
+                self.write(name);
+            },
+            CssSelectorCondition::Id((name, _)) => {
+                self.write("#");
+                self.write(name);
+            },
+            CssSelectorCondition::Pseudo((name, _)) => {
+                self.write(":");
+                self.write(name);
+            },
+            CssSelectorCondition::PseudoElement((name, _)) => {
+                self.write("::");
+                self.write(name);
+            },
+            CssSelectorCondition::Not { condition, .. } => {
+                self.write(":not(");
+                self.write_selector_condition(condition);
+                self.write(")");
+            },
+            CssSelectorCondition::Attribute { name, operator, value, .. } => {
+                self.write("[");
+                self.write(&name.0);
+                if let (Some(operator), Some(value)) = (operator, value) {
+                    self.write(&operator.to_string());
+                    self.write(&quote_string(&value.0));
+                }
+                self.write("]");
+            },
+        }
+    }
+
+    fn write_media_query_condition(&mut self, condition: &CssMediaQueryCondition) {
+        match condition {
+            CssMediaQueryCondition::Invalidated(_) => {},
+            CssMediaQueryCondition::Id((name, _)) => {
+                self.write(name);
+            },
+            CssMediaQueryCondition::OnlyId { id, .. } => {
+                self.write("only ");
+                self.write(&id.0);
+            },
+            CssMediaQueryCondition::ParenProperty((property, _)) => {
+                self.write("(");
+                self.write_property(property);
+                self.write(")");
+            },
+            CssMediaQueryCondition::And { left, right, .. } => {
+                self.write_media_query_condition(left);
+                self.write(" and ");
+                self.write_media_query_condition(right);
+            },
+        }
+    }
+}
+
+/// Returns the name of a property as written in the source,
+/// such as `font-size`, falling back to its normalized name.
+fn property_source_name(property: &CssProperty) -> String {
+    let text = property.name.1.text();
+    if text.is_empty() || text.contains(|ch: char| ch.is_whitespace() || ch == ':') {
+        property.name.0.clone()
+    } else {
+        text
+    }
+}
+
+fn quote_string(value: &str) -> String {
+    let mut output = String::from("\"");
+    for ch in value.chars() {
+        match ch {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\A "),
+            _ => output.push(ch),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Returns the shortest hexadecimal form of a color, such as `#f00`.
+fn shortest_hex_color(color: CssColor) -> String {
+    let hex = color.to_hex();
+    let bytes = &hex.as_bytes()[1..];
+    if bytes.chunks(2).all(|pair| pair[0] == pair[1]) {
+        "#".to_owned() + &bytes.iter().step_by(2).map(|b| *b as char).collect::<String>()
+    } else {
+        hex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_format_and_minify() {
+        let source = r#"@namespace s "library://ns.adobe.com/flex/spark";
+s|Button.primary:over, s|Panel s|Label::first  { font-size: 0.5em; skin-class: ClassReference("skins.ButtonSkin") }
+.a { color: rgb(255, 0, 0) }
+.b { color: rgb(255, 0, 0) }
+.a { color: rgb(255, 0, 0) }
+@font-face { src: url("Font.ttf") format("truetype"); font-family: Main; }
+@media (application-dpi: 240) and (os-platform: "IOS") {
+    .icon { icon: Embed(source="icon.png", mimeType="image/png"); padding: 1 2 3; fill-colors: #FFFFFF, #CCCCCC; }
+}"#;
+        let compilation_unit = CompilationUnit::new(None, source.into());
+        let document = CssParserFacade(&compilation_unit, default()).parse_document();
+        assert!(!compilation_unit.invalidated());
+
+        let formatted = CssSerializer::format(&document, &CssFormatOptions {
+            indent: "  ".into(),
+        });
+        assert_eq!(formatted, r#"@namespace s "library://ns.adobe.com/flex/spark";
+
+s|Button.primary:over, s|Panel s|Label::first {
+  font-size: 0.5em;
+  skin-class: ClassReference("skins.ButtonSkin");
+}
+
+.a {
+  color: rgb(255, 0, 0);
+}
+
+.b {
+  color: rgb(255, 0, 0);
+}
+
+.a {
+  color: rgb(255, 0, 0);
+}
+
+@font-face {
+  src: url("Font.ttf") format("truetype");
+  font-family: Main;
+}
+
+@media (application-dpi: 240) and (os-platform: "IOS") {
+  .icon {
+    icon: Embed(source="icon.png", mimeType="image/png");
+    padding: 1 2 3;
+    fill-colors: #FFFFFF, #CCCCCC;
+  }
+}
+"#);
+
+        // Formatting is stable
+        let compilation_unit = CompilationUnit::new(None, formatted.clone());
+        let document_1 = CssParserFacade(&compilation_unit, default()).parse_document();
+        assert!(!compilation_unit.invalidated());
+        assert_eq!(CssSerializer::format(&document_1, &CssFormatOptions { indent: "  ".into() }), formatted);
+
+        let minified = CssSerializer::minify(&document);
+        assert_eq!(minified, concat!(
+            r#"@namespace s "library://ns.adobe.com/flex/spark";"#,
+            r#"s|Button.primary:over,s|Panel s|Label::first{font-size:.5em;skin-class:ClassReference("skins.ButtonSkin")}"#,
+            r#".b,.a{color:#f00}"#,
+            r#"@font-face{src:url("Font.ttf") format("truetype");font-family:Main}"#,
+            r#"@media (application-dpi:240) and (os-platform:"IOS"){.icon{icon:Embed(source="icon.png",mimeType="image/png");padding:1 2 3;fill-colors:#fff,#ccc}}"#,
+        ));
+        let compilation_unit = CompilationUnit::new(None, minified);
+        CssParserFacade(&compilation_unit, default()).parse_document();
+        assert!(!compilation_unit.invalidated());
+    }
+}
@@ -0,0 +1,512 @@
+use crate::ns::*;
+use super::as3_nodes::*;
+use std::collections::BTreeSet;
+
+pub struct CssStyleManagerGeneratorOptions {
+    /// The package of the generated class, such as `"com.acme.themes"`.
+    /// Default: the top-level package.
+    pub package_name: String,
+    /// The name of the generated class. Default: `"Styles"`.
+    pub class_name: String,
+}
+
+impl Default for CssStyleManagerGeneratorOptions {
+    fn default() -> Self {
+        Self {
+            package_name: "".into(),
+            class_name: "Styles".into(),
+        }
+    }
+}
+
+/// Generates an ActionScript 3 program that registers the style
+/// declarations of a style sheet on a Flex style manager, the way
+/// the Flex compiler does.
+///
+/// The program consists of a class with a static `init(styleManager: IStyleManager2)`
+/// method that, for every selector of every rule, builds a `CSSSelector`
+/// along with its `CSSCondition`s, retrieves or creates the corresponding
+/// `CSSStyleDeclaration` and assigns the declared styles through `setStyle()`.
+///
+/// - Type selectors are resolved to fully qualified class names through the
+///   `@namespace` definitions of the style sheet and a `MxmlNameResolver`;
+///   unprefixed type selectors are kept as is.
+/// - `ClassReference("...")` values become class references and
+///   `ClassReference(null)` becomes `null`.
+/// - `Embed(...)` values become `[Embed]` static variables of type `Class`,
+///   and so do `@font-face` rules.
+/// - Colors become hexadecimal integers; translucent colors lose
+///   their alpha, which Flex expresses through separate styles.
+/// - `@media` rules are registered only if
+///   `styleManager.acceptMediaList(...)` accepts their media query list
+///   at runtime.
+/// - Selectors using syntax the style manager cannot express, such as
+///   `:not()`, attribute and pseudo-element conditions or combinators
+///   other than the descendant combinator, are skipped and reported
+///   as warnings.
+///
+/// Every generated node is located at the CSS rule, property or value
+/// it originates from.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let source = r#"@namespace s "library://ns.adobe.com/flex/spark"; s|Button.primary { color: #FF0000 }"#;
+/// let compilation_unit = CompilationUnit::new(None, source.into());
+/// let document = CssParserFacade(&compilation_unit, default()).parse_document();
+///
+/// let mut manifest = FlexManifest::new();
+/// manifest.add_component("Button", "spark.components.Button");
+/// let mut resolver = MxmlNameResolver::new();
+/// resolver.add_manifest("library://ns.adobe.com/flex/spark", manifest);
+///
+/// let program = CssStyleManagerGenerator::generate(&document, &resolver, &default());
+/// let printed = As3Printer::print_program(&program);
+/// assert!(printed.contains(r#"styleManager.getStyleDeclaration("spark.components.Button.primary")"#));
+/// assert!(printed.contains(r#"style.setStyle("color", 0xFF0000);"#));
+/// ```
+pub struct CssStyleManagerGenerator<'a> {
+    name_resolver: &'a MxmlNameResolver,
+    namespaces: HashMap<String, String>,
+    imports: BTreeSet<String>,
+    /// `[Embed]` static variables.
+    fields: Vec<Rc<Directive>>,
+}
+
+const CSS_CONDITION: &str = "mx.styles.CSSCondition";
+const CSS_SELECTOR: &str = "mx.styles.CSSSelector";
+const CSS_STYLE_DECLARATION: &str = "mx.styles.CSSStyleDeclaration";
+const STYLE_MANAGER: &str = "mx.styles.IStyleManager2";
+
+impl<'a> CssStyleManagerGenerator<'a> {
+    /// Generates a program containing a package that defines a class.
+    pub fn generate(document: &CssDocument, name_resolver: &'a MxmlNameResolver, options: &CssStyleManagerGeneratorOptions) -> Rc<Program> {
+        let mut generator = CssStyleManagerGenerator {
+            name_resolver,
+            namespaces: HashMap::new(),
+            imports: [CSS_CONDITION, CSS_SELECTOR, CSS_STYLE_DECLARATION, STYLE_MANAGER].into_iter().map(|name| name.to_owned()).collect(),
+            fields: vec![],
+        };
+        let loc = document.location.clone();
+
+        let mut body = vec![
+            variable_definition(vec![], "selector", Some(qualified_name_expression(CSS_SELECTOR, &loc)), None, &loc),
+            variable_definition(vec![], "style", Some(qualified_name_expression(CSS_STYLE_DECLARATION, &loc)), None, &loc),
+        ];
+        for directive in document.directives.iter() {
+            match directive.as_ref() {
+                CssDirective::NamespaceDefinition(defn) => {
+                    generator.namespaces.insert(defn.prefix.0.clone(), defn.uri.0.clone());
+                },
+                CssDirective::Rule(rule) => {
+                    generator.generate_rule(rule, &mut body);
+                },
+                CssDirective::MediaQuery(query) => {
+                    let mut consequent = vec![];
+                    for rule in query.rules.iter() {
+                        generator.generate_rule(rule, &mut consequent);
+                    }
+                    let media_list = query.conditions.iter().map(|condition| condition.location().text()).collect::<Vec<_>>().join(", ");
+                    let test = call_expression(member_expression(&identifier_expression("styleManager", &query.location), "acceptMediaList", &query.location), vec![string_literal(&media_list, &query.location)], &query.location);
+                    body.push(if_statement(test, consequent, &query.location));
+                },
+                CssDirective::FontFace(font_face) => {
+                    generator.generate_font_face(font_face);
+                },
+                CssDirective::Import(_) |
+                CssDirective::Invalidated(_) => {},
+            }
+        }
+
+        let init = Rc::new(Directive::FunctionDefinition(FunctionDefinition {
+            location: loc.clone(),
+            asdoc: None,
+            attributes: vec![Attribute::Public(loc.clone()), Attribute::Static(loc.clone())],
+            name: FunctionName::Identifier(("init".into(), loc.clone())),
+            common: function_common(vec![Rc::new(Parameter {
+                location: loc.clone(),
+                kind: ParameterKind::Required,
+                destructuring: TypedDestructuring {
+                    location: loc.clone(),
+                    destructuring: identifier_expression("styleManager", &loc),
+                    type_annotation: Some(qualified_name_expression(STYLE_MANAGER, &loc)),
+                },
+                default_value: None,
+            })], Some(Rc::new(Expression::QualifiedIdentifier(QualifiedIdentifier {
+                location: loc.clone(),
+                attribute: false,
+                qualifier: None,
+                id: QualifiedIdentifierIdentifier::Id(("void".into(), loc.clone())),
+            }))), body, &loc),
+        }));
+
+        let mut class_block = vec![];
+        class_block.append(&mut generator.fields);
+        class_block.push(init);
+
+        let class_definition = Rc::new(Directive::ClassDefinition(ClassDefinition {
+            location: loc.clone(),
+            asdoc: None,
+            attributes: vec![Attribute::Public(loc.clone())],
+            name: (options.class_name.clone(), loc.clone()),
+            type_parameters: None,
+            extends_clause: None,
+            implements_clause: None,
+            block: Rc::new(Block {
+                location: loc.clone(),
+                directives: class_block,
+            }),
+        }));
+
+        let mut package_block = vec![];
+        for import in generator.imports.iter() {
+            package_block.push(import_directive(import, &loc));
+        }
+        package_block.push(class_definition);
+
+        let package_name = if options.package_name.is_empty() {
+            vec![]
+        } else {
+            options.package_name.split('.').map(|name| (name.to_owned(), loc.clone())).collect()
+        };
+        Rc::new(Program {
+            location: loc.clone(),
+            packages: vec![Rc::new(PackageDefinition {
+                location: loc.clone(),
+                asdoc: None,
+                name: package_name,
+                block: Rc::new(Block {
+                    location: loc,
+                    directives: package_block,
+                }),
+            })],
+            directives: vec![],
+        })
+    }
+
+    fn generate_rule(&mut self, rule: &CssRule, output: &mut Vec<Rc<Directive>>) {
+        let loc = rule.location.clone();
+        let mut properties = vec![];
+        for property in rule.properties.iter() {
+            if let Some(value) = self.generate_value(&property.value) {
+                properties.push((property, value));
+            }
+        }
+        for selector in rule.selectors.iter() {
+            let Some((selector_expression, key)) = self.generate_selector(selector) else {
+                continue;
+            };
+            let selector_variable = identifier_expression("selector", &loc);
+            let style = identifier_expression("style", &loc);
+            let style_manager = identifier_expression("styleManager", &loc);
+            output.push(expression_statement(assignment_expression(selector_variable.clone(), selector_expression, &loc)));
+            output.push(expression_statement(assignment_expression(style.clone(), call_expression(member_expression(&style_manager, "getStyleDeclaration", &loc), vec![string_literal(&key, &loc)], &loc), &loc)));
+            let new_style = new_expression(qualified_name_expression(CSS_STYLE_DECLARATION, &loc), vec![selector_variable, style_manager], &loc);
+            output.push(if_statement(
+                binary_expression(Operator::Equals, style.clone(), null_literal(&loc), &loc),
+                vec![expression_statement(assignment_expression(style.clone(), new_style, &loc))],
+                &loc,
+            ));
+            for (property, value) in properties.iter() {
+                let ploc = &property.location;
+                output.push(expression_statement(call_expression(member_expression(&style, "setStyle", ploc), vec![string_literal(&property.name.0, &property.name.1), value.clone()], ploc)));
+            }
+        }
+    }
+
+    /// Generates a `CSSSelector` construction along with the string
+    /// the style manager identifies the declaration by, such as
+    /// `"spark.components.Button.primary"`.
+    fn generate_selector(&mut self, selector: &CssSelector) -> Option<(Rc<Expression>, String)> {
+        self.generate_selector_with_ancestor(selector, None)
+    }
+
+    fn generate_selector_with_ancestor(&mut self, selector: &CssSelector, ancestor: Option<(Rc<Expression>, String)>) -> Option<(Rc<Expression>, String)> {
+        match selector {
+            CssSelector::Invalidated(_) => None,
+            CssSelector::Combinator(combinator) => {
+                if combinator.combinator_type != CssCombinatorType::Descendant {
+                    report_unsupported_selector(&combinator.location, &combinator.combinator_type.to_string());
+                    return None;
+                }
+                let ancestor = self.generate_selector_with_ancestor(&combinator.left, ancestor)?;
+                self.generate_selector_with_ancestor(&combinator.right, Some(ancestor))
+            },
+            CssSelector::Base(base) => {
+                let loc = base.location.clone();
+                let subject = match (&base.namespace_prefix, &base.element_name) {
+                    (_, None) => "*".to_owned(),
+                    (Some((prefix, _)), Some((local_name, _))) => {
+                        self.namespaces.get(prefix)
+                            .and_then(|uri| self.name_resolver.resolve(uri, local_name))
+                            .unwrap_or_else(|| local_name.clone())
+                    },
+                    (None, Some((local_name, _))) => local_name.clone(),
+                };
+                let mut conditions = vec![];
+                let mut key_conditions = String::new();
+                for condition in base.conditions.iter() {
+                    let (kind, value, prefix) = match condition.as_ref() {
+                        CssSelectorCondition::Class((name, _)) => ("class", name, "."),
+                        CssSelectorCondition::Id((name, _)) => ("id", name, "#"),
+                        CssSelectorCondition::Pseudo((name, _)) => ("pseudo", name, ":"),
+                        CssSelectorCondition::Invalidated(_) => return None,
+                        _ => {
+                            report_unsupported_selector(&condition.location(), &condition.location().text());
+                            return None;
+                        },
+                    };
+                    let cloc = condition.location();
+                    conditions.push(new_expression(qualified_name_expression(CSS_CONDITION, &cloc), vec![string_literal(kind, &cloc), string_literal(value, &cloc)], &cloc));
+                    key_conditions.push_str(prefix);
+                    key_conditions.push_str(value);
+                }
+                let mut key = if subject == "*" && !key_conditions.is_empty() { key_conditions } else { subject.clone() + &key_conditions };
+                let conditions = if conditions.is_empty() { null_literal(&loc) } else { array_literal(conditions, &loc) };
+                let ancestor_expression = if let Some((ancestor_expression, ancestor_key)) = ancestor {
+                    key = format!("{ancestor_key} {key}");
+                    ancestor_expression
+                } else {
+                    null_literal(&loc)
+                };
+                Some((new_expression(qualified_name_expression(CSS_SELECTOR, &loc), vec![string_literal(&subject, &loc), conditions, ancestor_expression], &loc), key))
+            },
+        }
+    }
+
+    /// Generates the expression of a style value, returning `None`
+    /// for invalidated values.
+    fn generate_value(&mut self, value: &CssPropertyValue) -> Option<Rc<Expression>> {
+        let loc = value.location();
+        Some(match value {
+            CssPropertyValue::Invalidated(_) => return None,
+            CssPropertyValue::Number(number) => numeric_literal(&number.value.to_string(), &loc),
+            CssPropertyValue::Color(_) |
+            CssPropertyValue::RgbColor(_) => numeric_literal(&format!("0x{:06X}", value.as_color().unwrap().rgb), &loc),
+            CssPropertyValue::String(string) => string_literal(&string.value, &loc),
+            CssPropertyValue::Identifier(identifier) => {
+                if identifier.value == "true" || identifier.value == "false" {
+                    Rc::new(Expression::BooleanLiteral(BooleanLiteral {
+                        location: loc,
+                        value: identifier.value == "true",
+                    }))
+                } else {
+                    string_literal(&identifier.value, &loc)
+                }
+            },
+            CssPropertyValue::Array(array) => {
+                let elements = array.elements.iter().filter_map(|element| self.generate_value(element)).collect();
+                array_literal(elements, &loc)
+            },
+            CssPropertyValue::MultiValue(multi_value) => {
+                let elements = multi_value.values.iter().filter_map(|element| self.generate_value(element)).collect();
+                array_literal(elements, &loc)
+            },
+            CssPropertyValue::ClassReference(reference) => {
+                if reference.name.0 == "null" {
+                    null_literal(&loc)
+                } else {
+                    if reference.name.0.contains('.') {
+                        self.imports.insert(reference.name.0.clone());
+                    }
+                    qualified_name_expression(&reference.name.0, &reference.name.1)
+                }
+            },
+            CssPropertyValue::PropertyReference(reference) => identifier_expression(&reference.name.0, &reference.name.1),
+            CssPropertyValue::Url(url) => string_literal(&url.url.0, &loc),
+            CssPropertyValue::Local(local) => string_literal(&local.name.0, &loc),
+            CssPropertyValue::Embed(embed) => {
+                let source = embed.entries.iter()
+                    .find(|entry| entry.key.as_ref().is_none_or(|(key, _)| key == "source"))
+                    .map_or(String::new(), |entry| entry.value.0.clone());
+                let entries = embed.entries.iter().map(|entry| {
+                    let key = entry.key.as_ref().map_or(("source".to_owned(), entry.location.clone()), |key| key.clone());
+                    (key, entry.value.clone())
+                }).collect();
+                let name = self.generate_embed(&source, entries, &loc);
+                identifier_expression(&name, &loc)
+            },
+        })
+    }
+
+    /// Generates an `[Embed]` static variable of type `Class`
+    /// for an `@font-face` rule.
+    fn generate_font_face(&mut self, font_face: &CssFontFace) {
+        let mut source = String::new();
+        let mut entries = vec![];
+        for property in font_face.properties.iter() {
+            let (name, value) = (&property.name, &property.value);
+            match value.as_ref() {
+                CssPropertyValue::Url(url) if name.0 == "src" => {
+                    source = url.url.0.clone();
+                    entries.insert(0, (("source".to_owned(), name.1.clone()), url.url.clone()));
+                },
+                CssPropertyValue::Local(local) if name.0 == "src" => {
+                    source = local.name.0.clone();
+                    entries.insert(0, (("systemFont".to_owned(), name.1.clone()), local.name.clone()));
+                },
+                CssPropertyValue::String(string) => {
+                    entries.push((name.clone(), (string.value.clone(), string.location.clone())));
+                },
+                CssPropertyValue::Invalidated(_) => {},
+                _ => {
+                    entries.push((name.clone(), (value.location().text(), value.location())));
+                },
+            }
+        }
+        self.generate_embed(&source, entries, &font_face.location);
+    }
+
+    /// Generates an `[Embed]` static variable of type `Class`,
+    /// returning its name, such as `_embed_css_icon_png_1`.
+    fn generate_embed(&mut self, source: &str, entries: Vec<((String, Location), (String, Location))>, loc: &Location) -> String {
+        let base_name = source.rsplit(['/', '\\']).next().unwrap_or("");
+        let base_name: String = base_name.chars().map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' }).collect();
+        let name = format!("_embed_css_{}_{}", base_name, self.fields.len() + 1);
+        let metadata = Rc::new(Metadata {
+            location: loc.clone(),
+            asdoc: None,
+            name: ("Embed".into(), loc.clone()),
+            entries: Some(entries.into_iter().map(|(key, value)| Rc::new(MetadataEntry {
+                location: key.1.combine_with(value.1.clone()),
+                key: Some(key),
+                value: Rc::new(MetadataValue::String(value)),
+            })).collect()),
+        });
+        self.fields.push(variable_definition(vec![
+            Attribute::Metadata(metadata),
+            Attribute::Private(loc.clone()),
+            Attribute::Static(loc.clone()),
+        ], &name, Some(identifier_expression("Class", loc)), None, loc));
+        name
+    }
+}
+
+fn report_unsupported_selector(loc: &Location, syntax: &str) {
+    loc.compilation_unit().add_diagnostic(Diagnostic::new_warning(loc, DiagnosticKind::UnsupportedCssSelector, diagarg![syntax.to_owned()]));
+}
+
+fn new_expression(base: Rc<Expression>, arguments: Vec<Rc<Expression>>, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::New(NewExpression {
+        location: loc.clone(),
+        base,
+        arguments: Some(arguments),
+    }))
+}
+
+fn null_literal(loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::NullLiteral(NullLiteral {
+        location: loc.clone(),
+    }))
+}
+
+fn array_literal(elements: Vec<Rc<Expression>>, loc: &Location) -> Rc<Expression> {
+    Rc::new(Expression::ArrayLiteral(ArrayLiteral {
+        location: loc.clone(),
+        asdoc: None,
+        elements: elements.into_iter().map(Element::Expression).collect(),
+    }))
+}
+
+fn if_statement(test: Rc<Expression>, consequent: Vec<Rc<Directive>>, loc: &Location) -> Rc<Directive> {
+    Rc::new(Directive::IfStatement(IfStatement {
+        location: loc.clone(),
+        test,
+        consequent: Rc::new(Directive::Block(Block {
+            location: loc.clone(),
+            directives: consequent,
+        })),
+        alternative: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_style_manager_generation() {
+        let source = r#"@namespace s "library://ns.adobe.com/flex/spark";
+@font-face { src: url("fonts/Open Sans.ttf"); fontFamily: "Open Sans"; embedAsCFF: true }
+s|Button.primary, .danger:over {
+    color: #F00;
+    font-family: "Open Sans", Arial;
+    skinClass: ClassReference("com.acme.skins.PrimarySkin");
+    icon: Embed(source="icons/ok.png");
+    emphasized: true;
+}
+s|Group s|Button { focusSkin: ClassReference(null) }
+@media (application-dpi: 240) { s|Button { fontSize: 18 } }"#;
+        let compilation_unit = CompilationUnit::new(None, source.into());
+        let document = CssParserFacade(&compilation_unit, default()).parse_document();
+        assert!(compilation_unit.diagnostics().is_empty());
+
+        let mut manifest = FlexManifest::new();
+        manifest.add_component("Button", "spark.components.Button");
+        manifest.add_component("Group", "spark.components.Group");
+        let mut resolver = MxmlNameResolver::new();
+        resolver.add_manifest("library://ns.adobe.com/flex/spark", manifest);
+
+        let program = CssStyleManagerGenerator::generate(&document, &resolver, &CssStyleManagerGeneratorOptions {
+            package_name: "com.acme".into(),
+            class_name: "ThemeStyles".into(),
+        });
+        let printed = As3Printer::print_program(&program);
+        assert!(printed.contains("import com.acme.skins.PrimarySkin;"));
+        assert!(printed.contains("public class ThemeStyles"));
+        assert!(printed.contains(r#"[Embed(source="fonts/Open Sans.ttf", fontFamily="Open Sans", embedAsCFF="true")]"#));
+        assert!(printed.contains("private static var _embed_css_Open_Sans_ttf_1: Class;"));
+        assert!(printed.contains(r#"[Embed(source="icons/ok.png")]"#));
+        assert!(printed.contains("public static function init(styleManager: mx.styles.IStyleManager2): void"));
+        assert!(printed.contains(r#"selector = new mx.styles.CSSSelector("spark.components.Button", [new mx.styles.CSSCondition("class", "primary")], null);"#));
+        assert!(printed.contains(r#"style = styleManager.getStyleDeclaration("spark.components.Button.primary");"#));
+        assert!(printed.contains(r#"style = styleManager.getStyleDeclaration(".danger:over");"#));
+        assert!(printed.contains(r#"style.setStyle("color", 0xFF0000);"#));
+        assert!(printed.contains(r#"style.setStyle("fontFamily", ["Open Sans", "Arial"]);"#));
+        assert!(printed.contains(r#"style.setStyle("skinClass", com.acme.skins.PrimarySkin);"#));
+        assert!(printed.contains(r#"style.setStyle("icon", _embed_css_ok_png_2);"#));
+        assert!(printed.contains(r#"style.setStyle("emphasized", true);"#));
+        assert!(printed.contains(r#"new mx.styles.CSSSelector("spark.components.Button", null, new mx.styles.CSSSelector("spark.components.Group", null, null))"#));
+        assert!(printed.contains(r#"styleManager.getStyleDeclaration("spark.components.Group spark.components.Button")"#));
+        assert!(printed.contains(r#"style.setStyle("focusSkin", null);"#));
+        assert!(printed.contains(r#"if (styleManager.acceptMediaList("(application-dpi: 240)"))"#));
+
+        // Selectors the style manager cannot express are skipped
+        // rather than generated as broader selectors
+        let compilation_unit = CompilationUnit::new(None, r#"@namespace s "library://ns.adobe.com/flex/spark";
+s|Button:not(.x), s|Button[label], s|Button.y { color: red }
+s|Group s|Button { color: red }"#.into());
+        let document = CssParserFacade(&compilation_unit, default()).parse_document();
+        let CssDirective::Rule(rule) = document.directives[2].as_ref() else { panic!() };
+        let CssSelector::Combinator(combinator) = rule.selectors[0].as_ref() else { panic!() };
+        // The parser only produces descendant combinators
+        let child_selector = Rc::new(CssSelector::Combinator(CssCombinatorSelector {
+            combinator_type: CssCombinatorType::Child,
+            ..combinator.clone()
+        }));
+        let document = CssDocument {
+            location: document.location.clone(),
+            directives: vec![
+                document.directives[0].clone(),
+                document.directives[1].clone(),
+                Rc::new(CssDirective::Rule(CssRule { selectors: vec![child_selector], ..rule.clone() })),
+            ],
+        };
+        let printed_unsupported = As3Printer::print_program(&CssStyleManagerGenerator::generate(&document, &resolver, &default()));
+        assert_eq!(printed_unsupported.matches("new mx.styles.CSSSelector(").count(), 1);
+        assert!(printed_unsupported.contains(r#"styleManager.getStyleDeclaration("spark.components.Button.y")"#));
+        let summary: Vec<String> = compilation_unit.diagnostics().iter().map(|d| d.format_message_english()).collect();
+        assert_eq!(summary, [
+            "Unsupported selector syntax ':not(.x)'; the selector is ignored.",
+            "Unsupported selector syntax '[label]'; the selector is ignored.",
+            "Unsupported selector syntax '>'; the selector is ignored.",
+        ]);
+
+        // The generated source is valid ActionScript
+        let generated = CompilationUnit::new(None, printed);
+        ParserFacade(&generated, default()).parse_program();
+        assert!(!generated.invalidated());
+    }
+}
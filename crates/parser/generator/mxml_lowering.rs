@@ -0,0 +1,702 @@
+use crate::ns::*;
+use super::as3_nodes::*;
+use lazy_regex::*;
+use std::collections::BTreeSet;
+
+/// Provides the component information required for lowering MXML.
+pub trait MxmlComponentResolver {
+    /// Resolves the fully qualified class name of a component
+    /// given its namespace URI and local name.
+    fn resolve_component(&self, uri: &str, local_name: &str) -> Option<String>;
+
+    /// Returns the name of the default property of a class,
+    /// as declared by the `[DefaultProperty]` meta-data.
+    fn default_property(&self, class_name: &str) -> Option<String>;
+
+    /// Returns the fully qualified type name of a property of a class.
+    fn property_type(&self, class_name: &str, property_name: &str) -> Option<String>;
+
+    /// Returns the fully qualified event class of an event of a class,
+    /// as declared by the `[Event]` meta-data, if the class declares
+    /// such an event.
+    fn event_type(&self, class_name: &str, event_name: &str) -> Option<String>;
+}
+
+/// A component resolver consisting of explicitly registered
+/// components, properties and events.
+///
+/// Components not explicitly registered are resolved through
+/// a `MxmlNameResolver`, if one is given.
+#[derive(Clone, Default)]
+pub struct MxmlComponentMap {
+    components: HashMap<(String, String), String>,
+    name_resolver: Option<MxmlNameResolver>,
+    default_properties: HashMap<String, String>,
+    property_types: HashMap<(String, String), String>,
+    event_types: HashMap<(String, String), String>,
+}
+
+impl MxmlComponentMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps a namespace URI and local name to a fully qualified class name.
+    pub fn add_component(&mut self, uri: &str, local_name: &str, class_name: &str) {
+        self.components.insert((uri.to_owned(), local_name.to_owned()), class_name.to_owned());
+    }
+
+    /// Sets the resolver used for components not explicitly registered,
+    /// such as those given by manifests or package wildcard namespaces.
+    pub fn set_name_resolver(&mut self, name_resolver: MxmlNameResolver) {
+        self.name_resolver = Some(name_resolver);
+    }
+
+    pub fn set_default_property(&mut self, class_name: &str, property_name: &str) {
+        self.default_properties.insert(class_name.to_owned(), property_name.to_owned());
+    }
+
+    pub fn add_property(&mut self, class_name: &str, property_name: &str, type_name: &str) {
+        self.property_types.insert((class_name.to_owned(), property_name.to_owned()), type_name.to_owned());
+    }
+
+    pub fn add_event(&mut self, class_name: &str, event_name: &str, type_name: &str) {
+        self.event_types.insert((class_name.to_owned(), event_name.to_owned()), type_name.to_owned());
+    }
+}
+
+impl MxmlComponentResolver for MxmlComponentMap {
+    fn resolve_component(&self, uri: &str, local_name: &str) -> Option<String> {
+        self.components.get(&(uri.to_owned(), local_name.to_owned())).cloned()
+            .or_else(|| self.name_resolver.as_ref().and_then(|resolver| resolver.resolve(uri, local_name)))
+    }
+
+    fn default_property(&self, class_name: &str) -> Option<String> {
+        self.default_properties.get(class_name).cloned()
+    }
+
+    fn property_type(&self, class_name: &str, property_name: &str) -> Option<String> {
+        self.property_types.get(&(class_name.to_owned(), property_name.to_owned())).cloned()
+    }
+
+    fn event_type(&self, class_name: &str, event_name: &str) -> Option<String> {
+        self.event_types.get(&(class_name.to_owned(), event_name.to_owned())).cloned()
+    }
+}
+
+pub struct MxmlLoweringOptions {
+    /// The package of the generated class, such as `"com.acme.views"`.
+    /// Default: the top-level package.
+    pub package_name: String,
+    /// The name of the generated class. Default: the base name
+    /// of the MXML file, without extension.
+    pub class_name: Option<String>,
+}
+
+impl Default for MxmlLoweringOptions {
+    fn default() -> Self {
+        Self {
+            package_name: "".into(),
+            class_name: None,
+        }
+    }
+}
+
+/// Lowers a MXML document into an ActionScript 3 program, the way
+/// the Flex compiler does.
+///
+/// The root element becomes a class extending the root component,
+/// with the following members:
+///
+/// - A `[Bindable] public var` for every element with an `id` attribute.
+/// - The `<fx:Script>` directives, if the document was parsed
+///   with the `parse_mxml_scripts` option enabled.
+/// - A constructor assigning the root attributes and children.
+/// - A factory method for every child component, named like
+///   `_Main_Button1_i`, instantiating and initializing it.
+/// - A handler method for every event attribute, named after the `id`
+///   of the component or like `__button1_click`, taking an `event` parameter.
+///
+/// Attributes are assigned to properties, unless they name an event of
+/// the component, in which case they become event listeners. Child components
+/// are assigned to the default property of their parent, and child elements
+/// of the same namespace as their parent, such as `<s:layout>`, are assigned to
+/// properties. Children of `<fx:Declarations>` are instantiated without
+/// being assigned to any property.
+///
+/// Data bindings, if parsed with the `parse_mxml_bindings` option enabled,
+/// are lowered to one-time assignments. View states are not lowered;
+/// state-specific attributes and property elements, along with the `includeIn`,
+/// `excludeFrom` and `itemCreationPolicy` attributes, are skipped.
+///
+/// Every generated node is located at the MXML element or attribute
+/// it originates from. Unknown components are reported as verify errors.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let source = r#"<s:Application xmlns:s="library://ns.adobe.com/flex/spark"><s:Button id="button"/></s:Application>"#;
+/// let compilation_unit = CompilationUnit::new(None, source.into());
+/// let mxml = ParserFacade(&compilation_unit, default()).parse_mxml();
+///
+/// let mut resolver = MxmlComponentMap::new();
+/// resolver.add_component("library://ns.adobe.com/flex/spark", "Application", "spark.components.Application");
+/// resolver.add_component("library://ns.adobe.com/flex/spark", "Button", "spark.components.Button");
+/// resolver.set_default_property("spark.components.Application", "mxmlContentFactory");
+///
+/// let program = MxmlLowering::lower(&mxml, &resolver, &MxmlLoweringOptions {
+///     class_name: Some("Main".into()),
+///     ..default()
+/// });
+/// assert!(As3Printer::print_program(&program).contains("public class Main extends spark.components.Application"));
+/// ```
+pub struct MxmlLowering<'a> {
+    compilation_unit: Rc<CompilationUnit>,
+    resolver: &'a dyn MxmlComponentResolver,
+    class_name: String,
+    imports: BTreeSet<String>,
+    /// Members generated before the constructor.
+    fields: Vec<Rc<Directive>>,
+    /// Members generated after the constructor.
+    methods: Vec<Rc<Directive>>,
+    /// Instance counters used for naming, per class name.
+    counters: HashMap<String, usize>,
+}
+
+impl<'a> MxmlLowering<'a> {
+    /// Lowers a MXML document into a program containing a package
+    /// that defines a class.
+    pub fn lower(mxml: &Mxml, resolver: &'a dyn MxmlComponentResolver, options: &MxmlLoweringOptions) -> Rc<Program> {
+        let compilation_unit = mxml.location.compilation_unit();
+        let class_name = options.class_name.clone().unwrap_or_else(|| {
+            compilation_unit.file_path().map_or("".into(), |file_path| {
+                let file_name = file_paths::FlexPath::new_native(&file_path).base_name();
+                file_name.split('.').next().unwrap_or("").to_owned()
+            })
+        });
+        let root = mxml.content.iter().find_map(|content| {
+            if let MxmlContent::Element(element) = content.as_ref() { Some(element.clone()) } else { None }
+        });
+        let Some(root) = root else {
+            return Rc::new(Program {
+                location: mxml.location.clone(),
+                packages: vec![],
+                directives: vec![],
+            });
+        };
+
+        let mut lowering = MxmlLowering {
+            compilation_unit,
+            resolver,
+            class_name: class_name.clone(),
+            imports: BTreeSet::new(),
+            fields: vec![],
+            methods: vec![],
+            counters: HashMap::new(),
+        };
+
+        let loc = root.location.clone();
+        let root_class = lowering.resolve_element(&root);
+
+        // Constructor
+        let mut constructor_body = vec![Rc::new(Directive::SuperStatement(SuperStatement {
+            location: loc.clone(),
+            arguments: vec![],
+        }))];
+        if let Some(root_class) = &root_class {
+            let target = this_expression(&loc);
+            lowering.lower_element_body(&root, root_class, &class_name, &target, &mut constructor_body);
+        }
+        let constructor = Rc::new(Directive::FunctionDefinition(FunctionDefinition {
+            location: loc.clone(),
+            asdoc: None,
+            attributes: vec![Attribute::Public(loc.clone())],
+            name: FunctionName::Constructor((class_name.clone(), loc.clone())),
+            common: function_common(vec![], None, constructor_body, &loc),
+        }));
+
+        let mut class_block = vec![];
+        class_block.append(&mut lowering.fields);
+        class_block.push(constructor);
+        class_block.append(&mut lowering.methods);
+
+        let class_definition = Rc::new(Directive::ClassDefinition(ClassDefinition {
+            location: loc.clone(),
+            asdoc: None,
+            attributes: vec![Attribute::Public(loc.clone())],
+            name: (class_name, loc.clone()),
+            type_parameters: None,
+            extends_clause: root_class.as_ref().map(|name| qualified_name_expression(name, &loc)),
+            implements_clause: None,
+            block: Rc::new(Block {
+                location: loc.clone(),
+                directives: class_block,
+            }),
+        }));
+
+        let mut package_block = vec![];
+        for import in lowering.imports.iter() {
+            package_block.push(import_directive(import, &loc));
+        }
+        package_block.push(class_definition);
+
+        let package_name = if options.package_name.is_empty() {
+            vec![]
+        } else {
+            options.package_name.split('.').map(|name| (name.to_owned(), loc.clone())).collect()
+        };
+        Rc::new(Program {
+            location: mxml.location.clone(),
+            packages: vec![Rc::new(PackageDefinition {
+                location: loc.clone(),
+                asdoc: None,
+                name: package_name,
+                block: Rc::new(Block {
+                    location: loc,
+                    directives: package_block,
+                }),
+            })],
+            directives: vec![],
+        })
+    }
+
+    /// Resolves the class of a component element, reporting
+    /// a verify error if it is unknown.
+    fn resolve_element(&mut self, element: &MxmlElement) -> Option<String> {
+        let resolved = element.name.resolve_name(&element.namespace).ok()
+            .and_then(|(uri, local_name)| self.resolver.resolve_component(&uri, &local_name));
+        let Some(class_name) = resolved else {
+            self.compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&element.name.location, DiagnosticKind::UnknownMxmlComponent, diagarg![element.name.to_string(&element.namespace)]));
+            return None;
+        };
+        if class_name.contains('.') {
+            self.imports.insert(class_name.clone());
+        }
+        Some(class_name)
+    }
+
+    /// Generates the statements initializing the properties, events
+    /// and children of a component stored at `target`. Handler methods
+    /// are named after `instance_name`.
+    fn lower_element_body(&mut self, element: &MxmlElement, class_name: &str, instance_name: &str, target: &Rc<Expression>, output: &mut Vec<Rc<Directive>>) {
+
+        for attribute in element.attributes.iter() {
+            if attribute.xmlns || attribute.name.prefix.is_some() || Self::is_reserved_attribute(&attribute.name.name) {
+                continue;
+            }
+            let loc = attribute.location.clone();
+            let name = &attribute.name.name;
+            if let Some(event_type) = self.resolver.event_type(class_name, name) {
+                let handler_name = format!("__{instance_name}_{name}");
+                self.generate_event_handler(&handler_name, &event_type, attribute);
+                output.push(expression_statement(call_expression(
+                    member_expression(target, "addEventListener", &loc),
+                    vec![string_literal(name, &loc), member_expression(&this_expression(&loc), &handler_name, &loc)],
+                    &loc,
+                )));
+            } else {
+                // width="100%" sets percentWidth, as does height="100%" percentHeight
+                let percentage = if ["width", "height"].contains(&name.as_str()) && attribute.binding.is_none() {
+                    attribute.value.0.trim().strip_suffix('%')
+                } else {
+                    None
+                };
+                let (name, value) = if let Some(percentage) = percentage {
+                    let mut chars = name.chars();
+                    let percent_name: String = "percent".chars().chain(chars.next().into_iter().flat_map(char::to_uppercase)).chain(chars).collect();
+                    let value = literal_value(&percent_name, percentage, None, Some("Number"), &loc);
+                    (percent_name, value)
+                } else {
+                    let property_type = self.resolver.property_type(class_name, name);
+                    (name.clone(), literal_value(name, &attribute.value.0, attribute.binding.as_ref(), property_type.as_deref(), &loc))
+                };
+                output.push(expression_statement(assignment_expression(member_expression(target, &name, &loc), value, &loc)));
+            }
+        }
+
+        let element_uri = element.name.resolve_prefix(&element.namespace).ok();
+        let mut default_children: Vec<Rc<MxmlElement>> = vec![];
+        let mut text_content = false;
+        for content in element.content.iter().flatten() {
+            let child = match content.as_ref() {
+                MxmlContent::Element(child) => child,
+                MxmlContent::Characters((text, _)) if !text.trim().is_empty() => {
+                    text_content = true;
+                    continue;
+                },
+                MxmlContent::CData(_) => {
+                    text_content = true;
+                    continue;
+                },
+                _ => continue,
+            };
+            if child.is_language_element("Script") {
+                self.fields.extend(child.script_directives.iter().flatten().cloned());
+            } else if child.is_language_element("Declarations") {
+                for declaration in mxml_child_elements(child) {
+                    if let Some(factory) = self.generate_factory(&declaration) {
+                        output.push(expression_statement(factory));
+                    }
+                }
+            } else if child.name.resolve_prefix(&child.namespace).ok() == element_uri && self.is_property_element(child, class_name) {
+                if child.name.name.contains('.') || child.name.name == "states" {
+                    continue;
+                }
+                let loc = child.location.clone();
+                let property_type = self.resolver.property_type(class_name, &child.name.name);
+                let value = self.property_element_value(&child.name.name, child, &mxml_child_elements(child), property_type.as_deref());
+                output.push(expression_statement(assignment_expression(member_expression(target, &child.name.name, &loc), value, &loc)));
+            } else if !child.is_language_element(&child.name.name) {
+                default_children.push(child.clone());
+            }
+        }
+
+        if default_children.is_empty() && !text_content {
+            return;
+        }
+        let loc = element.location.clone();
+        let Some(default_property) = self.resolver.default_property(class_name) else {
+            self.compilation_unit.add_diagnostic(Diagnostic::new_verify_error(&element.name.location, DiagnosticKind::MxmlComponentHasNoDefaultProperty, diagarg![class_name.to_owned()]));
+            return;
+        };
+        let property_type = self.resolver.property_type(class_name, &default_property);
+        let value = self.property_element_value(&default_property, element, &default_children, property_type.as_deref());
+        output.push(expression_statement(assignment_expression(member_expression(target, &default_property, &loc), value, &loc)));
+    }
+
+    /// Indicates whether an attribute is handled otherwise than as
+    /// a property or event, such as `id` and state-related attributes.
+    fn is_reserved_attribute(name: &str) -> bool {
+        ["id", "includeIn", "excludeFrom", "itemCreationPolicy", "itemDestructionPolicy"].contains(&name) || name.contains('.')
+    }
+
+    /// Indicates whether a child element sharing the namespace of its
+    /// parent denotes a property rather than a component.
+    fn is_property_element(&self, child: &MxmlElement, class_name: &str) -> bool {
+        self.resolver.property_type(class_name, &child.name.name).is_some()
+            || child.name.name.starts_with(|ch: char| ch.is_lowercase())
+    }
+
+    /// Returns the value of the property `name` given as an element, either
+    /// consisting of text or of the given component children.
+    fn property_element_value(&mut self, name: &str, element: &MxmlElement, children: &[Rc<MxmlElement>], property_type: Option<&str>) -> Rc<Expression> {
+        let loc = element.location.clone();
+        if children.is_empty() {
+            let text = element.inner_text();
+            return literal_value(name, text.trim(), element.content_binding.as_ref(), property_type, &loc);
+        }
+        let mut factories = vec![];
+        for child in children.iter() {
+            if let Some(factory) = self.generate_factory(child) {
+                factories.push(factory);
+            }
+        }
+        let is_array = property_type.is_none_or(|t| t == "Array" || t.starts_with("Vector.<"));
+        if factories.len() == 1 && !is_array {
+            return factories.remove(0);
+        }
+        Rc::new(Expression::ArrayLiteral(ArrayLiteral {
+            location: loc,
+            asdoc: None,
+            elements: factories.into_iter().map(Element::Expression).collect(),
+        }))
+    }
+
+    /// Generates the factory method of a component element and
+    /// returns a call to it.
+    fn generate_factory(&mut self, element: &MxmlElement) -> Option<Rc<Expression>> {
+        let class_name = self.resolve_element(element)?;
+        let loc = element.location.clone();
+        let simple_name = class_name.rsplit('.').next().unwrap().to_owned();
+        let counter = self.counters.entry(class_name.clone()).or_insert(0);
+        *counter += 1;
+        let method_name = format!("_{}_{}{}_i", self.class_name, simple_name, counter);
+        let instance_name = element.attribute_value("id").map_or_else(|| format!("{}{}", simple_name.to_lowercase(), counter), |(id, _)| id.clone());
+
+        let type_expression = qualified_name_expression(&class_name, &loc);
+        let temp = identifier_expression("temp", &loc);
+        let mut body = vec![variable_definition(vec![], "temp", Some(type_expression.clone()), Some(Rc::new(Expression::New(NewExpression {
+            location: loc.clone(),
+            base: type_expression.clone(),
+            arguments: Some(vec![]),
+        }))), &loc)];
+        self.lower_element_body(element, &class_name, &instance_name, &temp, &mut body);
+
+        if let Some((id, id_location)) = element.attribute_value("id") {
+            self.fields.push(variable_definition(vec![
+                Attribute::Metadata(Rc::new(Metadata {
+                    location: id_location.clone(),
+                    asdoc: None,
+                    name: ("Bindable".into(), id_location.clone()),
+                    entries: None,
+                })),
+                Attribute::Public(id_location.clone()),
+            ], id, Some(type_expression.clone()), None, id_location));
+            body.push(expression_statement(assignment_expression(member_expression(&this_expression(&loc), id, &loc), temp.clone(), &loc)));
+        }
+        body.push(Rc::new(Directive::ReturnStatement(ReturnStatement {
+            location: loc.clone(),
+            expression: Some(temp),
+        })));
+
+        self.methods.push(Rc::new(Directive::FunctionDefinition(FunctionDefinition {
+            location: loc.clone(),
+            asdoc: None,
+            attributes: vec![Attribute::Private(loc.clone())],
+            name: FunctionName::Identifier((method_name.clone(), loc.clone())),
+            common: function_common(vec![], Some(type_expression), body, &loc),
+        })));
+
+        Some(call_expression(member_expression(&this_expression(&loc), &method_name, &loc), vec![], &loc))
+    }
+
+    /// Generates an event handler method whose body consists of
+    /// the directives given by an attribute value.
+    fn generate_event_handler(&mut self, name: &str, event_type: &str, attribute: &MxmlAttribute) {
+        let loc = attribute.location.clone();
+        let value_location = &attribute.value.1;
+        let (first_offset, last_offset) = (value_location.first_offset() + 1, value_location.last_offset() - 1);
+
+        // Handlers parsed along with the document are used as is. Otherwise,
+        // attribute values containing entities are parsed from a separate
+        // compilation unit consisting of the unescaped value.
+        let body = if let Some(directives) = &attribute.event_handler {
+            directives.clone()
+        } else if self.compilation_unit.text()[first_offset..last_offset] == attribute.value.0 {
+            ParserFacade(&self.compilation_unit, ParserOptions {
+                byte_range: Some((first_offset, last_offset)),
+                ..default()
+            }).parse_directives(ParserDirectiveContext::Default)
+        } else {
+            let embedded = EmbeddedCompilationUnit::new(&self.compilation_unit, &[(attribute.value.0.clone(), first_offset)]);
+            let directives = ParserFacade(&embedded.compilation_unit(), default()).parse_directives(ParserDirectiveContext::Default);
+            embedded.transfer_diagnostics();
+            directives
+        };
+
+        if event_type.contains('.') {
+            self.imports.insert(event_type.to_owned());
+        }
+        let parameter = Rc::new(Parameter {
+            location: loc.clone(),
+            kind: ParameterKind::Required,
+            destructuring: TypedDestructuring {
+                location: loc.clone(),
+                destructuring: identifier_expression("event", &loc),
+                type_annotation: Some(qualified_name_expression(event_type, &loc)),
+            },
+            default_value: None,
+        });
+        self.methods.push(Rc::new(Directive::FunctionDefinition(FunctionDefinition {
+            location: loc.clone(),
+            asdoc: None,
+            attributes: vec![Attribute::Public(loc.clone())],
+            name: FunctionName::Identifier((name.to_owned(), loc.clone())),
+            common: function_common(vec![parameter], Some(Rc::new(Expression::VoidType(VoidTypeExpression {
+                location: loc.clone(),
+            }))), body, &loc),
+        })));
+    }
+}
+
+fn mxml_child_elements(element: &MxmlElement) -> Vec<Rc<MxmlElement>> {
+    element.content.iter().flatten().filter_map(|content| {
+        if let MxmlContent::Element(child) = content.as_ref() { Some(child.clone()) } else { None }
+    }).collect()
+}
+
+/// Converts a literal attribute or character value into an expression,
+/// according to the property type if known. Values that are not valid
+/// for a numeric property are reported and converted into strings.
+fn literal_value(name: &str, value: &str, binding: Option<&Rc<MxmlBinding>>, property_type: Option<&str>, loc: &Location) -> Rc<Expression> {
+    if let Some(binding) = binding {
+        return binding_expression(binding, loc);
+    }
+    let numeric = matches!(property_type, Some("Number" | "int" | "uint"));
+    let boolean = property_type == Some("Boolean");
+    let string = matches!(property_type, Some("String" | "Object" | "*"));
+
+    // Colors such as #FF0000
+    if property_type == Some("uint") && value.starts_with('#') && regex_is_match!(r"^#[0-9a-fA-F]+$", value) {
+        return numeric_literal(&format!("0x{}", &value[1..]), loc);
+    }
+    if boolean || (!string && property_type.is_none() && (value == "true" || value == "false")) {
+        return Rc::new(Expression::BooleanLiteral(BooleanLiteral {
+            location: loc.clone(),
+            value: value == "true",
+        }));
+    }
+    if numeric || (!string && property_type.is_none()) {
+        if let Some(exp) = numeric_value(value, loc) {
+            return exp;
+        }
+        if let Some(property_type) = property_type.filter(|_| numeric) {
+            loc.compilation_unit().add_diagnostic(Diagnostic::new_verify_error(loc, DiagnosticKind::InvalidMxmlNumericValue, diagarg![value.to_owned(), name.to_owned(), property_type.to_owned()]));
+        }
+    }
+    string_literal(value, loc)
+}
+
+/// Converts a decimal or hexadecimal number, optionally negative,
+/// into an expression.
+fn numeric_value(value: &str, loc: &Location) -> Option<Rc<Expression>> {
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    if !regex_is_match!(r"^(0[xX][0-9a-fA-F]+|([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][+-]?[0-9]+)?)$", digits) {
+        return None;
+    }
+    let literal = numeric_literal(digits, loc);
+    Some(if negative {
+        Rc::new(Expression::Unary(UnaryExpression {
+            location: loc.clone(),
+            operator: Operator::Negative,
+            expression: literal,
+        }))
+    } else {
+        literal
+    })
+}
+
+/// Lowers a data binding into an expression; bindings consisting of
+/// multiple segments are concatenated as strings.
+fn binding_expression(binding: &MxmlBinding, loc: &Location) -> Rc<Expression> {
+    let mut segments = binding.segments.iter().map(|segment| match segment {
+        MxmlBindingSegment::Literal((text, _)) => string_literal(text, loc),
+        // Parenthesize operations such as "Hi {a ? b : c}" for concatenation
+        MxmlBindingSegment::Expression(exp) if binding.segments.len() > 1 && !is_primary_expression(exp) => paren_expression(exp.clone(), loc),
+        MxmlBindingSegment::Expression(exp) => exp.clone(),
+    });
+    let Some(first) = segments.next() else {
+        return string_literal("", loc);
+    };
+    if binding.segments.len() == 1 {
+        return first;
+    }
+    // Ensure string concatenation
+    let first = if matches!(binding.segments[0], MxmlBindingSegment::Expression(_)) {
+        add_expression(string_literal("", loc), first, loc)
+    } else {
+        first
+    };
+    segments.fold(first, |left, right| add_expression(left, right, loc))
+}
+
+/// Indicates whether an expression binds tighter than any operator.
+fn is_primary_expression(exp: &Expression) -> bool {
+    matches!(exp, Expression::QualifiedIdentifier(_) | Expression::Member(_) | Expression::ComputedMember(_) |
+        Expression::Call(_) | Expression::Paren(_) | Expression::ThisLiteral(_) | Expression::NullLiteral(_) |
+        Expression::BooleanLiteral(_) | Expression::NumericLiteral(_) | Expression::StringLiteral(_) |
+        Expression::ArrayLiteral(_) | Expression::ObjectInitializer(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_lowering() {
+        let source = r#"<?xml version="1.0"?>
+<s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark" width="400" creationComplete="init()">
+    <fx:Script>
+        private function init(): void {}
+    </fx:Script>
+    <fx:Declarations>
+        <s:Button id="hidden"/>
+    </fx:Declarations>
+    <s:layout>
+        <s:VerticalLayout gap="10"/>
+    </s:layout>
+    <s:Button id="button" label="Hello {name}" click="trace(event); button.enabled = 1 &lt; 2;"/>
+    <s:Button label="Other" enabled="false"/>
+    <s:Label>Text</s:Label>
+    <s:Unknown/>
+</s:Application>"#;
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let mxml = ParserFacade(&compilation_unit, ParserOptions {
+            parse_mxml_scripts: true,
+            parse_mxml_bindings: true,
+            ..default()
+        }).parse_mxml();
+        let spark = "library://ns.adobe.com/flex/spark";
+        let mut resolver = MxmlComponentMap::new();
+        for name in ["Application", "Button", "Label", "VerticalLayout"] {
+            resolver.add_component(spark, name, &format!("spark.components.{name}"));
+        }
+        resolver.set_default_property("spark.components.Application", "mxmlContent");
+        resolver.set_default_property("spark.components.Label", "text");
+        resolver.add_property("spark.components.Application", "mxmlContent", "Array");
+        resolver.add_property("spark.components.Application", "layout", "spark.layouts.supportClasses.LayoutBase");
+        resolver.add_property("spark.components.Label", "text", "String");
+        resolver.add_event("spark.components.Application", "creationComplete", "mx.events.FlexEvent");
+        resolver.add_event("spark.components.Button", "click", "flash.events.MouseEvent");
+
+        let program = MxmlLowering::lower(&mxml, &resolver, &MxmlLoweringOptions {
+            package_name: "com.acme".into(),
+            class_name: Some("Main".into()),
+        });
+        let printed = As3Printer::print_program(&program);
+        assert!(printed.contains("public class Main extends spark.components.Application"));
+        assert!(printed.contains("import mx.events.FlexEvent;"));
+        assert!(printed.contains("[Bindable]\n        public var button: spark.components.Button;"));
+        assert!(printed.contains("this.width = 400;"));
+        assert!(printed.contains("this.addEventListener(\"creationComplete\", this.__Main_creationComplete);"));
+        assert!(printed.contains("temp.label = \"Hello \" + name;"));
+        assert!(printed.contains("temp.enabled = false;"));
+        assert!(printed.contains("this._Main_Button1_i();"));
+        assert!(printed.contains("this.layout = this._Main_VerticalLayout1_i();"));
+        assert!(printed.contains("this.mxmlContent = [this._Main_Button2_i(), this._Main_Button3_i(), this._Main_Label1_i()];"));
+        assert!(printed.contains("temp.text = \"Text\";"));
+        assert!(printed.contains("public function __button_click(event: flash.events.MouseEvent): void {\n            trace(event);\n            button.enabled = 1 < 2;"));
+
+        // Generated nodes are located at the originating element
+        let Directive::ClassDefinition(class_definition) = program.packages[0].block.directives.last().unwrap().as_ref() else {
+            panic!();
+        };
+        assert_eq!(class_definition.location.first_line_number(), 2);
+
+        // The generated source is valid ActionScript
+        let generated = CompilationUnit::new(None, printed);
+        ParserFacade(&generated, default()).parse_program();
+        assert!(!generated.invalidated());
+
+        // Unknown component
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind() == DiagnosticKind::UnknownMxmlComponent);
+        assert_eq!(diagnostics[0].location().first_line_number(), 15);
+    }
+
+    #[test]
+    fn test_lowering_attribute_values() {
+        let source = r#"<s:Button xmlns:s="library://ns.adobe.com/flex/spark" label="Hi {a ? b : c}!" width="100%" x="-5" height="tall" ñame="x"/>"#;
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let mxml = ParserFacade(&compilation_unit, ParserOptions {
+            parse_mxml_bindings: true,
+            ..default()
+        }).parse_mxml();
+        let mut resolver = MxmlComponentMap::new();
+        resolver.add_component("library://ns.adobe.com/flex/spark", "Button", "spark.components.Button");
+        for name in ["width", "height", "percentWidth", "x"] {
+            resolver.add_property("spark.components.Button", name, "Number");
+        }
+        let program = MxmlLowering::lower(&mxml, &resolver, &MxmlLoweringOptions {
+            package_name: "".into(),
+            class_name: Some("Main".into()),
+        });
+        let printed = As3Printer::print_program(&program);
+        assert!(printed.contains("this.label = \"Hi \" + (a ? b : c) + \"!\";"));
+        assert!(printed.contains("this.percentWidth = 100;"));
+        assert!(printed.contains("this.x = -5;"));
+        assert!(printed.contains("this.height = \"tall\";"));
+        assert!(printed.contains("this.ñame = \"x\";"));
+
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].format_message_english(), "'tall' is not a valid value for the property 'height' of type 'Number'.");
+    }
+}
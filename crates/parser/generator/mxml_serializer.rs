@@ -0,0 +1,291 @@
+use crate::ns::*;
+
+/// Serializes MXML trees as source text.
+///
+/// In the default mode, namespace declarations, attribute order,
+/// CDATA sections, comments and processing instructions are preserved,
+/// and character data is written as is, escaped by `escape_xml`. Whitespace
+/// dropped by the parser, as per the `ignore_xml_whitespace` option,
+/// is not restored.
+///
+/// In the formatting mode, given by `MxmlFormatOptions`, every element,
+/// comment, CDATA section and processing instruction is written on its
+/// own line, and elements containing solely character data are
+/// written on a single line.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let source = r#"<s:Group xmlns:s="library://ns.adobe.com/flex/spark"><s:Label text="a &amp; b"/></s:Group>"#;
+/// let compilation_unit = CompilationUnit::new(None, source.into());
+/// let mxml = ParserFacade(&compilation_unit, default()).parse_mxml();
+/// assert_eq!(MxmlSerializer::serialize(&mxml), source);
+/// assert_eq!(MxmlSerializer::format(&mxml, &default()), "<s:Group xmlns:s=\"library://ns.adobe.com/flex/spark\">\n    <s:Label text=\"a &amp; b\"/>\n</s:Group>\n");
+/// ```
+pub struct MxmlSerializer<'a> {
+    output: String,
+    format: Option<&'a MxmlFormatOptions>,
+    indent: usize,
+}
+
+/// Options for formatting MXML.
+pub struct MxmlFormatOptions {
+    /// The characters used for one level of indentation. Default: four spaces.
+    pub indent: String,
+    /// The maximum number of attributes written on the same line as their
+    /// element name. Elements with more attributes have each attribute
+    /// written on its own line. Default: 4.
+    pub max_inline_attributes: usize,
+    /// Indicates whether to order attributes as namespace declarations,
+    /// `id`, properties, then events, preserving the relative order
+    /// of each group. Default: true.
+    pub order_attributes: bool,
+    /// Resolves components and their events for ordering attributes.
+    /// Without a resolver, every attribute other than `id` and namespace
+    /// declarations is ordered as a property. Default: none.
+    pub resolver: Option<Rc<dyn MxmlComponentResolver>>,
+}
+
+impl Default for MxmlFormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: "    ".into(),
+            max_inline_attributes: 4,
+            order_attributes: true,
+            resolver: None,
+        }
+    }
+}
+
+impl<'a> MxmlSerializer<'a> {
+    /// Serializes a MXML document, preserving its structure.
+    pub fn serialize(document: &Mxml) -> String {
+        let mut serializer = MxmlSerializer { output: String::new(), format: None, indent: 0 };
+        for content in document.content.iter() {
+            serializer.write_content(content);
+        }
+        serializer.output
+    }
+
+    /// Serializes a MXML element, preserving its structure.
+    pub fn serialize_element(element: &MxmlElement) -> String {
+        let mut serializer = MxmlSerializer { output: String::new(), format: None, indent: 0 };
+        serializer.write_element(element);
+        serializer.output
+    }
+
+    /// Formats a MXML document.
+    pub fn format(document: &Mxml, options: &MxmlFormatOptions) -> String {
+        let mut serializer = MxmlSerializer { output: String::new(), format: Some(options), indent: 0 };
+        for content in document.content.iter() {
+            serializer.write_content(content);
+        }
+        serializer.output
+    }
+
+    /// Formats a MXML element.
+    pub fn format_element(element: &MxmlElement, options: &MxmlFormatOptions) -> String {
+        let mut serializer = MxmlSerializer { output: String::new(), format: Some(options), indent: 0 };
+        serializer.write_element(element);
+        serializer.output
+    }
+
+    fn write(&mut self, text: &str) {
+        self.output.push_str(text);
+    }
+
+    fn write_indent(&mut self) {
+        if let Some(format) = self.format {
+            for _ in 0..self.indent {
+                self.output.push_str(&format.indent);
+            }
+        }
+    }
+
+    fn write_line_break(&mut self) {
+        if self.format.is_some() {
+            self.output.push('\n');
+        }
+    }
+
+    /// Writes a content node. In the formatting mode, the node
+    /// is written on its own line.
+    fn write_content(&mut self, content: &MxmlContent) {
+        match content {
+            MxmlContent::Characters((text, _)) => {
+                if self.format.is_some() {
+                    if text.trim().is_empty() {
+                        return;
+                    }
+                    self.write_indent();
+                    self.write(&escape_xml(text.trim()));
+                    self.write_line_break();
+                } else {
+                    self.write(&escape_xml(text));
+                }
+            },
+            MxmlContent::CData((markup, _)) |
+            MxmlContent::Comment((markup, _)) => {
+                self.write_indent();
+                self.write(markup);
+                self.write_line_break();
+            },
+            MxmlContent::ProcessingInstruction { name, data, .. } => {
+                self.write_indent();
+                self.write("<?");
+                self.write(name);
+                if let Some(data) = data {
+                    self.write(data);
+                }
+                self.write("?>");
+                self.write_line_break();
+            },
+            MxmlContent::Element(element) => {
+                self.write_indent();
+                self.write_element(element);
+                self.write_line_break();
+            },
+        }
+    }
+
+    /// Writes an element starting at the current position, leaving
+    /// the cursor after its closing tag.
+    fn write_element(&mut self, element: &MxmlElement) {
+        self.write("<");
+        self.write_name(&element.name);
+
+        let attributes = self.ordered_attributes(element);
+        let wrap = self.format.is_some_and(|format| attributes.len() > format.max_inline_attributes);
+        for attribute in attributes.iter() {
+            if wrap {
+                self.write("\n");
+                self.indent += 1;
+                self.write_indent();
+                self.indent -= 1;
+            } else {
+                self.write(" ");
+            }
+            self.write_name(&attribute.name);
+            self.write("=\"");
+            self.write(&escape_xml(&attribute.value.0));
+            self.write("\"");
+        }
+
+        let Some(content) = &element.content else {
+            self.write("/>");
+            return;
+        };
+        self.write(">");
+
+        let inline = self.format.is_none() || content.iter().all(|node| matches!(node.as_ref(), MxmlContent::Characters(_)));
+        if inline {
+            for node in content.iter() {
+                if let (MxmlContent::Characters((text, _)), Some(_)) = (node.as_ref(), self.format) {
+                    self.write(&escape_xml(text.trim()));
+                } else {
+                    self.write_content(node);
+                }
+            }
+        } else {
+            self.write_line_break();
+            self.indent += 1;
+            for node in content.iter() {
+                self.write_content(node);
+            }
+            self.indent -= 1;
+            self.write_indent();
+        }
+
+        self.write("</");
+        self.write_name(element.closing_name.as_ref().unwrap_or(&element.name));
+        self.write(">");
+    }
+
+    fn write_name(&mut self, name: &MxmlName) {
+        if let Some(prefix) = &name.prefix {
+            self.write(prefix);
+            self.write(":");
+        }
+        self.write(&name.name);
+    }
+
+    /// Returns the attributes of an element in source order or,
+    /// in the formatting mode, in the configured order.
+    fn ordered_attributes(&self, element: &MxmlElement) -> Vec<Rc<MxmlAttribute>> {
+        let mut attributes = element.attributes.clone();
+        attributes.sort_by_key(|attribute| attribute.location.first_offset());
+        let Some(format) = self.format.filter(|format| format.order_attributes) else {
+            return attributes;
+        };
+        let class_name = format.resolver.as_ref().and_then(|resolver| {
+            let (uri, local_name) = element.name.resolve_name(&element.namespace).ok()?;
+            resolver.resolve_component(&uri, &local_name)
+        });
+        attributes.sort_by_key(|attribute| {
+            if attribute.xmlns {
+                0
+            } else if attribute.name.prefix.is_none() && attribute.name.name == "id" {
+                1
+            } else if attribute.name.prefix.is_none() && class_name.as_ref().is_some_and(|class_name| {
+                format.resolver.as_ref().unwrap().event_type(class_name, &attribute.name.name).is_some()
+            }) {
+                3
+            } else {
+                2
+            }
+        });
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_serialize_and_format() {
+        let source = r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- Main view -->
+<s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark"><fx:Script><![CDATA[
+    var x = 10;
+]]></fx:Script><s:Button click="go()" label="Go" id="button" width="100" height="20"/><s:Label>1 &lt; 2</s:Label></s:Application>"#;
+        let compilation_unit = CompilationUnit::new(None, source.into());
+        let mxml = ParserFacade(&compilation_unit, default()).parse_mxml();
+        assert!(!compilation_unit.invalidated());
+
+        // Serialization preserves the structure, except for dropped whitespace
+        assert_eq!(MxmlSerializer::serialize(&mxml), source.replace(">\n<", "><"));
+
+        let mut resolver = MxmlComponentMap::new();
+        resolver.add_component("library://ns.adobe.com/flex/spark", "Button", "spark.components.Button");
+        resolver.add_event("spark.components.Button", "click", "flash.events.MouseEvent");
+        let formatted = MxmlSerializer::format(&mxml, &MxmlFormatOptions {
+            resolver: Some(Rc::new(resolver)),
+            ..default()
+        });
+        assert_eq!(formatted, r#"<?xml version="1.0" encoding="utf-8"?>
+<!-- Main view -->
+<s:Application xmlns:fx="http://ns.adobe.com/mxml/2009" xmlns:s="library://ns.adobe.com/flex/spark">
+    <fx:Script>
+        <![CDATA[
+    var x = 10;
+]]>
+    </fx:Script>
+    <s:Button
+        id="button"
+        label="Go"
+        width="100"
+        height="20"
+        click="go()"/>
+    <s:Label>1 &lt; 2</s:Label>
+</s:Application>
+"#);
+
+        // Formatting is stable
+        let compilation_unit = CompilationUnit::new(None, formatted.clone());
+        let mxml = ParserFacade(&compilation_unit, default()).parse_mxml();
+        assert_eq!(MxmlSerializer::format(&mxml, &default()), formatted);
+    }
+}
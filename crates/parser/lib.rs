@@ -7,5 +7,7 @@ pub mod diagnostics;
 pub mod operator;
 pub mod parser;
 pub mod util;
+pub mod analysis;
+pub mod generator;
 
 pub mod ns;
\ No newline at end of file
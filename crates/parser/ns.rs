@@ -5,4 +5,6 @@ pub use crate::compilation_unit::*;
 pub use crate::diagnostics::*;
 pub use crate::operator::*;
 pub use crate::parser::*;
-pub use crate::util::*;
\ No newline at end of file
+pub use crate::util::*;
+pub use crate::analysis::*;
+pub use crate::generator::*;
\ No newline at end of file
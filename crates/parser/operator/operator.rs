@@ -51,6 +51,66 @@ pub enum Operator {
     NullCoalescing,
 }
 
+impl Operator {
+    /// Indicates whether the operator is a postfix unary operator.
+    pub fn is_postfix(&self) -> bool {
+        matches!(self, Self::PostIncrement | Self::PostDecrement | Self::NonNull)
+    }
+}
+
+impl std::fmt::Display for Operator {
+    /// Writes the operator as it appears in source text.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::PostIncrement => "++",
+            Self::PostDecrement => "--",
+            Self::NonNull => "!",
+            Self::Delete => "delete",
+            Self::Void => "void",
+            Self::Typeof => "typeof",
+            Self::Await => "await",
+            Self::Yield => "yield",
+            Self::PreIncrement => "++",
+            Self::PreDecrement => "--",
+            Self::Positive => "+",
+            Self::Negative => "-",
+            Self::BitwiseNot => "~",
+            Self::LogicalNot => "!",
+
+            Self::Power => "**",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Remainder => "%",
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+            Self::ShiftRightUnsigned => ">>>",
+            Self::Lt => "<",
+            Self::Gt => ">",
+            Self::Le => "<=",
+            Self::Ge => ">=",
+            Self::Instanceof => "instanceof",
+            Self::In => "in",
+            Self::NotIn => "not in",
+            Self::Is => "is",
+            Self::IsNot => "is not",
+            Self::As => "as",
+            Self::Equals => "==",
+            Self::NotEquals => "!=",
+            Self::StrictEquals => "===",
+            Self::StrictNotEquals => "!==",
+            Self::BitwiseAnd => "&",
+            Self::BitwiseXor => "^",
+            Self::BitwiseOr => "|",
+            Self::LogicalAnd => "&&",
+            Self::LogicalXor => "^^",
+            Self::LogicalOr => "||",
+            Self::NullCoalescing => "??",
+        })
+    }
+}
+
 /// Represents binary operator associativity.
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum BinaryAssociativity {
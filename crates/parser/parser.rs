@@ -15,8 +15,18 @@ mod css_parser;
 pub use css_parser::*;
 mod css_tokenizer;
 pub use css_tokenizer::*;
+mod css_style_sheet_loader;
+pub use css_style_sheet_loader::*;
+mod fxg_parser;
+pub use fxg_parser::*;
+mod mxml_event_registry;
+pub use mxml_event_registry::*;
+mod asdoc_tag_registry;
+pub use asdoc_tag_registry::*;
 mod parser_error;
 pub use parser_error::*;
+mod source_resolver;
+pub use source_resolver::*;
 mod token;
 pub use token::*;
 mod tokenizer;
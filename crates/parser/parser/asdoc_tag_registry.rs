@@ -0,0 +1,85 @@
+use crate::ns::*;
+
+/// Describes how the content of a custom ASDoc tag is parsed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AsDocTagShape {
+    /// Free text, as in `@tag text`.
+    Text,
+    /// A reference, as in `@tag com.x.Y#z`.
+    Reference,
+    /// A name followed by a description, as in `@tag name description`.
+    NameAndDescription,
+    /// A tag without content, as in `@tag`.
+    Flag,
+}
+
+/// Registry of custom ASDoc tags recognized by the parser
+/// in addition to the built-in tags.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let mut tags = AsDocTagRegistry::new();
+/// tags.register("mxmlDefault", AsDocTagShape::Text);
+/// let options = ParserOptions {
+///     custom_asdoc_tags: Rc::new(tags),
+///     ..default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AsDocTagRegistry {
+    tags: HashMap<String, AsDocTagShape>,
+}
+
+impl AsDocTagRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a custom tag, replacing any previous registration
+    /// of the same name.
+    pub fn register(&mut self, name: &str, shape: AsDocTagShape) {
+        self.tags.insert(name.to_owned(), shape);
+    }
+
+    /// Returns the shape of a registered tag.
+    pub fn get(&self, name: &str) -> Option<AsDocTagShape> {
+        self.tags.get(name).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_custom_tags() {
+        let mut tags = AsDocTagRegistry::new();
+        tags.register("mxmlDefault", AsDocTagShape::Text);
+        tags.register("related", AsDocTagShape::Reference);
+        tags.register("skin", AsDocTagShape::NameAndDescription);
+        tags.register("sealed", AsDocTagShape::Flag);
+        let source = "/**\n * @mxmlDefault content\n * @related C#x\n * @skin background Background.\n * @sealed\n */\nvar x;";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let program = ParserFacade(&compilation_unit, ParserOptions {
+            custom_asdoc_tags: Rc::new(tags),
+            ..default()
+        }).parse_program();
+        assert!(!compilation_unit.invalidated());
+        let Directive::VariableDefinition(defn) = program.directives[0].as_ref() else {
+            panic!();
+        };
+        let tags = &defn.asdoc.as_ref().unwrap().tags;
+        assert_eq!(tags.len(), 4);
+        assert!(matches!(&tags[0].0, AsDocTag::Custom { content: AsDocCustomTagContent::Text(text), .. } if text == "content"));
+        assert!(matches!(&tags[1].0, AsDocTag::Custom { content: AsDocCustomTagContent::Reference(_), .. }));
+        assert!(matches!(&tags[2].0, AsDocTag::Custom { content: AsDocCustomTagContent::NameAndDescription { name, .. }, .. } if name == "background"));
+        assert!(matches!(&tags[3].0, AsDocTag::Custom { name, content: AsDocCustomTagContent::Flag } if name == "sealed"));
+    }
+}
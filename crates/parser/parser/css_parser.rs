@@ -142,11 +142,14 @@ impl<'input> CssParser<'input> {
         }
     }
 
-    fn expect_unitless_number(&mut self) -> Option<f64> {
-        if let Token::CssNumber { value, .. } = self.token.0.clone() {
+    /// Expects a number, returning its value and unit. Percentages
+    /// are returned as ratios with the `%` unit.
+    fn expect_number(&mut self) -> Option<CssNumberAndUnit> {
+        if let Token::CssNumber { value, unit } = self.token.0.clone() {
             self.expecting_token_error = false;
+            let unit = if self.token.1.text().ends_with('%') { Some("%".to_owned()) } else { unit };
             self.next();
-            Some(value)
+            Some((value, unit))
         } else {
             self.expecting_token_error = true;
             self.add_syntax_error(&self.token_location(), DiagnosticKind::Unexpected, diagarg![self.token.0.clone()]);
@@ -229,7 +232,12 @@ impl<'input> CssParser<'input> {
         } else if self.peek(Token::CssAtNamespace) {
             self.mark_location();
             self.next();
-            let prefix = self.expect_identifier();
+            // @namespace "uri" defines the default namespace
+            let prefix = if let Token::String(_) = self.token.0 {
+                (String::new(), Location::with_offset(self.compilation_unit(), self.token.1.first_offset()))
+            } else {
+                self.expect_identifier()
+            };
             let uri = if self.expecting_token_error {
                 (String::new(), self.tokenizer.cursor_location())
             } else {
@@ -248,6 +256,8 @@ impl<'input> CssParser<'input> {
             self.parse_media_query()
         } else if self.peek(Token::CssAtFontFace) {
             self.parse_font_face()
+        } else if self.peek(Token::CssAtImport) {
+            self.parse_import()
         } else {
             self.add_syntax_error(&self.token.1, DiagnosticKind::ExpectingDirective, diagarg![self.token.0.clone()]);
             let d = self.create_invalidated_directive(&self.tokenizer.cursor_location());
@@ -296,6 +306,45 @@ impl<'input> CssParser<'input> {
         }))
     }
 
+    fn parse_import(&mut self) -> Rc<CssDirective> {
+        self.mark_location();
+        self.next();
+        let url = if self.peek_keyword("url") {
+            self.next();
+            if self.peek(Token::ParenOpen) {
+                self.parse_arguments().unwrap().parse_text()
+            } else {
+                // Recover from @import url "file.css";
+                self.add_syntax_error(&self.token.1, DiagnosticKind::Expecting, diagarg![Token::ParenOpen, self.token.0.clone()]);
+                self.expect_string()
+            }
+        } else {
+            self.expect_string()
+        };
+        let mut media: Vec<Rc<CssMediaQueryCondition>> = vec![];
+        if !self.expecting_token_error {
+            loop {
+                if let Some(condition) = self.parse_opt_media_query_condition() {
+                    media.push(condition);
+                } else if self.eof() || self.peek(Token::CssSemicolons) {
+                    break;
+                } else {
+                    self.add_syntax_error(&self.token.1, DiagnosticKind::Unexpected, diagarg![self.token.0.clone()]);
+                    self.next();
+                }
+                if !self.consume(Token::Comma) {
+                    break;
+                }
+            }
+            self.expect(Token::CssSemicolons);
+        }
+        Rc::new(CssDirective::Import(CssImport {
+            location: self.pop_location(),
+            url,
+            media,
+        }))
+    }
+
     fn parse_font_face(&mut self) -> Rc<CssDirective> {
         self.mark_location();
         self.next();
@@ -596,19 +645,26 @@ impl<'input> CssParser<'input> {
         } else if let Some(id) = self.peek_identifier() {
             self.mark_location();
             self.next();
-            let color_int = css_color_constant_to_int(&id.0);
-            // COLOR_NAME such as "red"
-            if let Some(color_int) = color_int {
+            let color = CssColor::from_name(&id.0);
+            // COLOR_NAME such as "red" or "transparent"
+            if let Some(color) = color {
                 base = Some(Rc::new(CssPropertyValue::Color(CssColorPropertyValue {
                     location: self.pop_location(),
-                    color_int,
+                    color_int: color.rgb,
+                    alpha: color.alpha,
                 })));
             // rgb(...)
-            } else if id.0 == "rgb" && self.peek(Token::ParenOpen) {
-                if let Some(color_int) = self.parse_arguments().unwrap().parse_rgb() {
+            // rgba(...)
+            // hsl(...)
+            // hsla(...)
+            } else if ["rgb", "rgba", "hsl", "hsla"].contains(&id.0.as_str()) && self.peek(Token::ParenOpen) {
+                let arguments = self.parse_arguments().unwrap();
+                let color = if id.0.starts_with("rgb") { arguments.parse_rgb() } else { arguments.parse_hsl() };
+                if let Some(color) = color {
                     base = Some(Rc::new(CssPropertyValue::RgbColor(CssRgbColorPropertyValue {
                         location: self.pop_location(),
-                        color_int,
+                        color_int: color.rgb,
+                        alpha: color.alpha,
                     })));
                 } else {
                     let loc = self.pop_location();
@@ -732,13 +788,21 @@ impl<'input> CssParser<'input> {
     }
 }
 
-fn rgb_bytes_to_integer(r: f64, g: f64, b: f64) -> u32 {
-    (calc_rgb_byte(r) << 16) | (calc_rgb_byte(g) << 8) | calc_rgb_byte(b)
+/// A number along with its unit.
+type CssNumberAndUnit = (f64, Option<String>);
+
+/// Converts a saturation or lightness into a ratio; percentages
+/// are already ratios, whereas plain numbers range from 0 to 100.
+fn percentage_to_ratio((value, unit): CssNumberAndUnit) -> f64 {
+    if unit.as_deref() == Some("%") { value } else { value / 100.0 }
 }
 
-fn calc_rgb_byte(value: f64) -> u32 {
+fn calc_rgb_byte((value, unit): CssNumberAndUnit) -> u32 {
+    // Percentage
+    if unit.as_deref() == Some("%") {
+        (value * 255.0).round().to_u32().unwrap_or(0).clamp(0, 255)
     // Integer
-    if value.round() == value {
+    } else if value.round() == value {
         value.round().to_u32().unwrap_or(0).clamp(0, 255)
     // Float
     } else {
@@ -813,22 +877,50 @@ impl<'input> CssParserFacade<'input> {
         r
     }
 
-    pub fn parse_rgb(&self) -> Option<u32> {
+    /// Parses the arguments of `rgb()` or `rgba()` until end-of-file.
+    pub fn parse_rgb(&self) -> Option<CssColor> {
+        let ([r, g, b], alpha) = self.parse_color_components()?;
+        Some(CssColor::new((calc_rgb_byte(r) << 16) | (calc_rgb_byte(g) << 8) | calc_rgb_byte(b), alpha))
+    }
+
+    /// Parses the arguments of `hsl()` or `hsla()` until end-of-file.
+    pub fn parse_hsl(&self) -> Option<CssColor> {
+        let ([(hue, hue_unit), saturation, lightness], alpha) = self.parse_color_components()?;
+        let hue = match hue_unit.as_deref() {
+            Some("rad") => hue.to_degrees(),
+            Some("turn") => hue * 360.0,
+            _ => hue,
+        };
+        Some(CssColor::from_hsl(hue, percentage_to_ratio(saturation), percentage_to_ratio(lightness), alpha))
+    }
+
+    /// Parses three color components and an optional alpha ratio, either
+    /// separated by commas or by whitespace with the alpha preceded by `/`.
+    fn parse_color_components(&self) -> Option<([CssNumberAndUnit; 3], f64)> {
         let mut parser = self.create_parser();
         parser.next();
-        let r = parser.expect_unitless_number()?;
-        let g: f64;
-        let b: f64;
-        if parser.consume(Token::Comma) {
-            g = parser.expect_unitless_number()?;
-            parser.expect(Token::Comma);
-            b = parser.expect_unitless_number()?;
-        } else {
-            g = parser.expect_unitless_number()?;
-            b = parser.expect_unitless_number()?;
+        let first = parser.expect_number()?;
+        let comma_separated = parser.peek(Token::Comma);
+        let mut components = vec![first];
+        for i in 1..4 {
+            if i == 3 && parser.eof() {
+                break;
+            }
+            if comma_separated {
+                parser.expect(Token::Comma);
+            } else if i == 3 {
+                parser.expect(Token::Div);
+            }
+            components.push(parser.expect_number()?);
         }
         parser.expect_eof();
-        Some(rgb_bytes_to_integer(r, g, b))
+        let alpha = match components.get(3) {
+            Some((value, _)) => *value,
+            None => 1.0,
+        };
+        components.truncate(3);
+        let rgb: [CssNumberAndUnit; 3] = components.try_into().ok()?;
+        Some((rgb, alpha.clamp(0.0, 1.0)))
     }
 
     pub fn parse_embed_entries(&self) -> Vec<Rc<CssEmbedEntry>> {
@@ -845,3 +937,41 @@ impl<'input> CssParserFacade<'input> {
         entries
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_css_colors() {
+        let source = ".a { a: #f008; b: #FF000080; c: rgba(255, 0, 0, 0.5); d: rgb(100% 0% 0% / 50%); e: hsl(120, 100%, 25%); f: hsla(0.5turn, 100%, 50%, 0); g: transparent; h: indigo; i: IndianRed }";
+        let compilation_unit = CompilationUnit::new(None, source.into());
+        let document = CssParserFacade(&compilation_unit, default()).parse_document();
+        assert!(compilation_unit.diagnostics().is_empty());
+        let CssDirective::Rule(rule) = document.directives[0].as_ref() else { panic!() };
+        let colors: Vec<String> = rule.properties.iter().map(|property| property.value.as_color().unwrap().to_hex()).collect();
+        assert_eq!(colors, ["#ff000088", "#ff000080", "#ff000080", "#ff000080", "#008000", "#00ffff00", "#00000000", "#4b0082", "#cd5c5c"]);
+        assert_eq!(rule.properties[2].value.as_color().unwrap().to_flex_styles(), (0xff0000, 0.5));
+
+        let compilation_unit = CompilationUnit::new(None, ".a { a: rgba(255, 0, 0, 1); b: rgba(255, 0, 0, 50%) }".into());
+        let document = CssParserFacade(&compilation_unit, default()).parse_document();
+        let CssDirective::Rule(rule) = document.directives[0].as_ref() else { panic!() };
+        assert_eq!(rule.properties[0].value.as_color().unwrap().to_flex_styles(), (0xff0000, 1.0));
+        assert_eq!(rule.properties[1].value.as_color().unwrap().to_flex_styles(), (0xff0000, 0.5));
+
+        let location = Location::with_offset(&compilation_unit, 0);
+        let raw_color = |alpha: &str| CssRgbColorPropertyValue::from_raw_arguments(&location, &["255".into(), "0".into(), "0".into(), alpha.into()]).unwrap().color();
+        assert_eq!(raw_color("1").to_flex_styles(), (0xff0000, 1.0));
+        assert_eq!(raw_color("50%").to_flex_styles(), (0xff0000, 0.5));
+    }
+    #[test]
+    fn test_css_import_without_parentheses() {
+        for source in ["@import url;", "@import url \"a.css\";"] {
+            let compilation_unit = CompilationUnit::new(None, source.into());
+            CssParserFacade(&compilation_unit, default()).parse_document();
+            let diagnostics = compilation_unit.diagnostics();
+            assert_eq!(diagnostics.len(), 1);
+            assert!(diagnostics[0].kind() == DiagnosticKind::Expecting);
+        }
+    }
+}
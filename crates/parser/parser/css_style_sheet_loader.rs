@@ -0,0 +1,249 @@
+use crate::ns::*;
+
+/// A parsed style sheet along with the style sheets
+/// it imports through `@import` directives.
+pub struct CssStyleSheet {
+    pub compilation_unit: Rc<CompilationUnit>,
+    pub document: Rc<CssDocument>,
+    /// Imported style sheets along with their `@import` directives,
+    /// in source order. Imports that failed to resolve or that are
+    /// circular are not included.
+    pub imports: Vec<(Rc<CssDirective>, Rc<CssStyleSheet>)>,
+}
+
+impl CssStyleSheet {
+    /// Returns the style sheet with its imports expanded in place.
+    /// Rules of imports with a media query list are wrapped in
+    /// `@media` queries with the same conditions.
+    pub fn flatten(&self) -> CssDocument {
+        let mut directives = vec![];
+        self.flatten_into(&[], &mut directives);
+        CssDocument {
+            location: self.document.location.clone(),
+            directives,
+        }
+    }
+
+    fn flatten_into(&self, media: &[Rc<CssMediaQueryCondition>], output: &mut Vec<Rc<CssDirective>>) {
+        // Rules pending wrapping into a `@media` query.
+        let mut rules: Vec<Rc<CssRule>> = vec![];
+        for directive in self.document.directives.iter() {
+            if media.is_empty() {
+                match directive.as_ref() {
+                    CssDirective::Import(import) => {
+                        if let Some((_, style_sheet)) = self.imports.iter().find(|(d, _)| Rc::ptr_eq(d, directive)) {
+                            style_sheet.flatten_into(&import.media, output);
+                        }
+                    },
+                    _ => output.push(directive.clone()),
+                }
+                continue;
+            }
+            if let CssDirective::Rule(rule) = directive.as_ref() {
+                rules.push(Rc::new(rule.clone()));
+                continue;
+            }
+            wrap_media_rules(media, &mut rules, output);
+            match directive.as_ref() {
+                CssDirective::Import(import) => {
+                    if let Some((_, style_sheet)) = self.imports.iter().find(|(d, _)| Rc::ptr_eq(d, directive)) {
+                        let media = if import.media.is_empty() { media.to_vec() } else { combine_media_conditions(media, &import.media) };
+                        style_sheet.flatten_into(&media, output);
+                    }
+                },
+                CssDirective::MediaQuery(query) => {
+                    output.push(Rc::new(CssDirective::MediaQuery(CssMediaQuery {
+                        location: query.location.clone(),
+                        conditions: combine_media_conditions(media, &query.conditions),
+                        rules: query.rules.clone(),
+                    })));
+                },
+                _ => output.push(directive.clone()),
+            }
+        }
+        wrap_media_rules(media, &mut rules, output);
+    }
+}
+
+fn wrap_media_rules(media: &[Rc<CssMediaQueryCondition>], rules: &mut Vec<Rc<CssRule>>, output: &mut Vec<Rc<CssDirective>>) {
+    if rules.is_empty() {
+        return;
+    }
+    let location = rules[0].location.combine_with(rules.last().unwrap().location.clone());
+    output.push(Rc::new(CssDirective::MediaQuery(CssMediaQuery {
+        location,
+        conditions: media.to_vec(),
+        rules: std::mem::take(rules),
+    })));
+}
+
+/// Combines two media query lists such that the result applies
+/// where both apply.
+fn combine_media_conditions(outer: &[Rc<CssMediaQueryCondition>], inner: &[Rc<CssMediaQueryCondition>]) -> Vec<Rc<CssMediaQueryCondition>> {
+    let mut result = vec![];
+    for left in outer.iter() {
+        for right in inner.iter() {
+            result.push(Rc::new(CssMediaQueryCondition::And {
+                location: right.location(),
+                left: left.clone(),
+                right: right.clone(),
+            }));
+        }
+    }
+    result
+}
+
+/// Loads a style sheet along with the style sheets it imports.
+///
+/// Imported style sheets are resolved relative to the importing
+/// compilation unit through the `source_resolver` of the parser options,
+/// and are added as nested compilation units of the importing compilation
+/// unit, so that `nested_diagnostics()` includes their diagnostics.
+/// Circular imports are reported as syntax errors.
+///
+/// # Example
+///
+/// ```no_run
+/// use as3_parser::ns::*;
+///
+/// let compilation_unit = CompilationUnit::new(Some("theme/defaults.css".into()), std::fs::read_to_string("theme/defaults.css").unwrap());
+/// let style_sheet = CssStyleSheetLoader(&compilation_unit, default()).load();
+/// let document = style_sheet.flatten();
+/// for diagnostic in compilation_unit.nested_diagnostics() {
+///     println!("{}", diagnostic.format_english());
+/// }
+/// ```
+pub struct CssStyleSheetLoader<'input>(pub &'input Rc<CompilationUnit>, pub ParserOptions);
+
+impl<'input> CssStyleSheetLoader<'input> {
+    pub fn load(&self) -> Rc<CssStyleSheet> {
+        self.load_compilation_unit(self.0, self.options(self.1.byte_range))
+    }
+
+    /// Returns the loader options with the given byte range, which
+    /// applies to the loaded style sheet but not to its imports.
+    fn options(&self, byte_range: Option<(usize, usize)>) -> ParserOptions {
+        ParserOptions {
+            ignore_xml_whitespace: self.1.ignore_xml_whitespace,
+            byte_range,
+            custom_asdoc_tags: self.1.custom_asdoc_tags.clone(),
+            parse_mxml_scripts: self.1.parse_mxml_scripts,
+            parse_mxml_bindings: self.1.parse_mxml_bindings,
+            parse_mxml_styles: self.1.parse_mxml_styles,
+            mxml_events: self.1.mxml_events.clone(),
+            source_resolver: self.1.source_resolver.clone(),
+        }
+    }
+
+    fn load_compilation_unit(&self, compilation_unit: &Rc<CompilationUnit>, options: ParserOptions) -> Rc<CssStyleSheet> {
+        let document = CssParserFacade(compilation_unit, options).parse_document();
+        let mut imports = vec![];
+        for directive in document.directives.iter() {
+            let CssDirective::Import(import) = directive.as_ref() else {
+                continue;
+            };
+            if import.url.0.is_empty() {
+                continue;
+            }
+            let (file_path, content) = match self.1.source_resolver.resolve(&import.url.0, compilation_unit) {
+                Ok(result) => result,
+                Err(error) => {
+                    compilation_unit.add_diagnostic(Diagnostic::new_syntax_error(&import.url.1, match error {
+                        SourceResolutionError::ParentSourceIsNotAFile => DiagnosticKind::ParentSourceIsNotAFile,
+                        SourceResolutionError::FailedToReadFile => DiagnosticKind::FailedToIncludeFile,
+                    }, vec![]));
+                    continue;
+                },
+            };
+            if is_circular_import(compilation_unit, &file_path) {
+                compilation_unit.add_diagnostic(Diagnostic::new_syntax_error(&import.url.1, DiagnosticKind::CircularCssImport, diagarg![import.url.0.clone()]));
+                continue;
+            }
+            let nested_compilation_unit = CompilationUnit::new(Some(file_path), content);
+            nested_compilation_unit.set_compiler_options(compilation_unit.compiler_options());
+            compilation_unit.add_nested_compilation_unit(nested_compilation_unit.clone());
+            imports.push((directive.clone(), self.load_compilation_unit(&nested_compilation_unit, self.options(None))));
+        }
+        Rc::new(CssStyleSheet {
+            compilation_unit: compilation_unit.clone(),
+            document,
+            imports,
+        })
+    }
+}
+
+/// Indicates whether a file path is that of a compilation unit
+/// or of any of the compilation units importing it.
+fn is_circular_import(compilation_unit: &Rc<CompilationUnit>, file_path: &str) -> bool {
+    let mut unit = Some(compilation_unit.clone());
+    while let Some(u) = unit {
+        if u.file_path().as_deref() == Some(file_path) {
+            return true;
+        }
+        unit = u.included_from();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    struct MapSourceResolver(HashMap<String, String>);
+
+    impl SourceResolver for MapSourceResolver {
+        fn resolve(&self, source: &str, _origin: &Rc<CompilationUnit>) -> Result<(String, String), SourceResolutionError> {
+            self.0.get(source).map(|content| (source.to_owned(), content.clone())).ok_or(SourceResolutionError::FailedToReadFile)
+        }
+    }
+
+    #[test]
+    fn test_css_imports() {
+        let files = [
+            ("colors.css", "@import \"base.css\";\n.a { color: red }"),
+            ("mobile.css", "@import url('colors.css');\n.b { color: blue }\n@media (os-platform: \"IOS\") { .c { color: green } }"),
+            ("base.css", "@import 'mobile.css';\n.base { color: white }"),
+        ];
+        let options = ParserOptions {
+            source_resolver: Rc::new(MapSourceResolver(files.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect())),
+            ..default()
+        };
+        let compilation_unit = CompilationUnit::new(Some("main.css".into()), "@import url(\"colors.css\");\n@import \"mobile.css\" (application-dpi: 160), print;\n@import \"missing.css\";\n.main { color: black }".into());
+        let style_sheet = CssStyleSheetLoader(&compilation_unit, options).load();
+
+        let CssDirective::Import(import) = style_sheet.document.directives[1].as_ref() else { panic!() };
+        assert_eq!(import.url.0, "mobile.css");
+        assert_eq!(import.media.len(), 2);
+        assert_eq!(CssSerializer::minify_directive(&style_sheet.document.directives[1]), "@import url(\"mobile.css\") (application-dpi:160),print;");
+
+        // The import cycle between colors.css, base.css and mobile.css
+        // is reported once under each import of main.css.
+        assert_eq!(style_sheet.imports.len(), 2);
+        assert_eq!(compilation_unit.nested_compilation_units().len(), 2);
+        let diagnostics = compilation_unit.nested_diagnostics();
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics[0].kind() == DiagnosticKind::FailedToIncludeFile);
+        assert!(diagnostics[1].kind() == DiagnosticKind::CircularCssImport);
+        assert!(diagnostics[2].kind() == DiagnosticKind::CircularCssImport);
+
+        let document = style_sheet.flatten();
+        let summary: Vec<String> = document.directives.iter().map(|directive| match directive.as_ref() {
+            CssDirective::Rule(rule) => rule.location.text(),
+            CssDirective::MediaQuery(query) => format!("@media {} {}", query.conditions.len(), query.rules.len()),
+            _ => panic!(),
+        }).collect();
+        assert_eq!(summary, [
+            // colors.css > base.css > mobile.css
+            ".b { color: blue }",
+            "@media 1 1",
+            ".base { color: white }",
+            ".a { color: red }",
+            // mobile.css > colors.css > base.css, under (application-dpi: 160), print
+            "@media 2 1",
+            "@media 2 1",
+            "@media 2 1",
+            "@media 2 1",
+            ".main { color: black }",
+        ]);
+    }
+}
@@ -135,6 +135,11 @@ impl<'input> CssTokenizer<'input> {
                 self.characters.skip_count_in_place(6);
                 return (Token::CssAtMedia, start.combine_with(self.cursor_location()));
             }
+            // @import
+            if self.characters.peek_seq(7) == "@import" {
+                self.characters.skip_count_in_place(7);
+                return (Token::CssAtImport, start.combine_with(self.cursor_location()));
+            }
         }
 
         if ch == '!' && self.characters.peek_seq(10) == "!important" {
@@ -266,6 +271,10 @@ impl<'input> CssTokenizer<'input> {
                 self.characters.next();
                 (Token::Plus, start.combine_with(self.cursor_location()))
             },
+            '/' => {
+                self.characters.next();
+                (Token::Div, start.combine_with(self.cursor_location()))
+            },
             _ => {
                 if self.characters.reached_end() {
                     return (Token::Eof, start);
@@ -0,0 +1,512 @@
+use crate::ns::*;
+
+/// The FXG namespace.
+pub const FXG_NAMESPACE: &str = "http://ns.adobe.com/fxg/2008";
+
+#[derive(Copy, Clone)]
+enum FxgValueType {
+    Number,
+    Color,
+    Boolean,
+    String,
+    Path,
+    Enumeration(&'static [&'static str]),
+}
+
+impl FxgValueType {
+    fn description(&self) -> String {
+        match self {
+            Self::Number => "a number".into(),
+            Self::Color => "a #RRGGBB color".into(),
+            Self::Boolean => "'true' or 'false'".into(),
+            Self::String => "a string".into(),
+            Self::Path => "path data".into(),
+            Self::Enumeration(values) => format!("one of {}", values.iter().map(|v| format!("'{v}'")).collect::<Vec<_>>().join(", ")),
+        }
+    }
+}
+
+struct FxgElementSchema {
+    attributes: Vec<(&'static str, FxgValueType)>,
+    properties: &'static [&'static str],
+    /// Allowed child elements.
+    children: &'static [&'static str],
+    /// Indicates whether any attribute and content is allowed, as
+    /// for the text elements.
+    open: bool,
+}
+
+const BLEND_MODES: &[&str] = &[
+    "add", "alpha", "darken", "difference", "erase", "hardlight", "invert", "layer", "lighten",
+    "multiply", "normal", "subtract", "screen", "overlay", "colordodge", "colorburn", "exclusion",
+    "softlight", "hue", "saturation", "color", "luminosity", "auto",
+];
+const MASK_TYPES: &[&str] = &["clip", "alpha", "luminosity"];
+const WINDINGS: &[&str] = &["evenOdd", "nonZero"];
+const FILL_MODES: &[&str] = &["clip", "repeat", "scale"];
+const CAPS: &[&str] = &["round", "square", "none"];
+const JOINTS: &[&str] = &["round", "miter", "bevel"];
+const SCALE_MODES: &[&str] = &["normal", "vertical", "horizontal", "none"];
+const SPREAD_METHODS: &[&str] = &["pad", "reflect", "repeat"];
+const INTERPOLATION_METHODS: &[&str] = &["rgb", "linearRGB"];
+const FILTER_TYPES: &[&str] = &["inner", "outer", "full"];
+
+const GRAPHICS: &[&str] = &["Group", "Rect", "Ellipse", "Line", "Path", "BitmapImage", "RichText"];
+const FILLS: &[&str] = &["SolidColor", "LinearGradient", "RadialGradient", "BitmapFill"];
+const STROKES: &[&str] = &["SolidColorStroke", "LinearGradientStroke", "RadialGradientStroke"];
+const FILTERS: &[&str] = &[
+    "BlurFilter", "DropShadowFilter", "GlowFilter", "BevelFilter", "ColorMatrixFilter",
+    "GradientGlowFilter", "GradientBevelFilter",
+];
+
+fn placement_attributes() -> Vec<(&'static str, FxgValueType)> {
+    use FxgValueType::*;
+    vec![
+        ("id", String), ("x", Number), ("y", Number), ("rotation", Number),
+        ("scaleX", Number), ("scaleY", Number), ("transformX", Number), ("transformY", Number),
+        ("alpha", Number), ("visible", Boolean), ("blendMode", Enumeration(BLEND_MODES)),
+        ("maskType", Enumeration(MASK_TYPES)), ("luminosityClip", Boolean), ("luminosityInvert", Boolean),
+    ]
+}
+
+fn stroke_attributes() -> Vec<(&'static str, FxgValueType)> {
+    use FxgValueType::*;
+    vec![
+        ("weight", Number), ("caps", Enumeration(CAPS)), ("joints", Enumeration(JOINTS)),
+        ("miterLimit", Number), ("pixelHinting", Boolean), ("scaleMode", Enumeration(SCALE_MODES)),
+    ]
+}
+
+fn gradient_attributes() -> Vec<(&'static str, FxgValueType)> {
+    use FxgValueType::*;
+    vec![
+        ("x", Number), ("y", Number), ("scaleX", Number), ("rotation", Number),
+        ("spreadMethod", Enumeration(SPREAD_METHODS)), ("interpolationMethod", Enumeration(INTERPOLATION_METHODS)),
+    ]
+}
+
+fn filter_attributes() -> Vec<(&'static str, FxgValueType)> {
+    use FxgValueType::*;
+    vec![
+        ("blurX", Number), ("blurY", Number), ("quality", Number), ("strength", Number),
+        ("knockout", Boolean), ("angle", Number), ("distance", Number),
+    ]
+}
+
+fn concat(lists: &[Vec<(&'static str, FxgValueType)>]) -> Vec<(&'static str, FxgValueType)> {
+    lists.iter().flatten().copied().collect()
+}
+
+/// Returns the FXG 2.0 schema of an element.
+fn fxg_schema(name: &str) -> Option<FxgElementSchema> {
+    use FxgValueType::*;
+    let shape_properties: &'static [&'static str] = &["fill", "stroke", "transform", "filters", "mask"];
+    let (attributes, properties, children, open) = match name {
+        "Graphic" => (vec![
+            ("version", String), ("viewWidth", Number), ("viewHeight", Number),
+            ("scaleGridLeft", Number), ("scaleGridRight", Number), ("scaleGridTop", Number), ("scaleGridBottom", Number),
+            ("maskType", Enumeration(MASK_TYPES)), ("luminosityClip", Boolean), ("luminosityInvert", Boolean),
+        ], &["mask"][..], &["Library", "Private", "Group", "Rect", "Ellipse", "Line", "Path", "BitmapImage", "RichText"][..], false),
+        "Group" => (concat(&[placement_attributes(), vec![
+            ("scaleGridLeft", Number), ("scaleGridRight", Number), ("scaleGridTop", Number), ("scaleGridBottom", Number),
+        ]]), &["transform", "filters", "mask"][..], GRAPHICS, false),
+        "Rect" => (concat(&[placement_attributes(), vec![
+            ("width", Number), ("height", Number), ("radiusX", Number), ("radiusY", Number),
+            ("topLeftRadiusX", Number), ("topLeftRadiusY", Number), ("topRightRadiusX", Number), ("topRightRadiusY", Number),
+            ("bottomLeftRadiusX", Number), ("bottomLeftRadiusY", Number), ("bottomRightRadiusX", Number), ("bottomRightRadiusY", Number),
+        ]]), shape_properties, &[][..], false),
+        "Ellipse" => (concat(&[placement_attributes(), vec![("width", Number), ("height", Number)]]), shape_properties, &[][..], false),
+        "Line" => (concat(&[placement_attributes(), vec![
+            ("xFrom", Number), ("yFrom", Number), ("xTo", Number), ("yTo", Number),
+        ]]), &["stroke", "transform", "filters", "mask"][..], &[][..], false),
+        "Path" => (concat(&[placement_attributes(), vec![("data", Path), ("winding", Enumeration(WINDINGS))]]), shape_properties, &[][..], false),
+        "BitmapImage" => (concat(&[placement_attributes(), vec![
+            ("width", Number), ("height", Number), ("source", String), ("fillMode", Enumeration(FILL_MODES)),
+        ]]), &["transform", "filters", "mask"][..], &[][..], false),
+        "RichText" => (placement_attributes(), &["transform", "filters", "mask", "content"][..], &[][..], true),
+        "SolidColor" => (vec![("color", Color), ("alpha", Number)], &[][..], &[][..], false),
+        "LinearGradient" => (gradient_attributes(), &["matrix"][..], &["GradientEntry"][..], false),
+        "RadialGradient" => (concat(&[gradient_attributes(), vec![("scaleY", Number), ("focalPointRatio", Number)]]), &["matrix"][..], &["GradientEntry"][..], false),
+        "BitmapFill" => (vec![
+            ("x", Number), ("y", Number), ("scaleX", Number), ("scaleY", Number), ("rotation", Number),
+            ("source", String), ("fillMode", Enumeration(FILL_MODES)),
+        ], &["matrix"][..], &[][..], false),
+        "SolidColorStroke" => (concat(&[stroke_attributes(), vec![("color", Color), ("alpha", Number)]]), &[][..], &[][..], false),
+        "LinearGradientStroke" => (concat(&[stroke_attributes(), gradient_attributes()]), &["matrix"][..], &["GradientEntry"][..], false),
+        "RadialGradientStroke" => (concat(&[stroke_attributes(), gradient_attributes(), vec![("scaleY", Number), ("focalPointRatio", Number)]]), &["matrix"][..], &["GradientEntry"][..], false),
+        "GradientEntry" => (vec![("color", Color), ("alpha", Number), ("ratio", Number)], &[][..], &[][..], false),
+        "Transform" => (vec![], &["matrix", "colorTransform"][..], &[][..], false),
+        "Matrix" => (vec![("a", Number), ("b", Number), ("c", Number), ("d", Number), ("tx", Number), ("ty", Number)], &[][..], &[][..], false),
+        "ColorTransform" => (vec![
+            ("alphaMultiplier", Number), ("redMultiplier", Number), ("greenMultiplier", Number), ("blueMultiplier", Number),
+            ("alphaOffset", Number), ("redOffset", Number), ("greenOffset", Number), ("blueOffset", Number),
+        ], &[][..], &[][..], false),
+        "BlurFilter" => (vec![("blurX", Number), ("blurY", Number), ("quality", Number)], &[][..], &[][..], false),
+        "DropShadowFilter" => (concat(&[filter_attributes(), vec![
+            ("alpha", Number), ("color", Color), ("hideObject", Boolean), ("inner", Boolean),
+        ]]), &[][..], &[][..], false),
+        "GlowFilter" => (concat(&[filter_attributes(), vec![("alpha", Number), ("color", Color), ("inner", Boolean)]]), &[][..], &[][..], false),
+        "BevelFilter" => (concat(&[filter_attributes(), vec![
+            ("highlightAlpha", Number), ("highlightColor", Color), ("shadowAlpha", Number), ("shadowColor", Color),
+            ("type", Enumeration(FILTER_TYPES)),
+        ]]), &[][..], &[][..], false),
+        "ColorMatrixFilter" => (vec![("matrix", String)], &[][..], &[][..], false),
+        "GradientGlowFilter" | "GradientBevelFilter" => (concat(&[filter_attributes(), vec![("type", Enumeration(FILTER_TYPES))]]), &[][..], &["GradientEntry"][..], false),
+        "Library" => (vec![], &[][..], &["Definition"][..], false),
+        "Definition" => (vec![("name", String)], &[][..], &["Group"][..], false),
+        _ => return None,
+    };
+    Some(FxgElementSchema { attributes, properties, children, open })
+}
+
+/// Returns the elements allowed inside a property element.
+fn fxg_property_content(name: &str) -> &'static [&'static str] {
+    match name {
+        "fill" => FILLS,
+        "stroke" => STROKES,
+        "transform" => &["Transform"],
+        "filters" => FILTERS,
+        "mask" => &["Group"],
+        "matrix" => &["Matrix"],
+        "colorTransform" => &["ColorTransform"],
+        _ => &[],
+    }
+}
+
+/// Builds FXG documents from MXML documents, validating element
+/// and attribute names against the FXG 2.0 schema and parsing
+/// attribute values.
+///
+/// Attributes belonging to a namespace other than the FXG namespace,
+/// the content of `<Private>` elements and the content of text elements
+/// are not validated. Symbols defined by `<Definition>` elements
+/// may be used as graphical elements.
+struct FxgBuilder {
+    compilation_unit: Rc<CompilationUnit>,
+    definitions: Vec<String>,
+}
+
+impl FxgBuilder {
+    fn build(&mut self, mxml: &Rc<Mxml>) -> Rc<FxgDocument> {
+        let root = mxml.content.iter().find_map(|content| {
+            if let MxmlContent::Element(element) = content.as_ref() { Some(element.clone()) } else { None }
+        });
+        let root = root.and_then(|root| {
+            if self.element_name(&root).as_deref() != Some("Graphic") {
+                self.add_verify_error(&root.name.location, DiagnosticKind::FxgRootMustBeGraphic, vec![]);
+                return None;
+            }
+            self.collect_definitions(&root);
+            Some(self.build_element(&root, "Graphic"))
+        });
+        Rc::new(FxgDocument {
+            location: mxml.location.clone(),
+            mxml: mxml.clone(),
+            root,
+        })
+    }
+
+    fn add_verify_error(&self, location: &Location, kind: DiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        self.compilation_unit.add_diagnostic(Diagnostic::new_verify_error(location, kind, arguments));
+    }
+
+    /// Returns the local name of an element in the FXG namespace.
+    fn element_name(&self, element: &MxmlElement) -> Option<String> {
+        let (uri, local_name) = element.name.resolve_name(&element.namespace).ok()?;
+        if uri == FXG_NAMESPACE { Some(local_name) } else { None }
+    }
+
+    fn collect_definitions(&mut self, root: &MxmlElement) {
+        for library in child_elements(root) {
+            if self.element_name(&library).as_deref() != Some("Library") {
+                continue;
+            }
+            for definition in child_elements(&library) {
+                if let Some((name, _)) = definition.attribute_value("name") {
+                    self.definitions.push(name.clone());
+                }
+            }
+        }
+    }
+
+    fn build_element(&mut self, element: &Rc<MxmlElement>, name: &str) -> Rc<FxgElement> {
+        // Symbol defined by <Definition>
+        let schema = fxg_schema(name).unwrap_or_else(|| FxgElementSchema {
+            attributes: placement_attributes(),
+            properties: &["transform", "filters", "mask"],
+            children: &[],
+            open: false,
+        });
+        let mut attributes = vec![];
+        for attribute in element.attributes.iter() {
+            if attribute.xmlns {
+                continue;
+            }
+            if let Some(prefix) = &attribute.name.prefix {
+                if element.namespace.get(prefix).as_deref() != Some(FXG_NAMESPACE) {
+                    continue;
+                }
+            }
+            let attribute_name = &attribute.name.name;
+            let Some((_, value_type)) = schema.attributes.iter().find(|(name, _)| name == attribute_name) else {
+                if !schema.open {
+                    self.add_verify_error(&attribute.name.location, DiagnosticKind::UnknownFxgAttribute, diagarg![attribute_name.clone(), name.to_owned()]);
+                }
+                continue;
+            };
+            if let Some(value) = self.parse_value(attribute, *value_type) {
+                attributes.push(Rc::new(FxgAttribute {
+                    location: attribute.location.clone(),
+                    name: (attribute_name.clone(), attribute.name.location.clone()),
+                    value,
+                }));
+            }
+        }
+
+        let mut properties = vec![];
+        let mut children = vec![];
+        if !schema.open && name != "Private" {
+            for child in child_elements(element) {
+                let Some(child_name) = self.element_name(&child) else {
+                    self.add_verify_error(&child.name.location, DiagnosticKind::UnknownFxgElement, diagarg![child.name.to_string(&child.namespace)]);
+                    continue;
+                };
+                if schema.properties.contains(&child_name.as_str()) {
+                    properties.push(self.build_property_element(&child, &child_name));
+                } else if let Some(child) = self.build_child_element(&child, &child_name, schema.children) {
+                    children.push(child);
+                }
+            }
+        }
+
+        Rc::new(FxgElement {
+            location: element.location.clone(),
+            name: (name.to_owned(), element.name.location.clone()),
+            attributes,
+            properties,
+            children,
+            source: element.clone(),
+        })
+    }
+
+    fn build_property_element(&mut self, element: &Rc<MxmlElement>, name: &str) -> Rc<FxgPropertyElement> {
+        let allowed = fxg_property_content(name);
+        let mut elements = vec![];
+        if !allowed.is_empty() {
+            for child in child_elements(element) {
+                let Some(child_name) = self.element_name(&child) else {
+                    self.add_verify_error(&child.name.location, DiagnosticKind::UnknownFxgElement, diagarg![child.name.to_string(&child.namespace)]);
+                    continue;
+                };
+                if let Some(child) = self.build_child_element(&child, &child_name, allowed) {
+                    elements.push(child);
+                }
+            }
+        }
+        Rc::new(FxgPropertyElement {
+            location: element.location.clone(),
+            name: (name.to_owned(), element.name.location.clone()),
+            elements,
+        })
+    }
+
+    /// Builds a child element, reporting it if it is unknown or
+    /// not among the allowed elements.
+    fn build_child_element(&mut self, element: &Rc<MxmlElement>, name: &str, allowed: &[&str]) -> Option<Rc<FxgElement>> {
+        let is_definition = self.definitions.iter().any(|definition| definition == name);
+        if fxg_schema(name).is_none() && name != "Private" && !is_definition {
+            self.add_verify_error(&element.name.location, DiagnosticKind::UnknownFxgElement, diagarg![name.to_owned()]);
+            return None;
+        }
+        let allowed = allowed.contains(&name) || (is_definition && allowed.contains(&"Group") && allowed.contains(&"Rect"));
+        if !allowed {
+            self.add_verify_error(&element.name.location, DiagnosticKind::FxgElementNotAllowedHere, diagarg![name.to_owned()]);
+            return None;
+        }
+        Some(self.build_element(element, name))
+    }
+
+    fn parse_value(&self, attribute: &MxmlAttribute, value_type: FxgValueType) -> Option<FxgValue> {
+        let text = attribute.value.0.trim();
+        let value = match value_type {
+            FxgValueType::Number => parse_number(text).map(FxgValue::Number),
+            FxgValueType::Color => text.strip_prefix('#')
+                .filter(|hex| hex.len() == 6)
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .map(FxgValue::Color),
+            FxgValueType::Boolean => match text {
+                "true" => Some(FxgValue::Boolean(true)),
+                "false" => Some(FxgValue::Boolean(false)),
+                _ => None,
+            },
+            FxgValueType::String => Some(FxgValue::String(attribute.value.0.clone())),
+            FxgValueType::Enumeration(values) => values.contains(&text).then(|| FxgValue::Enumeration(text.to_owned())),
+            FxgValueType::Path => {
+                return match parse_path_data(&attribute.value.0) {
+                    Ok(commands) => Some(FxgValue::Path(commands)),
+                    Err(offset) => {
+                        // Locate the error within the attribute value if it contains no entities
+                        let value_location = &attribute.value.1;
+                        let first_offset = value_location.first_offset() + 1;
+                        let raw = &self.compilation_unit.text()[first_offset..(value_location.last_offset() - 1)];
+                        let location = if raw == attribute.value.0 {
+                            Location::with_offsets(&self.compilation_unit, first_offset + offset, first_offset + offset)
+                        } else {
+                            value_location.clone()
+                        };
+                        self.add_verify_error(&location, DiagnosticKind::InvalidFxgPathData, vec![]);
+                        None
+                    },
+                };
+            },
+        };
+        if value.is_none() {
+            self.add_verify_error(&attribute.value.1, DiagnosticKind::InvalidFxgAttributeValue, diagarg![attribute.value.0.clone(), attribute.name.name.clone(), value_type.description()]);
+        }
+        value
+    }
+}
+
+fn child_elements(element: &MxmlElement) -> Vec<Rc<MxmlElement>> {
+    element.content.iter().flatten().filter_map(|content| {
+        if let MxmlContent::Element(child) = content.as_ref() { Some(child.clone()) } else { None }
+    }).collect()
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    let number = text.parse::<f64>().ok()?;
+    if number.is_finite() && !text.starts_with(['+', 'i', 'I', 'n', 'N']) { Some(number) } else { None }
+}
+
+/// Parses path data, such as `M 0 0 L 10 10 Z`, returning the byte
+/// offset of the first invalid character on failure.
+///
+/// As in SVG, a command letter may be omitted for repeated commands,
+/// in which case a repeated move command is a line command.
+fn parse_path_data(data: &str) -> Result<Vec<FxgPathCommand>, usize> {
+    let bytes = data.as_bytes();
+    let mut commands: Vec<FxgPathCommand> = vec![];
+    let mut i = 0;
+    let mut current: Option<char> = None;
+    loop {
+        // Skip whitespace and commas
+        while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b',') {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            break;
+        }
+        let ch = bytes[i] as char;
+        let command = if ch.is_ascii_alphabetic() && ch != 'e' && ch != 'E' {
+            if FxgPathCommand::argument_count(ch).is_none() {
+                return Err(i);
+            }
+            i += 1;
+            ch
+        } else {
+            match current {
+                Some('Z' | 'z') | None => return Err(i),
+                Some('M') => 'L',
+                Some('m') => 'l',
+                Some(command) => command,
+            }
+        };
+        let mut arguments = vec![];
+        for _ in 0..FxgPathCommand::argument_count(command).unwrap() {
+            while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b',') {
+                i += 1;
+            }
+            let start = i;
+            if i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b'+') {
+                i += 1;
+            }
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                // A second dot starts a new number, as in "0.5.5"
+                if bytes[i] == b'.' && data[start..i].contains('.') {
+                    break;
+                }
+                i += 1;
+            }
+            if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+                i += 1;
+                if i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b'+') {
+                    i += 1;
+                }
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let Ok(number) = data[start..i].parse::<f64>() else {
+                return Err(start);
+            };
+            arguments.push(number);
+        }
+        current = Some(command);
+        commands.push(FxgPathCommand { command, arguments });
+    }
+    Ok(commands)
+}
+
+/// A simplified interface for building FXG documents.
+pub struct FxgParserFacade<'input>(pub &'input Rc<CompilationUnit>, pub ParserOptions);
+
+impl<'input> FxgParserFacade<'input> {
+    /// Parses a FXG document until end-of-file.
+    pub fn parse_document(&self) -> Rc<FxgDocument> {
+        let mxml = ParserFacade(self.0, ParserOptions {
+            byte_range: self.1.byte_range,
+            ..default()
+        }).parse_mxml();
+        FxgBuilder {
+            compilation_unit: self.0.clone(),
+            definitions: vec![],
+        }.build(&mxml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    #[test]
+    fn test_fxg_document() {
+        let compilation_unit = CompilationUnit::new(None, r##"<?xml version="1.0"?>
+<Graphic version="2.0" xmlns="http://ns.adobe.com/fxg/2008" xmlns:d="http://ns.adobe.com/fxg/2008/dt">
+    <Library>
+        <Definition name="Star">
+            <Group><Path data="M 0 0 L 10 0 10 10 z"/></Group>
+        </Definition>
+    </Library>
+    <Rect width="100" height="50" d:userLabel="Background">
+        <fill><SolidColor color="#FF8000" alpha="0.5"/></fill>
+        <stroke><SolidColorStroke weight="2" caps="square"/></stroke>
+    </Rect>
+    <Star x="10"/>
+    <Path data="M 0 0 X 5" winding="odd" size="1"/>
+    <Ellipse><fill><SolidColorStroke/></fill></Ellipse>
+</Graphic>"##.into());
+        let document = FxgParserFacade(&compilation_unit, default()).parse_document();
+        let root = document.root.clone().unwrap();
+        assert_eq!(root.children.len(), 5);
+        let rect = &root.children[1];
+        assert_eq!(rect.attribute("width").unwrap().value.as_number(), Some(100.0));
+        let fill = rect.property("fill").unwrap();
+        assert_eq!(fill.elements[0].attribute("color").unwrap().value.as_color(), Some(0xFF8000));
+
+        let library = &root.children[0];
+        let path = &library.children[0].children[0].children[0];
+        let FxgValue::Path(commands) = &path.attribute("data").unwrap().value else {
+            panic!();
+        };
+        let letters: String = commands.iter().map(|command| command.command).collect();
+        assert_eq!(letters, "MLLz");
+
+        let diagnostics: Vec<(DiagnosticKind, usize)> = compilation_unit.diagnostics().iter()
+            .map(|d| (d.kind(), d.location().first_column())).collect();
+        assert_eq!(diagnostics.len(), 4);
+        assert!(diagnostics[0] == (DiagnosticKind::InvalidFxgPathData, 22));
+        assert!(diagnostics[1].0 == DiagnosticKind::InvalidFxgAttributeValue);
+        assert!(diagnostics[2].0 == DiagnosticKind::UnknownFxgAttribute);
+        assert!(diagnostics[3].0 == DiagnosticKind::FxgElementNotAllowedHere);
+    }
+}
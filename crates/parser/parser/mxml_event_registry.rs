@@ -0,0 +1,72 @@
+use crate::ns::*;
+
+/// Registry of MXML event names, whose attribute values the parser
+/// parses as event handlers.
+///
+/// Events are registered either for every component or for a component
+/// given by its namespace URI and local name, in which case they may be
+/// taken from the `[Event]` metadata of the component class.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let mut events = MxmlEventRegistry::new();
+/// events.add_event("creationComplete");
+/// events.add_component_event("library://ns.adobe.com/flex/spark", "Button", "click");
+/// let options = ParserOptions {
+///     mxml_events: Rc::new(events),
+///     ..default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MxmlEventRegistry {
+    events: HashSet<String>,
+    component_events: HashMap<(String, String), HashSet<String>>,
+}
+
+impl MxmlEventRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an event for every component.
+    pub fn add_event(&mut self, name: &str) {
+        self.events.insert(name.to_owned());
+    }
+
+    /// Registers an event for the component of a given namespace URI
+    /// and local name.
+    pub fn add_component_event(&mut self, uri: &str, local_name: &str, name: &str) {
+        self.component_events.entry((uri.to_owned(), local_name.to_owned())).or_default().insert(name.to_owned());
+    }
+
+    /// Registers the events declared by `[Event]` metadata, such as
+    /// `[Event(name="click", type="flash.events.MouseEvent")]`,
+    /// for the component of a given namespace URI and local name.
+    pub fn add_component_metadata(&mut self, uri: &str, local_name: &str, metadata: &[Rc<Metadata>]) {
+        for metadata in metadata.iter() {
+            if metadata.name.0 != "Event" {
+                continue;
+            }
+            for entry in metadata.entries.iter().flatten() {
+                if entry.key.as_ref().is_none_or(|(key, _)| key == "name") {
+                    let (MetadataValue::String((name, _)) | MetadataValue::IdentifierString((name, _))) = entry.value.as_ref();
+                    self.add_component_event(uri, local_name, name);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Indicates whether an attribute names an event of the component
+    /// of a given namespace URI and local name.
+    pub fn is_event(&self, uri: &str, local_name: &str, name: &str) -> bool {
+        self.events.contains(name) || self.component_events.get(&(uri.to_owned(), local_name.to_owned())).is_some_and(|events| events.contains(name))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty() && self.component_events.is_empty()
+    }
+}
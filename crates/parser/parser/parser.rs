@@ -8,6 +8,12 @@ pub struct Parser<'input> {
     locations: Vec<Location>,
     activations: Vec<ParserActivation>,
     ignore_xml_whitespace: bool,
+    custom_asdoc_tags: Rc<AsDocTagRegistry>,
+    parse_mxml_scripts: bool,
+    parse_mxml_bindings: bool,
+    parse_mxml_styles: bool,
+    mxml_events: Rc<MxmlEventRegistry>,
+    source_resolver: Rc<dyn SourceResolver>,
     expecting_token_error: bool,
 }
 
@@ -21,6 +27,12 @@ impl<'input> Parser<'input> {
             locations: vec![],
             activations: vec![],
             ignore_xml_whitespace: options.ignore_xml_whitespace,
+            custom_asdoc_tags: options.custom_asdoc_tags.clone(),
+            parse_mxml_scripts: options.parse_mxml_scripts,
+            parse_mxml_bindings: options.parse_mxml_bindings,
+            parse_mxml_styles: options.parse_mxml_styles,
+            mxml_events: options.mxml_events.clone(),
+            source_resolver: options.source_resolver.clone(),
             expecting_token_error: false,
         }
     }
@@ -28,6 +40,12 @@ impl<'input> Parser<'input> {
     fn options(&self) -> ParserOptions {
         ParserOptions {
             ignore_xml_whitespace: self.ignore_xml_whitespace,
+            custom_asdoc_tags: self.custom_asdoc_tags.clone(),
+            parse_mxml_scripts: self.parse_mxml_scripts,
+            parse_mxml_bindings: self.parse_mxml_bindings,
+            parse_mxml_styles: self.parse_mxml_styles,
+            mxml_events: self.mxml_events.clone(),
+            source_resolver: self.source_resolver.clone(),
             ..default()
         }
     }
@@ -4361,6 +4379,38 @@ impl<'input> Parser<'input> {
         })
     }
 
+    /// Parses a sequence of meta-data, such as `[Event(name="change")]`,
+    /// until end-of-file.
+    pub fn parse_metadata_list(&mut self) -> Vec<Rc<Metadata>> {
+        let mut list = vec![];
+        while !self.peek(Token::Eof) {
+            let start = self.token_location();
+            let exp = self.parse_expression(ParserExpressionContext {
+                allow_in: true, min_precedence: OperatorPrecedence::List, ..default()
+            });
+            match exp.to_metadata(self) {
+                Ok(Some(metadata)) => {
+                    for attribute in metadata {
+                        if let Attribute::Metadata(metadata) = attribute {
+                            list.push(metadata);
+                        }
+                    }
+                },
+                Ok(None) => {
+                    self.add_syntax_error(&exp.location(), DiagnosticKind::UnrecognizedMetadataSyntax, diagarg![]);
+                },
+                Err(MetadataRefineError1(MetadataRefineError::Syntax, loc)) => {
+                    self.add_syntax_error(&loc, DiagnosticKind::UnrecognizedMetadataSyntax, diagarg![]);
+                },
+            }
+            self.consume(Token::Semicolon);
+            if self.token.1.first_offset() == start.first_offset() && !self.peek(Token::Eof) {
+                self.next();
+            }
+        }
+        list
+    }
+
     pub fn parse_asdoc(&mut self) -> Option<Rc<AsDoc>> {
         let comments = self.compilation_unit().comments.borrow();
         let last_comment = comments.last().map(|last_comment| last_comment.clone());
@@ -4416,7 +4466,7 @@ impl<'input> Parser<'input> {
                 let tag_content_location = Location::with_offsets(self.compilation_unit(), line.location.first_offset() + tag_prefix.len() + tag_name.len(), line.location.last_offset());
                 building_content.push((tag_content.into(), tag_content_location));
 
-                if ["private", "inheritDoc"].contains(&tag_name) {
+                if ["private", "inheritDoc"].contains(&tag_name) || self.custom_asdoc_tags.get(tag_name) == Some(AsDocTagShape::Flag) {
                     self.parse_asdoc_tag_or_main_body(
                         &mut building_content_tag_name,
                         &mut building_content,
@@ -4496,6 +4546,14 @@ impl<'input> Parser<'input> {
         tags: &mut Vec<(AsDocTag, Location)>
     ) {
         if let Some((tag_name, ref tag_location)) = building_content_tag_name.as_ref() {
+            // Custom tags take precedence over built-in tags
+            if let Some(shape) = self.custom_asdoc_tags.get(tag_name) {
+                self.parse_custom_asdoc_tag(tag_name, tag_location, shape, building_content, tags);
+                *building_content_tag_name = None;
+                building_content.clear();
+                return;
+            }
+
             match tag_name.as_ref() {
                 // @author Author text
                 "author" => {
@@ -4570,6 +4628,29 @@ impl<'input> Parser<'input> {
                     tags.push((AsDocTag::Example(text), location));
                 },
 
+                // @exampleText text
+                "exampleText" => {
+                    let (text, location) = join_asdoc_content(building_content);
+                    let location = tag_location.combine_with(location);
+                    tags.push((AsDocTag::ExampleText(text), location));
+                },
+
+                // @helpid text
+                "helpid" => {
+                    let (text, location) = join_asdoc_content(building_content);
+                    let location = tag_location.combine_with(location);
+                    self.verify_asdoc_tag_content_not_empty(tag_name, tag_location, &text);
+                    tags.push((AsDocTag::Helpid(text), location));
+                },
+
+                // @includeExample path
+                "includeExample" => {
+                    let (text, location) = join_asdoc_content(building_content);
+                    let location = tag_location.combine_with(location);
+                    self.verify_asdoc_tag_content_not_empty(tag_name, tag_location, &text);
+                    tags.push((AsDocTag::IncludeExample(text), location));
+                },
+
                 // @inheritDoc
                 "inheritDoc" => {
                     let (text, location) = join_asdoc_content(building_content);
@@ -4596,6 +4677,14 @@ impl<'input> Parser<'input> {
                     tags.push((AsDocTag::Internal(text), location));
                 },
 
+                // @keyword text
+                "keyword" => {
+                    let (text, location) = join_asdoc_content(building_content);
+                    let location = tag_location.combine_with(location);
+                    self.verify_asdoc_tag_content_not_empty(tag_name, tag_location, &text);
+                    tags.push((AsDocTag::Keyword(text), location));
+                },
+
                 // @langversion text
                 "langversion" => {
                     let (text, location) = join_asdoc_content(building_content);
@@ -4714,6 +4803,14 @@ impl<'input> Parser<'input> {
                     }
                 },
 
+                // @tiptext text
+                "tiptext" => {
+                    let (text, location) = join_asdoc_content(building_content);
+                    let location = tag_location.combine_with(location);
+                    self.verify_asdoc_tag_content_not_empty(tag_name, tag_location, &text);
+                    tags.push((AsDocTag::Tiptext(text), location));
+                },
+
                 // @version Version text
                 "version" => {
                     let (content, location) = join_asdoc_content(building_content);
@@ -4727,7 +4824,7 @@ impl<'input> Parser<'input> {
 
                 // Unrecognized tag
                 _ => {
-                    self.add_syntax_error(&tag_location, DiagnosticKind::UnrecognizedAsDocTag, diagarg![tag_name.clone()]);
+                    self.add_syntax_error(tag_location, DiagnosticKind::UnrecognizedAsDocTag, diagarg![tag_name.clone()]);
                 },
             }
         } else if !building_content.is_empty() {
@@ -4741,6 +4838,61 @@ impl<'input> Parser<'input> {
         building_content.clear();
     }
 
+    /// Reports a tag whose content is empty.
+    fn verify_asdoc_tag_content_not_empty(&self, tag_name: &str, tag_location: &Location, content: &str) {
+        if regex_is_match!(r"^\s*$", content) {
+            self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingAsDocTag, diagarg![tag_name.to_owned()]);
+        }
+    }
+
+    fn parse_custom_asdoc_tag(
+        &self,
+        tag_name: &str,
+        tag_location: &Location,
+        shape: AsDocTagShape,
+        building_content: &[(String, Location)],
+        tags: &mut Vec<(AsDocTag, Location)>
+    ) {
+        let (content, c_location) = join_asdoc_content(building_content);
+        let location = tag_location.combine_with(c_location.clone());
+        let content = match shape {
+            // @tag text
+            AsDocTagShape::Text => AsDocCustomTagContent::Text(content),
+
+            // @tag reference
+            AsDocTagShape::Reference => {
+                let reference_loc = c_location.shift_whitespace(&self.compilation_unit().text()[c_location.first_offset()..c_location.last_offset()]);
+                let Some(reference) = self.parse_asdoc_reference(&content, &reference_loc, tag_location, tag_name) else {
+                    return;
+                };
+                AsDocCustomTagContent::Reference(reference)
+            },
+
+            // @tag name description
+            AsDocTagShape::NameAndDescription => {
+                if let Some((_, name, description)) = regex_captures!(r"(?x) ([^\s]+) (.*)", &content) {
+                    AsDocCustomTagContent::NameAndDescription { name: name.into(), description: description.trim_start().into() }
+                } else {
+                    // Name must be non empty
+                    if regex_is_match!(r"^\s*$", &content) {
+                        self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingAsDocTag, diagarg![tag_name.to_owned()]);
+                    }
+                    AsDocCustomTagContent::NameAndDescription { name: content, description: "".into() }
+                }
+            },
+
+            // @tag
+            AsDocTagShape::Flag => {
+                // Content must be empty
+                if !regex_is_match!(r"^\s*$", &content) {
+                    self.add_syntax_error(tag_location, DiagnosticKind::FailedParsingAsDocTag, diagarg![tag_name.to_owned()]);
+                }
+                AsDocCustomTagContent::Flag
+            },
+        };
+        tags.push((AsDocTag::Custom { name: tag_name.to_owned(), content }, location));
+    }
+
     fn parse_asdoc_reference(&self, reference: &str, reference_loc: &Location, tag_location: &Location, tag_name: &str) -> Option<Rc<AsDocReference>> {
         let split: Vec<&str> = reference.split("#").collect();
         if split.len() > 2 {
@@ -4809,12 +4961,12 @@ impl<'input> Parser<'input> {
             }
         }
 
+        let name = self.process_mxml_tag_name(name, &namespace);
+
         for attrib in &plain_attributes {
-            self.process_mxml_attribute(&mut attributes, &attrib, &namespace);
+            self.process_mxml_attribute(&mut attributes, &attrib, &name, &namespace);
         }
 
-        let name = self.process_mxml_tag_name(name, &namespace);
-
         let mut content: Option<Vec<Rc<MxmlContent>>> = None;
         let mut closing_name: Option<MxmlName> = None;
 
@@ -4840,14 +4992,281 @@ impl<'input> Parser<'input> {
             self.filter_mxml_whitespace_out(content);
         }
 
-        MxmlElement {
+        let mut element = MxmlElement {
             location: self.pop_location(),
             name,
             attributes,
             content,
             closing_name,
             namespace,
+            script_directives: None,
+            content_binding: None,
+            style_document: None,
+        };
+
+        if self.parse_mxml_bindings && !["Script", "Style", "Metadata"].iter().any(|name| element.is_language_element(name)) {
+            if let Some([node]) = element.content.as_deref() {
+                if let MxmlContent::Characters((_, location)) = node.as_ref() {
+                    element.content_binding = self.parse_mxml_binding(location);
+                }
+            }
+        }
+
+        if self.parse_mxml_scripts && element.is_language_element("Script") {
+            element.script_directives = Some(self.parse_mxml_script(&element));
+        }
+
+        if self.parse_mxml_styles && element.is_language_element("Style") {
+            element.style_document = self.parse_mxml_style(&element);
+        }
+
+        element
+    }
+
+    /// Parses the directives of a `<fx:Script>` element, either from
+    /// its content or from the file given by its `source` attribute.
+    fn parse_mxml_script(&mut self, element: &MxmlElement) -> Vec<Rc<Directive>> {
+        let context = ParserDirectiveContext::ClassBlock {
+            name: self.mxml_class_name(),
+        };
+
+        // <fx:Script source="file.as"/>
+        if let Some((source, source_location)) = element.attribute_value("source") {
+            let Some(nested_compilation_unit) = self.load_mxml_source(source, source_location) else {
+                return vec![];
+            };
+            let directives = ParserFacade(&nested_compilation_unit, ParserOptions {
+                ..self.options()
+            }).parse_directives(context);
+            if nested_compilation_unit.invalidated() {
+                self.compilation_unit().invalidated.set(true);
+            }
+            return directives;
+        }
+
+        let fragments = mxml_text_fragments(element);
+        if fragments.is_empty() {
+            return vec![];
+        }
+
+        // Parse a single chunk that appears as is in the host source
+        // using the host locations.
+        if let Some(byte_range) = self.verbatim_byte_range(&fragments) {
+            return ParserFacade(self.compilation_unit(), ParserOptions {
+                byte_range: Some(byte_range),
+                ..self.options()
+            }).parse_directives(context);
+        }
+
+        // Otherwise parse the concatenated chunks, mapping
+        // diagnostics to the host source.
+        let embedded = EmbeddedCompilationUnit::new(self.compilation_unit(), &fragments);
+        let directives = ParserFacade(&embedded.compilation_unit(), ParserOptions {
+            ..self.options()
+        }).parse_directives(context);
+        embedded.transfer_diagnostics();
+        directives
+    }
+
+    /// Parses the style sheet of a `<fx:Style>` element, either from
+    /// its content or from the file given by its `source` attribute.
+    fn parse_mxml_style(&mut self, element: &MxmlElement) -> Option<Rc<CssDocument>> {
+        // <fx:Style source="file.css"/>
+        if let Some((source, source_location)) = element.attribute_value("source") {
+            let nested_compilation_unit = self.load_mxml_source(source, source_location)?;
+            let document = CssParserFacade(&nested_compilation_unit, default()).parse_document();
+            if nested_compilation_unit.invalidated() {
+                self.compilation_unit().invalidated.set(true);
+            }
+            return Some(document);
         }
+
+        let fragments = mxml_text_fragments(element);
+        if fragments.is_empty() {
+            return None;
+        }
+
+        // Parse a single chunk that appears as is in the host source
+        // using the host locations.
+        if let Some(byte_range) = self.verbatim_byte_range(&fragments) {
+            return Some(CssParserFacade(self.compilation_unit(), ParserOptions {
+                byte_range: Some(byte_range),
+                ..default()
+            }).parse_document());
+        }
+
+        // Otherwise parse the concatenated chunks, mapping
+        // diagnostics to the host source.
+        let embedded = EmbeddedCompilationUnit::new(self.compilation_unit(), &fragments);
+        let document = CssParserFacade(&embedded.compilation_unit(), default()).parse_document();
+        embedded.transfer_diagnostics();
+        Some(document)
+    }
+
+    /// Resolves a source referenced by a MXML element into a nested
+    /// compilation unit, reporting a diagnostic on failure.
+    fn load_mxml_source(&self, source: &str, source_location: &Location) -> Option<Rc<CompilationUnit>> {
+        let nested_compilation_unit = match self.source_resolver.resolve(source, self.compilation_unit()) {
+            Ok((file_path, content)) => CompilationUnit::new(Some(file_path), content),
+            Err(error) => {
+                self.add_syntax_error(source_location, match error {
+                    SourceResolutionError::ParentSourceIsNotAFile => DiagnosticKind::ParentSourceIsNotAFile,
+                    SourceResolutionError::FailedToReadFile => DiagnosticKind::FailedToIncludeFile,
+                }, vec![]);
+                return None;
+            },
+        };
+        nested_compilation_unit.set_compiler_options(self.compilation_unit().compiler_options());
+        self.compilation_unit().add_nested_compilation_unit(nested_compilation_unit.clone());
+        Some(nested_compilation_unit)
+    }
+
+    /// Returns the host byte range of text fragments if they consist of
+    /// a single fragment that appears as is in the host source.
+    fn verbatim_byte_range(&self, fragments: &[(String, usize)]) -> Option<(usize, usize)> {
+        let [(text, offset)] = fragments else {
+            return None;
+        };
+        self.compilation_unit().text()[*offset..].starts_with(text.as_str()).then(|| (*offset, offset + text.len()))
+    }
+
+    /// Parses a data binding from the raw text at a location,
+    /// returning `None` if the text contains no binding expression.
+    fn parse_mxml_binding(&self, location: &Location) -> Option<Rc<MxmlBinding>> {
+        let characters = decode_xml_characters(&self.compilation_unit().text()[location.first_offset()..location.last_offset()], location.first_offset());
+        if !characters.iter().any(|(ch, _, _)| *ch == '{') {
+            return None;
+        }
+
+        // @{expression}
+        let two_way = characters.len() >= 2 && characters[0].0 == '@' && characters[1].0 == '{';
+
+        let mut segments: Vec<MxmlBindingSegment> = vec![];
+        let mut literal = String::new();
+        let mut literal_range: Option<(usize, usize)> = None;
+        let mut i = if two_way { 1 } else { 0 };
+
+        while i < characters.len() {
+            let (ch, start, end) = characters[i];
+
+            // \{ or \}
+            if ch == '\\' && matches!(characters.get(i + 1), Some(('{', _, _)) | Some(('}', _, _))) {
+                literal.push(characters[i + 1].0);
+                literal_range = Some((literal_range.map_or(start, |r| r.0), characters[i + 1].2));
+                i += 2;
+                continue;
+            }
+
+            if ch == '{' {
+                let Some(j) = find_mxml_binding_end(&characters, i + 1) else {
+                    self.add_syntax_error(&Location::with_offsets(self.compilation_unit(), start, location.last_offset()), DiagnosticKind::UnterminatedMxmlBinding, vec![]);
+                    break;
+                };
+                if let Some((first, last)) = literal_range.take() {
+                    segments.push(MxmlBindingSegment::Literal((std::mem::take(&mut literal), Location::with_offsets(self.compilation_unit(), first, last))));
+                }
+                segments.push(MxmlBindingSegment::Expression(self.parse_mxml_binding_expression(&characters[(i + 1)..j], end)));
+                i = j + 1;
+
+                if two_way {
+                    if i < characters.len() {
+                        self.add_syntax_error(&Location::with_offsets(self.compilation_unit(), characters[i].1, location.last_offset()), DiagnosticKind::TwoWayMxmlBindingMustBeSingleExpression, vec![]);
+                    }
+                    break;
+                }
+                continue;
+            }
+
+            literal.push(ch);
+            literal_range = Some((literal_range.map_or(start, |r| r.0), end));
+            i += 1;
+        }
+
+        if !segments.iter().any(|segment| matches!(segment, MxmlBindingSegment::Expression(_))) {
+            return None;
+        }
+
+        if let Some((first, last)) = literal_range {
+            segments.push(MxmlBindingSegment::Literal((literal, Location::with_offsets(self.compilation_unit(), first, last))));
+        }
+
+        Some(Rc::new(MxmlBinding {
+            location: location.clone(),
+            two_way,
+            segments,
+        }))
+    }
+
+    /// Parses the expression of a data binding from decoded characters.
+    /// `offset` is the host offset following the opening brace.
+    fn parse_mxml_binding_expression(&self, characters: &[(char, usize, usize)], offset: usize) -> Rc<Expression> {
+        let verbatim = characters.iter().all(|(ch, start, end)| end - start == ch.len_utf8());
+
+        // Parse using the host locations
+        if verbatim {
+            let first = characters.first().map_or(offset, |c| c.1);
+            let last = characters.last().map_or(offset, |c| c.2);
+            return ParserFacade(self.compilation_unit(), ParserOptions {
+                byte_range: Some((first, last)),
+                ..self.options()
+            }).parse_expression();
+        }
+
+        // Parse from the decoded characters, mapping
+        // diagnostics to the host source.
+        let mut fragments: Vec<(String, usize)> = vec![];
+        let mut contiguous = false;
+        for (ch, start, end) in characters.iter() {
+            let is_entity = end - start != ch.len_utf8();
+            if contiguous && !is_entity {
+                fragments.last_mut().unwrap().0.push(*ch);
+            } else {
+                fragments.push((ch.to_string(), *start));
+            }
+            contiguous = !is_entity;
+        }
+        let embedded = EmbeddedCompilationUnit::new(self.compilation_unit(), &fragments);
+        let exp = ParserFacade(&embedded.compilation_unit(), ParserOptions {
+            ..self.options()
+        }).parse_expression();
+        embedded.transfer_diagnostics();
+        exp
+    }
+
+    /// Parses the directives of an event handler from the raw text
+    /// of an attribute value.
+    fn parse_mxml_event_handler(&self, location: &Location) -> Vec<Rc<Directive>> {
+        let (first, last) = (location.first_offset(), location.last_offset());
+        let characters = decode_xml_characters(&self.compilation_unit().text()[first..last], first);
+        let verbatim = characters.iter().all(|(ch, start, end)| end - start == ch.len_utf8());
+
+        // Parse using the host locations
+        if verbatim {
+            return ParserFacade(self.compilation_unit(), ParserOptions {
+                byte_range: Some((first, last)),
+                ..self.options()
+            }).parse_directives(ParserDirectiveContext::Default);
+        }
+
+        // Parse the unescaped value, mapping diagnostics
+        // to the host source.
+        let value: String = characters.iter().map(|(ch, _, _)| *ch).collect();
+        let embedded = EmbeddedCompilationUnit::new(self.compilation_unit(), &[(value, first)]);
+        let directives = ParserFacade(&embedded.compilation_unit(), ParserOptions {
+            ..self.options()
+        }).parse_directives(ParserDirectiveContext::Default);
+        embedded.transfer_diagnostics();
+        directives
+    }
+
+    /// Returns the name of the class defined by the MXML
+    /// document, based on its file name.
+    fn mxml_class_name(&self) -> String {
+        let Some(file_path) = self.compilation_unit().file_path() else {
+            return "".into();
+        };
+        let file_name = file_paths::FlexPath::new_native(&file_path).base_name();
+        file_name.split('.').next().unwrap_or("").to_owned()
     }
 
     /// Filters whitespace chunks out of a content list when
@@ -4893,6 +5312,8 @@ impl<'input> Parser<'input> {
                 },
                 value: (attribute_value, attribute.value.1.clone()),
                 xmlns: true,
+                binding: None,
+                event_handler: None,
             }));
         // xmlns:prefix="uri"
         } else if attribute.name.0.starts_with("xmlns:") {
@@ -4910,11 +5331,13 @@ impl<'input> Parser<'input> {
                 },
                 value: (attribute_value, attribute.value.1.clone()),
                 xmlns: true,
+                binding: None,
+                event_handler: None,
             }));
         }
     }
 
-    fn process_mxml_attribute(&mut self, output: &mut Vec<Rc<MxmlAttribute>>, attribute: &PlainMxmlAttribute, namespace: &Rc<MxmlNamespace>) {
+    fn process_mxml_attribute(&mut self, output: &mut Vec<Rc<MxmlAttribute>>, attribute: &PlainMxmlAttribute, element_name: &MxmlName, namespace: &Rc<MxmlNamespace>) {
         // attrib="value"
         if !(attribute.name.0 == "xmlns" || attribute.name.0.starts_with("xmlns:")) {
             let attribute_value = unescape_xml(&attribute.value.0);
@@ -4928,6 +5351,22 @@ impl<'input> Parser<'input> {
                 None
             };
             let name = split.last().unwrap();
+            let value_location = &attribute.value.1;
+            let i = value_location.first_offset() + 1;
+            let j = decrease_last_offset(i, value_location.last_offset(), 1);
+            let is_event = prefix.is_none() && !self.mxml_events.is_empty() && element_name.resolve_name(namespace).is_ok_and(|(uri, local_name)| {
+                self.mxml_events.is_event(&uri, &local_name, name)
+            });
+            let event_handler = if is_event {
+                Some(self.parse_mxml_event_handler(&Location::with_offsets(self.compilation_unit(), i, j)))
+            } else {
+                None
+            };
+            let binding = if self.parse_mxml_bindings && !is_event {
+                self.parse_mxml_binding(&Location::with_offsets(self.compilation_unit(), i, j))
+            } else {
+                None
+            };
             let attrib = Rc::new(MxmlAttribute {
                 location: attribute.location.clone(),
                 name: MxmlName {
@@ -4937,6 +5376,8 @@ impl<'input> Parser<'input> {
                 },
                 value: (attribute_value, attribute.value.1.clone()),
                 xmlns: false,
+                binding,
+                event_handler,
             });
             match attrib.name.resolve_prefix(namespace) {
                 Ok(_) => {
@@ -5030,10 +5471,15 @@ impl<'input> Parser<'input> {
                         data: if data.is_empty() { None } else { Some(data) },
                     }));
                 }
-            } else if let Token::XmlText(text) = self.token.0.clone() {
-                let location = self.token_location();
-                self.next_ie_xml_content();
-                content.push(Rc::new(MxmlContent::Characters((unescape_xml(&text), location))));
+            // MXML does not delimit expressions by braces, therefore
+            // braces are part of the character data.
+            } else if matches!(self.token.0, Token::XmlText(_) | Token::BlockOpen) {
+                let start = self.token_location();
+                while matches!(self.token.0, Token::XmlText(_) | Token::BlockOpen) {
+                    self.next_ie_xml_content();
+                }
+                let location = start.combine_with(self.previous_token.1.clone());
+                content.push(Rc::new(MxmlContent::Characters((unescape_xml(&location.text()), location))));
             } else if self.consume_and_ie_xml_tag(Token::Lt) {
                 let start = self.token_location();
                 let element = self.parse_mxml_element(start, namespace, encoding);
@@ -5103,7 +5549,73 @@ fn parse_include_directive_source(nested_compilation_unit: Rc<CompilationUnit>,
     (packages, parser.parse_directives(context))
 }
 
-fn join_asdoc_content(content: &Vec<(String, Location)>) -> (String, Location) {
+/// Returns the non whitespace character data and CDATA chunks of
+/// a MXML element, each with the host offset it starts at.
+pub(crate) fn mxml_text_fragments(element: &MxmlElement) -> Vec<(String, usize)> {
+    let mut fragments: Vec<(String, usize)> = vec![];
+    for node in element.content.iter().flatten() {
+        match node.as_ref() {
+            MxmlContent::CData((_, location)) => {
+                fragments.push((node.inner_text(), location.first_offset() + "<![CDATA[".len()));
+            },
+            MxmlContent::Characters((text, location)) if !text.trim().is_empty() => {
+                fragments.push((text.clone(), location.first_offset()));
+            },
+            _ => {},
+        }
+    }
+    fragments
+}
+
+/// Decodes the characters of raw XML text, resolving entities, where
+/// each character is given with the host range it is decoded from.
+fn decode_xml_characters(raw: &str, offset: usize) -> Vec<(char, usize, usize)> {
+    let mut characters = vec![];
+    let mut i = 0;
+    while i < raw.len() {
+        if let Some(entity) = regex_find!(r"^&(#x[0-9a-fA-F]+|#[0-9]+|[A-Za-z][A-Za-z0-9]*);", &raw[i..]) {
+            for ch in unescape_xml(entity).chars() {
+                characters.push((ch, offset + i, offset + i + entity.len()));
+            }
+            i += entity.len();
+            continue;
+        }
+        let ch = raw[i..].chars().next().unwrap();
+        characters.push((ch, offset + i, offset + i + ch.len_utf8()));
+        i += ch.len_utf8();
+    }
+    characters
+}
+
+/// Finds the index of the closing brace of a data binding expression,
+/// skipping nested braces and string literals.
+fn find_mxml_binding_end(characters: &[(char, usize, usize)], mut i: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    while i < characters.len() {
+        let ch = characters[i].0;
+        if let Some(q) = quote {
+            if ch == '\\' {
+                i += 1;
+            } else if ch == q {
+                quote = None;
+            }
+        } else if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+        } else if ch == '{' {
+            depth += 1;
+        } else if ch == '}' {
+            if depth == 0 {
+                return Some(i);
+            }
+            depth -= 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+fn join_asdoc_content(content: &[(String, Location)]) -> (String, Location) {
     // Ignore first empty lines
     let mut i = 0usize;
     for content1 in content.iter() {
@@ -5247,6 +5759,25 @@ pub struct ParserOptions {
     /// Indicates the range of characters that shall be parsed,
     /// the first and last byte indices respectively.
     pub byte_range: Option<(usize, usize)>,
+    /// Custom ASDoc tags recognized in addition to the built-in tags.
+    /// Custom tags take precedence over built-in tags of the same name.
+    /// Default: empty.
+    pub custom_asdoc_tags: Rc<AsDocTagRegistry>,
+    /// For MXML, indicates whether to parse the `<fx:Script>` elements
+    /// as ActionScript directives in a class block context. Default: false.
+    pub parse_mxml_scripts: bool,
+    /// For MXML, indicates whether to parse data bindings in attribute values
+    /// and character content. Default: false.
+    pub parse_mxml_bindings: bool,
+    /// For MXML, indicates whether to parse the `<fx:Style>` elements
+    /// as CSS documents. Default: false.
+    pub parse_mxml_styles: bool,
+    /// For MXML, the events whose attribute values are parsed as
+    /// event handlers. Default: empty.
+    pub mxml_events: Rc<MxmlEventRegistry>,
+    /// Resolves sources referenced from the parsed source, such as
+    /// `<fx:Script source="...">` and `<fx:Style source="...">`. Default: `FileSystemSourceResolver`.
+    pub source_resolver: Rc<dyn SourceResolver>,
 }
 
 impl Default for ParserOptions {
@@ -5254,6 +5785,12 @@ impl Default for ParserOptions {
         Self {
             ignore_xml_whitespace: true,
             byte_range: None,
+            custom_asdoc_tags: Rc::new(AsDocTagRegistry::new()),
+            parse_mxml_scripts: false,
+            parse_mxml_bindings: false,
+            parse_mxml_styles: false,
+            mxml_events: Rc::new(MxmlEventRegistry::new()),
+            source_resolver: Rc::new(FileSystemSourceResolver),
         }
     }
 }
@@ -5306,6 +5843,13 @@ impl<'input> ParserFacade<'input> {
         parser.parse_directives(context)
     }
 
+    /// Parses a sequence of meta-data until end-of-file.
+    pub fn parse_metadata_list(&self) -> Vec<Rc<Metadata>> {
+        let mut parser = self.create_parser();
+        parser.next();
+        parser.parse_metadata_list()
+    }
+
     /// Parses `Mxml` until end-of-file.
     pub fn parse_mxml(&self) -> Rc<Mxml> {
         let mut parser = self.create_parser();
@@ -5313,3 +5857,152 @@ impl<'input> ParserFacade<'input> {
         parser.parse_mxml()
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use crate::ns::*;
+
+    struct MapSourceResolver(HashMap<String, String>);
+
+    impl SourceResolver for MapSourceResolver {
+        fn resolve(&self, source: &str, _origin: &Rc<CompilationUnit>) -> Result<(String, String), SourceResolutionError> {
+            self.0.get(source).map(|content| (source.to_owned(), content.clone())).ok_or(SourceResolutionError::FailedToReadFile)
+        }
+    }
+
+    fn mxml_script_options(files: &[(&str, &str)]) -> ParserOptions {
+        ParserOptions {
+            parse_mxml_scripts: true,
+            source_resolver: Rc::new(MapSourceResolver(files.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect())),
+            ..default()
+        }
+    }
+
+    fn first_child(mxml: &Rc<Mxml>) -> Rc<MxmlElement> {
+        let MxmlContent::Element(root) = mxml.content[0].as_ref() else { panic!() };
+        let MxmlContent::Element(child) = root.content.as_ref().unwrap()[0].as_ref() else { panic!() };
+        child.clone()
+    }
+
+    #[test]
+    fn test_mxml_inline_script() {
+        let source = "<s:Application xmlns:fx=\"http://ns.adobe.com/mxml/2009\" xmlns:s=\"library://ns.adobe.com/flex/spark\">\n<fx:Script><![CDATA[\nvar x: Number = 10;\nfunction f(): void {}\n]]></fx:Script>\n</s:Application>";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let mxml = ParserFacade(&compilation_unit, mxml_script_options(&[])).parse_mxml();
+        assert!(!compilation_unit.invalidated());
+        let script = first_child(&mxml);
+        let directives = script.script_directives.as_ref().unwrap();
+        assert_eq!(directives.len(), 2);
+        assert_eq!(directives[0].location().first_line_number(), 3);
+        assert_eq!(directives[0].location().text(), "var x: Number = 10;");
+    }
+
+    #[test]
+    fn test_mxml_script_source() {
+        let source = "<fx:Object xmlns:fx=\"http://ns.adobe.com/mxml/2009\">\n<fx:Script source=\"x.as\"/>\n<fx:Script source=\"y.as\"/>\n</fx:Object>";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let mxml = ParserFacade(&compilation_unit, mxml_script_options(&[("x.as", "var x;")])).parse_mxml();
+        let script = first_child(&mxml);
+        assert_eq!(script.script_directives.as_ref().unwrap().len(), 1);
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].id(), DiagnosticKind::FailedToIncludeFile.id());
+    }
+
+    #[test]
+    fn test_mxml_style_source() {
+        let source = "<fx:Object xmlns:fx=\"http://ns.adobe.com/mxml/2009\">\n<fx:Style source=\"x.css\"/>\n</fx:Object>";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let mxml = ParserFacade(&compilation_unit, ParserOptions {
+            parse_mxml_styles: true,
+            ..mxml_script_options(&[("x.css", ".c { color: red }")])
+        }).parse_mxml();
+        assert!(!compilation_unit.invalidated());
+        let external = first_child(&mxml);
+        assert_eq!(external.style_document.as_ref().unwrap().directives.len(), 1);
+    }
+
+    fn parse_mxml_binding_of(value: &str) -> (Rc<CompilationUnit>, Option<Rc<MxmlBinding>>) {
+        let source = format!("<s:Label xmlns:s=\"library://ns.adobe.com/flex/spark\" text=\"{value}\"/>");
+        let compilation_unit = CompilationUnit::new(None, source);
+        let mxml = ParserFacade(&compilation_unit, ParserOptions {
+            parse_mxml_bindings: true,
+            ..default()
+        }).parse_mxml();
+        let MxmlContent::Element(root) = mxml.content[0].as_ref() else { panic!() };
+        let binding = root.attributes.iter().find(|a| a.name.name == "text").unwrap().binding.clone();
+        (compilation_unit, binding)
+    }
+
+    #[test]
+    fn test_mxml_bindings() {
+        let (compilation_unit, binding) = parse_mxml_binding_of("Hi {name}!");
+        assert!(!compilation_unit.invalidated());
+        let binding = binding.unwrap();
+        assert!(!binding.two_way);
+        assert_eq!(binding.segments.len(), 3);
+        assert!(matches!(&binding.segments[0], MxmlBindingSegment::Literal((text, _)) if text == "Hi "));
+        let MxmlBindingSegment::Expression(exp) = &binding.segments[1] else { panic!() };
+        assert_eq!(exp.location().text(), "name");
+
+        let (compilation_unit, binding) = parse_mxml_binding_of("@{field.x}");
+        assert!(!compilation_unit.invalidated());
+        let binding = binding.unwrap();
+        assert!(binding.two_way);
+        assert_eq!(binding.segments.len(), 1);
+
+        let (compilation_unit, binding) = parse_mxml_binding_of("\\{not bound}");
+        assert!(!compilation_unit.invalidated());
+        assert!(binding.is_none());
+
+        let (compilation_unit, _) = parse_mxml_binding_of("{a");
+        assert_eq!(compilation_unit.diagnostics()[0].id(), DiagnosticKind::UnterminatedMxmlBinding.id());
+    }
+
+    #[test]
+    fn test_mxml_content_binding() {
+        let compilation_unit = CompilationUnit::new(None, r#"<s:Label xmlns:s="library://ns.adobe.com/flex/spark">{user.name}</s:Label>"#.into());
+        let mxml = ParserFacade(&compilation_unit, ParserOptions {
+            parse_mxml_bindings: true,
+            ..default()
+        }).parse_mxml();
+        assert!(!compilation_unit.invalidated());
+        let MxmlContent::Element(root) = mxml.content[0].as_ref() else { panic!() };
+        let binding = root.content_binding.clone().unwrap();
+        let [MxmlBindingSegment::Expression(exp)] = binding.segments.as_slice() else { panic!() };
+        assert_eq!(exp.location().text(), "user.name");
+    }
+
+    #[test]
+    fn test_mxml_event_handlers() {
+        let component = CompilationUnit::new(None, "[Event(name=\"click\", type=\"flash.events.MouseEvent\")] class Button {}".to_owned());
+        let program = ParserFacade(&component, default()).parse_program();
+        let Directive::ClassDefinition(defn) = program.directives[0].as_ref() else { panic!() };
+        let mut events = MxmlEventRegistry::new();
+        events.add_event("creationComplete");
+        events.add_component_metadata("library://ns.adobe.com/flex/spark", "Button", &Attribute::find_metadata(&defn.attributes));
+
+        let source = "<s:Button xmlns:s=\"library://ns.adobe.com/flex/spark\" click=\"onClick(event); model.dirty = true\" creationComplete=\"if (a &amp;&amp; b) { init() }\" label=\"{x}\" mouseOver=\"{\"/>";
+        let compilation_unit = CompilationUnit::new(None, source.to_owned());
+        let mxml = ParserFacade(&compilation_unit, ParserOptions {
+            parse_mxml_bindings: true,
+            mxml_events: Rc::new(events),
+            ..default()
+        }).parse_mxml();
+        let MxmlContent::Element(root) = mxml.content[0].as_ref() else { panic!() };
+        let attribute = |name: &str| root.attributes.iter().find(|a| a.name.name == name).unwrap().clone();
+
+        let click = attribute("click").event_handler.clone().unwrap();
+        assert_eq!(click.len(), 2);
+        assert_eq!(click[0].location().text(), "onClick(event);");
+        assert_eq!(attribute("creationComplete").event_handler.as_ref().unwrap().len(), 1);
+        assert!(attribute("label").event_handler.is_none());
+        assert!(attribute("label").binding.is_some());
+
+        // mouseOver is not an event, so its value is an unterminated binding
+        let diagnostics = compilation_unit.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].kind() == DiagnosticKind::UnterminatedMxmlBinding);
+    }
+}
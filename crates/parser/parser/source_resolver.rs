@@ -0,0 +1,40 @@
+use crate::ns::*;
+use file_paths::FlexPath;
+
+/// Indicates why a source referenced from another source could not be resolved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SourceResolutionError {
+    /// The referencing compilation unit is not a file and no working directory is available.
+    ParentSourceIsNotAFile,
+    /// The referenced file could not be read.
+    FailedToReadFile,
+}
+
+/// Resolves sources referenced from a compilation unit, such as
+/// `<fx:Script source="...">` and `<fx:Style source="...">` in MXML.
+pub trait SourceResolver {
+    /// Resolves a source path relative to the referencing compilation unit,
+    /// returning the resolved file path and the file content.
+    fn resolve(&self, source: &str, origin: &Rc<CompilationUnit>) -> Result<(String, String), SourceResolutionError>;
+}
+
+/// Resolves sources from the file system, relative to the directory
+/// of the referencing file, or to the working directory when
+/// the referencing compilation unit is not a file.
+pub struct FileSystemSourceResolver;
+
+impl SourceResolver for FileSystemSourceResolver {
+    fn resolve(&self, source: &str, origin: &Rc<CompilationUnit>) -> Result<(String, String), SourceResolutionError> {
+        let origin_file_path = if let Some(file_path) = origin.file_path() {
+            file_path
+        } else if let Ok(dir) = std::env::current_dir() {
+            // Resolve relative to the working directory
+            FlexPath::from_n_native([dir.to_string_lossy().as_ref(), "_"]).to_string_with_flex_separator()
+        } else {
+            return Err(SourceResolutionError::ParentSourceIsNotAFile);
+        };
+        let file_path = FlexPath::from_n_native([origin_file_path.as_ref(), "..", source]).to_string_with_flex_separator();
+        let content = std::fs::read_to_string(&file_path).map_err(|_| SourceResolutionError::FailedToReadFile)?;
+        Ok((file_path, content))
+    }
+}
@@ -28,6 +28,7 @@ pub enum Token {
     CssAtNamespace,
     CssAtMedia,
     CssAtFontFace,
+    CssAtImport,
     CssImportant,
     CssSemicolons,
 
@@ -199,6 +200,7 @@ impl ToString for Token {
             Token::CssAtNamespace => "at-namespace",
             Token::CssAtMedia => "at-media",
             Token::CssAtFontFace => "at-font-face",
+            Token::CssAtImport => "at-import",
             Token::CssImportant => "'!important'",
             Token::CssSemicolons => "semicolon",
 
@@ -161,5 +161,11 @@ pub use mxml::*;
 mod css;
 pub use css::*;
 
+// FXG
+mod fxg;
+pub use fxg::*;
+
 mod tree_semantics;
-pub use tree_semantics::*;
\ No newline at end of file
+pub use tree_semantics::*;
+mod tree_walker;
+pub use tree_walker::*;
\ No newline at end of file
@@ -13,14 +13,23 @@ pub enum AsDocTag {
     Author(String),
     Copy(Rc<AsDocReference>),
     Created(String),
+    /// A custom tag recognized through an `AsDocTagRegistry`.
+    Custom {
+        name: String,
+        content: AsDocCustomTagContent,
+    },
     Default(String),
     Deprecated {
         message: Option<String>,
     },
     EventType(Rc<Expression>),
     Example(String),
+    ExampleText(String),
+    Helpid(String),
+    IncludeExample(String),
     InheritDoc,
     Internal(String),
+    Keyword(String),
     Langversion(String),
     Param {
         name: String,
@@ -38,9 +47,22 @@ pub enum AsDocTag {
         class_reference: Rc<Expression>,
         description: Option<String>,
     },
+    Tiptext(String),
     Version(String),
 }
 
+/// The content of a custom ASDoc tag, according to its `AsDocTagShape`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AsDocCustomTagContent {
+    Text(String),
+    Reference(Rc<AsDocReference>),
+    NameAndDescription {
+        name: String,
+        description: String,
+    },
+    Flag,
+}
+
 /// An ASDoc reference consisting of an optional base and
 /// an optional instance property fragment (`#x`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
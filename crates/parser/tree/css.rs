@@ -31,6 +31,7 @@ impl ToString for CssCombinatorType {
 pub enum CssDirective {
     Invalidated(InvalidatedNode),
     FontFace(CssFontFace),
+    Import(CssImport),
     MediaQuery(CssMediaQuery),
     NamespaceDefinition(CssNamespaceDefinition),
     Rule(CssRule),
@@ -41,6 +42,7 @@ impl CssDirective {
         match self {
             Self::Invalidated(v) => v.location.clone(),
             Self::FontFace(v) => v.location.clone(),
+            Self::Import(v) => v.location.clone(),
             Self::MediaQuery(v) => v.location.clone(),
             Self::NamespaceDefinition(v) => v.location.clone(),
             Self::Rule(v) => v.location.clone(),
@@ -96,6 +98,15 @@ impl CssPropertyValue {
         }
     }
 
+    /// Returns the color of a hexadecimal, named or functional color.
+    pub fn as_color(&self) -> Option<CssColor> {
+        match self {
+            Self::Color(v) => Some(v.color()),
+            Self::RgbColor(v) => Some(v.color()),
+            _ => None,
+        }
+    }
+
     pub fn as_array(&self) -> Option<&CssArrayPropertyValue> {
         let Self::Array(v) = self else { return None; };
         Some(v)
@@ -217,35 +228,31 @@ impl ToString for CssAttributeOperator {
     }
 }
 
+/// A hexadecimal color, such as `#FF0000` or `#FF000080`,
+/// or a color name, such as `red` or `transparent`.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CssColorPropertyValue {
     pub location: Location,
     pub color_int: u32,
+    /// Alpha ratio from 0 to 1.
+    pub alpha: f64,
 }
 
 impl CssColorPropertyValue {
+    /// Parses a hexadecimal color of 3, 4, 6 or 8 digits.
     pub fn from_hex(location: Location, token_text: &str) -> Result<Self, ParserError> {
-        let mut token_text = if token_text.starts_with('#') { token_text.to_owned() } else {
-            "#".to_owned() + token_text
-        };
-        if token_text.len() == 4 {
-            let mut six = String::new();
-            let chars: Vec<_> = token_text.chars().collect();
-            six.push('#');
-            six.push(chars[1]);
-            six.push(chars[1]);
-            six.push(chars[2]);
-            six.push(chars[2]);
-            six.push(chars[3]);
-            six.push(chars[3]);
-            token_text = six;
-        }
+        let color = CssColor::from_hex(token_text).ok_or(ParserError::Common)?;
         Ok(Self {
             location,
-            color_int: u32::from_str_radix(&token_text[1..], 16).map_err(|_| ParserError::Common)?.clamp(0x000000, 0xFFFFFF),
+            color_int: color.rgb,
+            alpha: color.alpha,
         })
     }
 
+    pub fn color(&self) -> CssColor {
+        CssColor::new(self.color_int, self.alpha)
+    }
+
     pub fn text(&self) -> String {
         self.location.text()
     }
@@ -258,10 +265,14 @@ pub struct CssNumberPropertyValue {
     pub unit: Option<String>,
 }
 
+/// A functional color, such as `rgb(255, 0, 0)`, `rgba(255, 0, 0, 0.5)`,
+/// `hsl(0, 100%, 50%)` or `hsla(0, 100%, 50%, 0.5)`.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CssRgbColorPropertyValue {
     pub location: Location,
     pub color_int: u32,
+    /// Alpha ratio from 0 to 1.
+    pub alpha: f64,
 }
 
 impl CssRgbColorPropertyValue {
@@ -271,9 +282,14 @@ impl CssRgbColorPropertyValue {
             color_int: (Self::parse_component(&raw_arguments[0])? << 16)
                     |  (Self::parse_component(&raw_arguments[1])? << 8)
                     |   Self::parse_component(&raw_arguments[2])?,
+            alpha: if let Some(alpha) = raw_arguments.get(3) { Self::parse_alpha(alpha)? } else { 1.0 },
         })
     }
 
+    pub fn color(&self) -> CssColor {
+        CssColor::new(self.color_int, self.alpha)
+    }
+
     fn parse_component(input: &str) -> Result<u32, ParserError> {
         let i = input.find('%');
         let v: u32;
@@ -288,6 +304,16 @@ impl CssRgbColorPropertyValue {
         }
         Ok(v.clamp(0, 255))
     }
+
+    /// Parses an alpha ratio from 0 to 1 or a percentage.
+    fn parse_alpha(input: &str) -> Result<f64, ParserError> {
+        let ratio = if let Some(percent) = input.strip_suffix('%') {
+            f64::from_str(percent).map_err(|_| ParserError::Common)? / 100.0
+        } else {
+            f64::from_str(input).map_err(|_| ParserError::Common)?
+        };
+        Ok(ratio.clamp(0.0, 1.0))
+    }
 }
 
 /// A CSS text is a string value written without quotes.
@@ -463,9 +489,21 @@ pub struct CssRule {
     pub properties: Vec<Rc<CssProperty>>,
 }
 
+/// An `@import` directive, such as `@import url("theme.css");`
+/// or `@import "mobile.css" (os-platform: "IOS");`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CssImport {
+    pub location: Location,
+    pub url: (String, Location),
+    /// Media query conditions the imported style sheet applies to;
+    /// empty if the import is unconditional.
+    pub media: Vec<Rc<CssMediaQueryCondition>>,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CssNamespaceDefinition {
     pub location: Location,
+    /// The prefix, empty for the default namespace.
     pub prefix: (String, Location),
     pub uri: (String, Location),
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+use crate::ns::*;
+use serde::{Serialize, Deserialize};
+
+/// A FXG 2.0 graphics document.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FxgDocument {
+    pub location: Location,
+    /// The MXML document the FXG document is built from.
+    pub mxml: Rc<Mxml>,
+    /// The `<Graphic>` root element.
+    pub root: Option<Rc<FxgElement>>,
+}
+
+/// A FXG element, such as `<Group>`, `<Rect>` or `<SolidColor>`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FxgElement {
+    pub location: Location,
+    pub name: (String, Location),
+    /// The attributes valid for the element, with their parsed values.
+    pub attributes: Vec<Rc<FxgAttribute>>,
+    /// Property elements, such as `<fill>`, `<stroke>` and `<transform>`.
+    pub properties: Vec<Rc<FxgPropertyElement>>,
+    /// Graphical child elements, such as those of a `<Group>`.
+    pub children: Vec<Rc<FxgElement>>,
+    /// The MXML element the FXG element is built from.
+    pub source: Rc<MxmlElement>,
+}
+
+impl FxgElement {
+    pub fn attribute(&self, name: &str) -> Option<Rc<FxgAttribute>> {
+        self.attributes.iter().find(|attribute| attribute.name.0 == name).cloned()
+    }
+
+    pub fn property(&self, name: &str) -> Option<Rc<FxgPropertyElement>> {
+        self.properties.iter().find(|property| property.name.0 == name).cloned()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FxgAttribute {
+    pub location: Location,
+    pub name: (String, Location),
+    pub value: FxgValue,
+}
+
+/// A property element, such as `<fill>`, containing
+/// the element assigned to the property.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FxgPropertyElement {
+    pub location: Location,
+    pub name: (String, Location),
+    pub elements: Vec<Rc<FxgElement>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum FxgValue {
+    Number(f64),
+    /// A `#RRGGBB` color.
+    Color(u32),
+    Boolean(bool),
+    /// An enumeration value, such as a `blendMode`.
+    Enumeration(String),
+    String(String),
+    Path(Vec<FxgPathCommand>),
+}
+
+impl FxgValue {
+    pub fn as_number(&self) -> Option<f64> {
+        if let Self::Number(value) = self { Some(*value) } else { None }
+    }
+
+    pub fn as_color(&self) -> Option<u32> {
+        if let Self::Color(value) = self { Some(*value) } else { None }
+    }
+}
+
+/// A path command, such as `L 10 20`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FxgPathCommand {
+    /// The command letter; lowercase letters denote relative coordinates.
+    pub command: char,
+    pub arguments: Vec<f64>,
+}
+
+impl FxgPathCommand {
+    /// Returns the number of arguments of a path command letter,
+    /// or `None` if the letter is not a path command.
+    pub fn argument_count(command: char) -> Option<usize> {
+        match command.to_ascii_uppercase() {
+            'Z' => Some(0),
+            'H' | 'V' => Some(1),
+            'M' | 'L' | 'T' => Some(2),
+            'Q' | 'S' => Some(4),
+            'C' => Some(6),
+            _ => None,
+        }
+    }
+}
@@ -28,9 +28,34 @@ pub struct MxmlElement {
     pub namespace: Rc<MxmlNamespace>,
     pub content: Option<Vec<Rc<MxmlContent>>>,
     pub closing_name: Option<MxmlName>,
+    /// For a `<fx:Script>` element, the directives of the script, if
+    /// the `parse_mxml_scripts` parser option is enabled.
+    pub script_directives: Option<Vec<Rc<Directive>>>,
+    /// The data binding contained in the character content, if
+    /// the `parse_mxml_bindings` parser option is enabled.
+    pub content_binding: Option<Rc<MxmlBinding>>,
+    /// For a `<fx:Style>` element, the style sheet, if
+    /// the `parse_mxml_styles` parser option is enabled.
+    pub style_document: Option<Rc<CssDocument>>,
 }
 
 impl MxmlElement {
+    /// Indicates whether the element is a MXML language element of a given
+    /// local name, such as `<fx:Script>` or `<mx:Script>`.
+    pub fn is_language_element(&self, name: &str) -> bool {
+        self.name.resolve_name(&self.namespace).is_ok_and(|(uri, localname)| {
+            localname == name && (uri == MxmlNamespace::MXML_2009 || uri == MxmlNamespace::MXML_2006)
+        })
+    }
+
+    /// Returns the unescaped value of the attribute of a given name
+    /// that belongs to no namespace.
+    pub fn attribute_value(&self, name: &str) -> Option<&(String, Location)> {
+        self.attributes.iter()
+            .find(|attrib| !attrib.xmlns && attrib.name.prefix.is_none() && attrib.name.name == name)
+            .map(|attrib| &attrib.value)
+    }
+
     pub fn inner_text(&self) -> String {
         let mut j = String::new();
         if let Some(c) = self.content.as_ref() {
@@ -50,6 +75,35 @@ pub struct MxmlAttribute {
     pub name: MxmlName,
     /// Attribute value. The location data includes the quotes.
     pub value: (String, Location),
+    /// The data binding contained in the attribute value, if
+    /// the `parse_mxml_bindings` parser option is enabled.
+    pub binding: Option<Rc<MxmlBinding>>,
+    /// The directives of the event handler given by the attribute value,
+    /// if the attribute names an event registered in the `mxml_events`
+    /// parser option. The directives are parsed in a default directive
+    /// context; the `event` identifier is not declared by the parser.
+    pub event_handler: Option<Vec<Rc<Directive>>>,
+}
+
+/// A data binding, such as `{model.user.name}`, `Hi {name}!` or `@{field}`.
+///
+/// Expressions containing XML entities are parsed from a separate
+/// compilation unit, in which case their locations do not refer to
+/// the MXML compilation unit.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MxmlBinding {
+    /// The location of the text containing the binding, excluding quotes.
+    pub location: Location,
+    /// Indicates whether the binding is a two-way binding (`@{...}`).
+    pub two_way: bool,
+    pub segments: Vec<MxmlBindingSegment>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum MxmlBindingSegment {
+    /// A literal segment, with escaped braces (`\{` and `\}`) resolved.
+    Literal((String, Location)),
+    Expression(Rc<Expression>),
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -161,6 +215,12 @@ impl MxmlNamespace {
     /// Returns the prefix used for the default XML namespace.
     pub const DEFAULT_NAMESPACE: &'static str = "";
 
+    /// The MXML 2009 language namespace, conventionally prefixed `fx`.
+    pub const MXML_2009: &'static str = "http://ns.adobe.com/mxml/2009";
+
+    /// The MXML 2006 language namespace, conventionally prefixed `mx`.
+    pub const MXML_2006: &'static str = "http://www.adobe.com/2006/mxml";
+
     /// Constructs an empty set of namespace mappings.
     pub fn new(parent: Option<&Rc<MxmlNamespace>>) -> Self {
         let mut ns = Self {
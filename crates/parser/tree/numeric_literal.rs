@@ -70,7 +70,7 @@ impl NumericLiteral {
                 return n.map_err(|_| ParserError::Common);
             }
         }
-        i64::from_str(&s).map_err(|_| ParserError::Common)
+        i64::from_str(&(if negative { "-" } else { "" }.to_owned() + &s)).map_err(|_| ParserError::Common)
     }
 
     /// Parses a signed integer either in
@@ -86,7 +86,7 @@ impl NumericLiteral {
                 return n.map_err(|_| ParserError::Common);
             }
         }
-        i32::from_str(&s).map_err(|_| ParserError::Common)
+        i32::from_str(&(if negative { "-" } else { "" }.to_owned() + &s)).map_err(|_| ParserError::Common)
     }
 
     /// Parses an unsigned integer either in
@@ -126,7 +126,7 @@ impl NumericLiteral {
                 return n.map_or(Err(ParserError::Common), |n| Ok(n));
             }
         }
-        BigInt::from_str(&s).map_err(|_| ParserError::Common)
+        BigInt::from_str(&(if negative { "-" } else { "" }.to_owned() + &s)).map_err(|_| ParserError::Common)
     }
 }
 
@@ -0,0 +1,423 @@
+use crate::ns::*;
+
+/// A visitor of the directives and expressions of an ActionScript 3 tree.
+///
+/// Each method is invoked before the children of a node are visited
+/// and returns whether to visit them.
+pub trait TreeVisitor {
+    fn visit_directive(&mut self, _directive: &Rc<Directive>) -> bool {
+        true
+    }
+
+    fn visit_expression(&mut self, _expression: &Rc<Expression>) -> bool {
+        true
+    }
+}
+
+/// Walks the directives and expressions of an ActionScript 3 tree
+/// in source order, including type annotations, the directives of
+/// `include` directives and the expressions of ActionScript 3 attributes.
+/// Meta-data and ASDoc comments are not walked.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// struct StringCounter(usize);
+///
+/// impl TreeVisitor for StringCounter {
+///     fn visit_expression(&mut self, expression: &Rc<Expression>) -> bool {
+///         if let Expression::StringLiteral(_) = expression.as_ref() {
+///             self.0 += 1;
+///         }
+///         true
+///     }
+/// }
+///
+/// let compilation_unit = CompilationUnit::new(None, r#"function f(): void { trace("a", ["b"]) }"#.into());
+/// let program = ParserFacade(&compilation_unit, default()).parse_program();
+/// let mut counter = StringCounter(0);
+/// TreeWalker::walk_program(&mut counter, &program);
+/// assert_eq!(counter.0, 2);
+/// ```
+pub struct TreeWalker;
+
+impl TreeWalker {
+    pub fn walk_program(visitor: &mut dyn TreeVisitor, program: &Program) {
+        for package in program.packages.iter() {
+            Self::walk_directives(visitor, &package.block.directives);
+        }
+        Self::walk_directives(visitor, &program.directives);
+    }
+
+    pub fn walk_directives(visitor: &mut dyn TreeVisitor, directives: &[Rc<Directive>]) {
+        for directive in directives.iter() {
+            Self::walk_directive(visitor, directive);
+        }
+    }
+
+    pub fn walk_directive(visitor: &mut dyn TreeVisitor, directive: &Rc<Directive>) {
+        if !visitor.visit_directive(directive) {
+            return;
+        }
+        match directive.as_ref() {
+            Directive::EmptyStatement(_) |
+            Directive::BreakStatement(_) |
+            Directive::ContinueStatement(_) |
+            Directive::Invalidated(_) |
+            Directive::ImportDirective(_) |
+            Directive::PackageConcatDirective(_) => {},
+            Directive::ExpressionStatement(d) => Self::walk_expression(visitor, &d.expression),
+            Directive::SuperStatement(d) => Self::walk_expressions(visitor, &d.arguments),
+            Directive::Block(d) => Self::walk_directives(visitor, &d.directives),
+            Directive::LabeledStatement(d) => Self::walk_directive(visitor, &d.substatement),
+            Directive::IfStatement(d) => {
+                Self::walk_expression(visitor, &d.test);
+                Self::walk_directive(visitor, &d.consequent);
+                if let Some(alternative) = &d.alternative {
+                    Self::walk_directive(visitor, alternative);
+                }
+            },
+            Directive::SwitchStatement(d) => {
+                Self::walk_expression(visitor, &d.discriminant);
+                for case in d.cases.iter() {
+                    for label in case.labels.iter() {
+                        if let CaseLabel::Case((exp, _)) = label {
+                            Self::walk_expression(visitor, exp);
+                        }
+                    }
+                    Self::walk_directives(visitor, &case.directives);
+                }
+            },
+            Directive::SwitchTypeStatement(d) => {
+                Self::walk_expression(visitor, &d.discriminant);
+                for case in d.cases.iter() {
+                    if let Some(parameter) = &case.parameter {
+                        Self::walk_typed_destructuring(visitor, parameter);
+                    }
+                    Self::walk_directives(visitor, &case.block.directives);
+                }
+            },
+            Directive::DoStatement(d) => {
+                Self::walk_directive(visitor, &d.body);
+                Self::walk_expression(visitor, &d.test);
+            },
+            Directive::WhileStatement(d) => {
+                Self::walk_expression(visitor, &d.test);
+                Self::walk_directive(visitor, &d.body);
+            },
+            Directive::ForStatement(d) => {
+                match &d.init {
+                    Some(ForInitializer::Expression(exp)) => Self::walk_expression(visitor, exp),
+                    Some(ForInitializer::VariableDefinition(defn)) => Self::walk_variable_bindings(visitor, &defn.bindings),
+                    None => {},
+                }
+                if let Some(test) = &d.test {
+                    Self::walk_expression(visitor, test);
+                }
+                if let Some(update) = &d.update {
+                    Self::walk_expression(visitor, update);
+                }
+                Self::walk_directive(visitor, &d.body);
+            },
+            Directive::ForInStatement(d) => {
+                match &d.left {
+                    ForInBinding::Expression(exp) => Self::walk_expression(visitor, exp),
+                    ForInBinding::VariableDefinition(defn) => Self::walk_variable_bindings(visitor, &defn.bindings),
+                }
+                Self::walk_expression(visitor, &d.right);
+                Self::walk_directive(visitor, &d.body);
+            },
+            Directive::WithStatement(d) => {
+                Self::walk_expression(visitor, &d.object);
+                Self::walk_directive(visitor, &d.body);
+            },
+            Directive::ReturnStatement(d) => {
+                if let Some(exp) = &d.expression {
+                    Self::walk_expression(visitor, exp);
+                }
+            },
+            Directive::ThrowStatement(d) => Self::walk_expression(visitor, &d.expression),
+            Directive::DefaultXmlNamespaceStatement(d) => Self::walk_expression(visitor, &d.right),
+            Directive::TryStatement(d) => {
+                Self::walk_directives(visitor, &d.block.directives);
+                for catch_clause in d.catch_clauses.iter() {
+                    Self::walk_typed_destructuring(visitor, &catch_clause.parameter);
+                    Self::walk_directives(visitor, &catch_clause.block.directives);
+                }
+                if let Some(finally_clause) = &d.finally_clause {
+                    Self::walk_directives(visitor, &finally_clause.block.directives);
+                }
+            },
+            Directive::ConfigurationDirective(d) => Self::walk_directive(visitor, &d.directive),
+            Directive::UseNamespaceDirective(d) => Self::walk_expression(visitor, &d.expression),
+            Directive::IncludeDirective(d) => {
+                for package in d.nested_packages.iter() {
+                    Self::walk_directives(visitor, &package.block.directives);
+                }
+                Self::walk_directives(visitor, &d.nested_directives);
+            },
+            Directive::NormalConfigurationDirective(d) => Self::walk_directive(visitor, &d.directive),
+            Directive::DirectiveInjection(d) => {
+                let directives = d.directives.borrow().clone();
+                Self::walk_directives(visitor, &directives);
+            },
+            Directive::VariableDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                Self::walk_variable_bindings(visitor, &d.bindings);
+            },
+            Directive::FunctionDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                Self::walk_function_common(visitor, &d.common);
+            },
+            Directive::ClassDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                if let Some(exp) = &d.extends_clause {
+                    Self::walk_expression(visitor, exp);
+                }
+                if let Some(list) = &d.implements_clause {
+                    Self::walk_expressions(visitor, list);
+                }
+                Self::walk_directives(visitor, &d.block.directives);
+            },
+            Directive::EnumDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                if let Some(exp) = &d.as_clause {
+                    Self::walk_expression(visitor, exp);
+                }
+                Self::walk_directives(visitor, &d.block.directives);
+            },
+            Directive::InterfaceDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                if let Some(list) = &d.extends_clause {
+                    Self::walk_expressions(visitor, list);
+                }
+                Self::walk_directives(visitor, &d.block.directives);
+            },
+            Directive::TypeDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                Self::walk_expression(visitor, &d.right);
+            },
+            Directive::NamespaceDefinition(d) => {
+                Self::walk_attributes(visitor, &d.attributes);
+                if let Some(exp) = &d.right {
+                    Self::walk_expression(visitor, exp);
+                }
+            },
+        }
+    }
+
+    pub fn walk_expressions(visitor: &mut dyn TreeVisitor, expressions: &[Rc<Expression>]) {
+        for exp in expressions.iter() {
+            Self::walk_expression(visitor, exp);
+        }
+    }
+
+    pub fn walk_expression(visitor: &mut dyn TreeVisitor, expression: &Rc<Expression>) {
+        if !visitor.visit_expression(expression) {
+            return;
+        }
+        match expression.as_ref() {
+            Expression::NullLiteral(_) |
+            Expression::BooleanLiteral(_) |
+            Expression::NumericLiteral(_) |
+            Expression::StringLiteral(_) |
+            Expression::ThisLiteral(_) |
+            Expression::RegExpLiteral(_) |
+            Expression::XmlMarkup(_) |
+            Expression::ImportMeta(_) |
+            Expression::OptionalChainingPlaceholder(_) |
+            Expression::AnyType(_) |
+            Expression::VoidType(_) |
+            Expression::Invalidated(_) |
+            Expression::ReservedNamespace(_) => {},
+            Expression::QualifiedIdentifier(e) => Self::walk_qualified_identifier(visitor, e),
+            Expression::Paren(e) => Self::walk_expression(visitor, &e.expression),
+            Expression::Xml(e) => Self::walk_xml_element(visitor, &e.element),
+            Expression::XmlList(e) => Self::walk_xml_content(visitor, &e.content),
+            Expression::ArrayLiteral(e) => Self::walk_elements(visitor, &e.elements),
+            Expression::VectorLiteral(e) => {
+                Self::walk_expression(visitor, &e.element_type);
+                Self::walk_elements(visitor, &e.elements);
+            },
+            Expression::ObjectInitializer(e) => {
+                for field in e.fields.iter() {
+                    match field.as_ref() {
+                        InitializerField::Field { name, value, .. } => {
+                            match &name.0 {
+                                FieldName::Identifier(id) => Self::walk_qualified_identifier(visitor, id),
+                                FieldName::Brackets(exp) |
+                                FieldName::StringLiteral(exp) |
+                                FieldName::NumericLiteral(exp) => Self::walk_expression(visitor, exp),
+                            }
+                            if let Some(value) = value {
+                                Self::walk_expression(visitor, value);
+                            }
+                        },
+                        InitializerField::Rest((exp, _)) => Self::walk_expression(visitor, exp),
+                    }
+                }
+            },
+            Expression::Function(e) => Self::walk_function_common(visitor, &e.common),
+            Expression::New(e) => {
+                Self::walk_expression(visitor, &e.base);
+                if let Some(arguments) = &e.arguments {
+                    Self::walk_expressions(visitor, arguments);
+                }
+            },
+            Expression::Member(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_qualified_identifier(visitor, &e.identifier);
+            },
+            Expression::ComputedMember(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_expression(visitor, &e.key);
+            },
+            Expression::Descendants(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_qualified_identifier(visitor, &e.identifier);
+            },
+            Expression::Filter(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_expression(visitor, &e.test);
+            },
+            Expression::Super(e) => {
+                if let Some(object) = &e.object {
+                    Self::walk_expressions(visitor, object);
+                }
+            },
+            Expression::Call(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_expressions(visitor, &e.arguments);
+            },
+            Expression::WithTypeArguments(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_expressions(visitor, &e.arguments);
+            },
+            Expression::Unary(e) => Self::walk_expression(visitor, &e.expression),
+            Expression::OptionalChaining(e) => {
+                Self::walk_expression(visitor, &e.base);
+                Self::walk_expression(visitor, &e.expression);
+            },
+            Expression::Binary(e) => {
+                Self::walk_expression(visitor, &e.left);
+                Self::walk_expression(visitor, &e.right);
+            },
+            Expression::Conditional(e) => {
+                Self::walk_expression(visitor, &e.test);
+                Self::walk_expression(visitor, &e.consequent);
+                Self::walk_expression(visitor, &e.alternative);
+            },
+            Expression::Assignment(e) => {
+                Self::walk_expression(visitor, &e.left);
+                Self::walk_expression(visitor, &e.right);
+            },
+            Expression::Sequence(e) => {
+                Self::walk_expression(visitor, &e.left);
+                Self::walk_expression(visitor, &e.right);
+            },
+            Expression::NullableType(e) => Self::walk_expression(visitor, &e.base),
+            Expression::NonNullableType(e) => Self::walk_expression(visitor, &e.base),
+            Expression::ArrayType(e) => Self::walk_expression(visitor, &e.expression),
+            Expression::TupleType(e) => Self::walk_expressions(visitor, &e.expressions),
+            Expression::FunctionType(e) => {
+                for parameter in e.parameters.iter() {
+                    if let Some(exp) = &parameter.type_expression {
+                        Self::walk_expression(visitor, exp);
+                    }
+                }
+                if let Some(exp) = &e.result_type {
+                    Self::walk_expression(visitor, exp);
+                }
+            },
+        }
+    }
+
+    fn walk_qualified_identifier(visitor: &mut dyn TreeVisitor, id: &QualifiedIdentifier) {
+        if let Some(qualifier) = &id.qualifier {
+            Self::walk_expression(visitor, qualifier);
+        }
+        if let QualifiedIdentifierIdentifier::Brackets(exp) = &id.id {
+            Self::walk_expression(visitor, exp);
+        }
+    }
+
+    fn walk_elements(visitor: &mut dyn TreeVisitor, elements: &[Element]) {
+        for element in elements.iter() {
+            match element {
+                Element::Elision => {},
+                Element::Expression(exp) |
+                Element::Rest((exp, _)) => Self::walk_expression(visitor, exp),
+            }
+        }
+    }
+
+    fn walk_xml_element(visitor: &mut dyn TreeVisitor, element: &XmlElement) {
+        if let XmlTagName::Expression(exp) = &element.name {
+            Self::walk_expression(visitor, exp);
+        }
+        for attribute in element.attributes.iter() {
+            if let XmlAttributeValue::Expression(exp) = &attribute.value {
+                Self::walk_expression(visitor, exp);
+            }
+        }
+        if let Some(exp) = &element.attribute_expression {
+            Self::walk_expression(visitor, exp);
+        }
+        if let Some(content) = &element.content {
+            Self::walk_xml_content(visitor, content);
+        }
+    }
+
+    fn walk_xml_content(visitor: &mut dyn TreeVisitor, content: &[Rc<XmlContent>]) {
+        for content in content.iter() {
+            match content.as_ref() {
+                XmlContent::Element(element) => Self::walk_xml_element(visitor, element),
+                XmlContent::Expression(exp) => Self::walk_expression(visitor, exp),
+                _ => {},
+            }
+        }
+    }
+
+    fn walk_attributes(visitor: &mut dyn TreeVisitor, attributes: &[Attribute]) {
+        for attribute in attributes.iter() {
+            if let Attribute::Expression(exp) = attribute {
+                Self::walk_expression(visitor, exp);
+            }
+        }
+    }
+
+    fn walk_typed_destructuring(visitor: &mut dyn TreeVisitor, destructuring: &TypedDestructuring) {
+        Self::walk_expression(visitor, &destructuring.destructuring);
+        if let Some(exp) = &destructuring.type_annotation {
+            Self::walk_expression(visitor, exp);
+        }
+    }
+
+    fn walk_variable_bindings(visitor: &mut dyn TreeVisitor, bindings: &[Rc<VariableBinding>]) {
+        for binding in bindings.iter() {
+            Self::walk_typed_destructuring(visitor, &binding.destructuring);
+            if let Some(exp) = &binding.initializer {
+                Self::walk_expression(visitor, exp);
+            }
+        }
+    }
+
+    fn walk_function_common(visitor: &mut dyn TreeVisitor, common: &FunctionCommon) {
+        for parameter in common.signature.parameters.iter() {
+            Self::walk_typed_destructuring(visitor, &parameter.destructuring);
+            if let Some(exp) = &parameter.default_value {
+                Self::walk_expression(visitor, exp);
+            }
+        }
+        if let Some(exp) = &common.signature.result_type {
+            Self::walk_expression(visitor, exp);
+        }
+        match &common.body {
+            Some(FunctionBody::Expression(exp)) => Self::walk_expression(visitor, exp),
+            Some(FunctionBody::Block(block)) => Self::walk_directives(visitor, &block.directives),
+            None => {},
+        }
+    }
+}
@@ -1,12 +1,153 @@
 use std::collections::HashMap;
 use lazy_static::lazy_static;
 use maplit::hashmap;
+use serde::{Serialize, Deserialize};
 
 /// Converts a CSS [color constant](http://www.w3schools.com/css/css_colorsfull.asp) into an integer.
 pub fn css_color_constant_to_int(name: &str) -> Option<u32> {
     COLOR_MAP.get(&name.to_lowercase()).map(|i| *i)
 }
 
+/// A CSS color consisting of a 24-bit RGB integer and an alpha
+/// ratio from 0 to 1.
+///
+/// Flex represents translucent colors through two separate styles,
+/// such as `color` and `alpha` or `backgroundColor` and `backgroundAlpha`;
+/// `to_flex_styles()` and `from_flex_styles()` convert between both forms.
+///
+/// # Example
+///
+/// ```
+/// use as3_parser::ns::*;
+///
+/// let color = CssColor::from_hex("#ff000080").unwrap();
+/// assert_eq!(color.rgb, 0xff0000);
+/// assert_eq!(color.to_hex(), "#ff000080");
+/// assert_eq!(CssColor::from_hsl(120.0, 1.0, 0.25, 1.0).to_hex(), "#008000");
+/// assert_eq!(CssColor::from_name("transparent"), Some(CssColor::TRANSPARENT));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CssColor {
+    pub rgb: u32,
+    pub alpha: f64,
+}
+
+impl CssColor {
+    pub const TRANSPARENT: Self = Self { rgb: 0, alpha: 0.0 };
+
+    pub fn new(rgb: u32, alpha: f64) -> Self {
+        Self { rgb: rgb & 0xFFFFFF, alpha: alpha.clamp(0.0, 1.0) }
+    }
+
+    /// Constructs an opaque color.
+    pub fn opaque(rgb: u32) -> Self {
+        Self::new(rgb, 1.0)
+    }
+
+    /// Parses a hexadecimal color of 3, 4, 6 or 8 digits, optionally
+    /// preceded by `#`, where the last digits of the 4 and 8 digit
+    /// forms are the alpha.
+    pub fn from_hex(text: &str) -> Option<Self> {
+        let digits = text.strip_prefix('#').unwrap_or(text);
+        if !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digits: String = match digits.len() {
+            3 | 4 => digits.chars().flat_map(|ch| [ch, ch]).collect(),
+            6 | 8 => digits.to_owned(),
+            _ => return None,
+        };
+        let rgb = u32::from_str_radix(&digits[..6], 16).ok()?;
+        let alpha = if digits.len() == 8 { u32::from_str_radix(&digits[6..], 16).ok()? as f64 / 255.0 } else { 1.0 };
+        Some(Self::new(rgb, alpha))
+    }
+
+    /// Resolves a color name, including `transparent`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("transparent") {
+            return Some(Self::TRANSPARENT);
+        }
+        css_color_constant_to_int(name).map(Self::opaque)
+    }
+
+    /// Constructs a color from a hue in degrees and saturation,
+    /// lightness and alpha ratios from 0 to 1.
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let hue = hue.rem_euclid(360.0) / 360.0;
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let q = if lightness < 0.5 { lightness * (1.0 + saturation) } else { lightness + saturation - lightness * saturation };
+        let p = 2.0 * lightness - q;
+        let channel = |t: f64| -> u32 {
+            let t = t.rem_euclid(1.0);
+            let v = if t < 1.0 / 6.0 {
+                p + (q - p) * 6.0 * t
+            } else if t < 0.5 {
+                q
+            } else if t < 2.0 / 3.0 {
+                p + (q - p) * (2.0 / 3.0 - t) * 6.0
+            } else {
+                p
+            };
+            (v * 255.0).round().clamp(0.0, 255.0) as u32
+        };
+        Self::new((channel(hue + 1.0 / 3.0) << 16) | (channel(hue) << 8) | channel(hue - 1.0 / 3.0), alpha)
+    }
+
+    /// Constructs a color from a 32-bit ARGB integer, as used
+    /// by the Flash display list.
+    pub fn from_argb(argb: u32) -> Self {
+        Self::new(argb & 0xFFFFFF, (argb >> 24) as f64 / 255.0)
+    }
+
+    /// Returns a 32-bit ARGB integer.
+    pub fn to_argb(&self) -> u32 {
+        (self.alpha_byte() << 24) | self.rgb
+    }
+
+    /// Constructs a color from the separate color and alpha
+    /// styles of Flex, such as `backgroundColor` and `backgroundAlpha`.
+    pub fn from_flex_styles(color: u32, alpha: f64) -> Self {
+        Self::new(color, alpha)
+    }
+
+    /// Returns the values of the separate color and alpha
+    /// styles of Flex.
+    pub fn to_flex_styles(&self) -> (u32, f64) {
+        (self.rgb, self.alpha)
+    }
+
+    pub fn red(&self) -> u8 {
+        (self.rgb >> 16) as u8
+    }
+
+    pub fn green(&self) -> u8 {
+        (self.rgb >> 8) as u8
+    }
+
+    pub fn blue(&self) -> u8 {
+        self.rgb as u8
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.alpha >= 1.0
+    }
+
+    fn alpha_byte(&self) -> u32 {
+        (self.alpha * 255.0).round() as u32
+    }
+
+    /// Returns a lowercase hexadecimal color of 6 digits, or 8 digits
+    /// if the color is not opaque.
+    pub fn to_hex(&self) -> String {
+        if self.is_opaque() {
+            format!("#{:06x}", self.rgb)
+        } else {
+            format!("#{:06x}{:02x}", self.rgb, self.alpha_byte())
+        }
+    }
+}
+
 lazy_static! {
     /// Map color constant names to 24-bit RGB integer values.
     ///
@@ -42,7 +183,7 @@ lazy_static! {
         "steelblue".into() => 0x4682b4,
         "darkslateblue".into() => 0x483d8b,
         "mediumturquoise".into() => 0x48d1cc,
-        "indigo ".into() => 0x4b0082,
+        "indigo".into() => 0x4b0082,
         "darkolivegreen".into() => 0x556b2f,
         "cadetblue".into() => 0x5f9ea0,
         "cornflowerblue".into() => 0x6495ed,
@@ -93,7 +234,7 @@ lazy_static! {
         "darkkhaki".into() => 0xbdb76b,
         "silver".into() => 0xc0c0c0,
         "mediumvioletred".into() => 0xc71585,
-        "indianred ".into() => 0xcd5c5c,
+        "indianred".into() => 0xcd5c5c,
         "peru".into() => 0xcd853f,
         "chocolate".into() => 0xd2691e,
         "tan".into() => 0xd2b48c,
@@ -17,6 +17,22 @@ struct Arguments {
 
     #[arg(short, long)]
     css: bool,
+
+    /// For MXML, parses `<fx:Script>` elements.
+    #[arg(long)]
+    mxml_scripts: bool,
+
+    /// For MXML, parses data bindings.
+    #[arg(long)]
+    mxml_bindings: bool,
+
+    /// For MXML, parses `<fx:Style>` elements.
+    #[arg(long)]
+    mxml_styles: bool,
+
+    /// For MXML, the comma-separated events whose attributes are parsed as event handlers.
+    #[arg(long, value_delimiter = ',')]
+    mxml_events: Vec<String>,
 }
 
 fn main() -> io::Result<()> {
@@ -30,8 +46,19 @@ fn main() -> io::Result<()> {
     let source_path_diagnostics = FlexPath::new_native(&source_path).change_extension(".diag").to_string_with_flex_separator();
     let source_content = fs::read_to_string(&source_path)?;
     let compilation_unit = CompilationUnit::new(Some(source_path), source_content);
+    let mut mxml_events = MxmlEventRegistry::new();
+    for name in arguments.mxml_events.iter() {
+        mxml_events.add_event(name);
+    }
+    let options = ParserOptions {
+        parse_mxml_scripts: arguments.mxml_scripts,
+        parse_mxml_bindings: arguments.mxml_bindings,
+        parse_mxml_styles: arguments.mxml_styles,
+        mxml_events: Rc::new(mxml_events),
+        ..default()
+    };
     if arguments.mxml {
-        let document = ParserFacade(&compilation_unit, default()).parse_mxml();
+        let document = ParserFacade(&compilation_unit, options).parse_mxml();
         if arguments.file_log {
             fs::write(&source_path_ast_json, serde_json::to_string_pretty(&document).unwrap())?;
         }
@@ -41,7 +68,7 @@ fn main() -> io::Result<()> {
             fs::write(&source_path_ast_json, serde_json::to_string_pretty(&document).unwrap())?;
         }
     } else {
-        let program = ParserFacade(&compilation_unit, default()).parse_program();
+        let program = ParserFacade(&compilation_unit, options).parse_program();
         if arguments.file_log {
             fs::write(&source_path_ast_json, serde_json::to_string_pretty(&program).unwrap())?;
         }
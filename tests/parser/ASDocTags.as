@@ -0,0 +1,9 @@
+/**
+ * Main body.
+ * @exampleText Example text.
+ * @helpid 3001
+ * @includeExample examples/X.as
+ * @keyword x, y
+ * @tiptext Tip text.
+ */
+var x
\ No newline at end of file